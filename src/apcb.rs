@@ -2,10 +2,21 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use crate::types::{ApcbContext, Error, FileSystemError, PtrMut, Result};
+use crate::types::{
+    ApcbContext, ApcbEdit, Error, FileSystemError, MemDfeSearchVersion,
+    PriorityLevel, PtrMut, Result,
+};
+#[cfg(feature = "std")]
+use crate::types::{ApcbDiffItem, ApcbWarning};
 
-use crate::entry::EntryItemBody;
+use crate::entry::{
+    EntryItem, EntryItemBody, EntryMutItem, MemDfeSearchElements,
+};
 use crate::group::{GroupItem, GroupMutItem};
+use crate::ondisk::gnb::{EarlyPcieConfigBody, EarlyPcieConfigElement};
+use crate::ondisk::memory::{
+    ConsoleOutControl, DdrPostPackageRepairBody, DdrPostPackageRepairElement,
+};
 use crate::ondisk::GroupId;
 use crate::ondisk::ENTRY_ALIGNMENT;
 use crate::ondisk::ENTRY_HEADER;
@@ -15,12 +26,13 @@ use crate::ondisk::V2_HEADER;
 use crate::ondisk::V3_HEADER_EXT;
 use crate::ondisk::{
     take_body_from_collection, take_body_from_collection_mut,
-    take_header_from_collection, take_header_from_collection_mut,
-    HeaderWithTail, ParameterAttributes, SequenceElementAsBytes,
+    take_header_from_collection, take_header_from_collection_mut, BaudRate,
+    GnbEntryId, HeaderWithTail, MemoryEntryId, ParameterAttributes,
+    SequenceElementAsBytes, TokenEntryId,
 };
 pub use crate::ondisk::{
-    BoardInstances, ContextType, EntryCompatible, EntryId, Parameter,
-    PriorityLevels,
+    BoardInstances, ContextFormat, ContextType, EntryCompatible, EntryId,
+    Parameter, PriorityLevels,
 };
 use crate::token_accessors::{Tokens, TokensMut};
 use core::convert::TryInto;
@@ -31,27 +43,90 @@ use num_traits::ToPrimitive;
 use pre::pre;
 use static_assertions::const_assert;
 use zerocopy::AsBytes;
+use zerocopy::FromBytes;
 use zerocopy::LayoutVerified;
 
 // The following imports are only used for std enviroments and serde.
 #[cfg(feature = "std")]
 extern crate std;
 #[cfg(feature = "serde")]
-use crate::entry::{EntryItem, SerdeEntryItem};
+use crate::entry::SerdeEntryItem;
 #[cfg(feature = "serde")]
 use crate::group::SerdeGroupItem;
+#[cfg(feature = "std")]
+use crate::ondisk::memory::{
+    platform_specific_override, Ddr4DataBusElement, Ddr4OdtPatElement,
+    ErrorOutControl112, ErrorOutControl116, LrdimmDdr4CadBusElement,
+    LrdimmDdr4DataBusElement, LrdimmDdr4OdtPatElement, MaxFreqElement,
+    RdimmDdr4CadBusElement, UdimmDdr4CadBusElement,
+};
+#[cfg(feature = "std")]
+use crate::ondisk::BoardInstance;
+#[cfg(feature = "std")]
+use crate::ondisk::ParameterTokenConfig;
+#[cfg(feature = "std")]
+use crate::ondisk::ReservedBits;
+#[cfg(feature = "std")]
+use crate::ondisk::{
+    BoolToken, ByteToken, CbsEntryId, CcxEntryId, DfEntryId, DwordToken,
+    FchEntryId, PspEntryId, WordToken,
+};
+use crate::ondisk::{Parameters, ParametersIter};
+#[cfg(feature = "serde")]
+use crate::serde_proxy::{Hooks, ProxyDeserializer};
+#[cfg(feature = "std")]
+use crate::types::{
+    SiliconGeneration, SiliconIncompatibility, UnknownBitsReport,
+};
 #[cfg(feature = "serde")]
 use serde::de::{Deserialize, Deserializer};
 #[cfg(feature = "serde")]
 use serde::ser::{Serialize, SerializeStruct, Serializer};
-#[cfg(feature = "serde")]
+#[cfg(feature = "std")]
 use std::borrow::Cow;
+#[cfg(feature = "serde")]
+use std::cell::RefCell;
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+/// Calculates V3_HEADER_EXT::integrity_sign from the groups.  See
+/// ApcbIoOptions::integrity_sign_fn.
+pub type IntegritySignFn = fn(&[u8]) -> [u8; 32];
+
+/// Calculates V3_HEADER_EXT::header_checksum from the V3_HEADER_EXT (the
+/// header_checksum of which is 0 in the argument).  See
+/// ApcbIoOptions::header_checksum_fn.
+pub type HeaderChecksumFn = fn(&V3_HEADER_EXT) -> u8;
 
 #[derive(Clone)]
 pub struct ApcbIoOptions {
     pub check_checksum: bool,
     pub check_signature_ending: bool,
     pub context: ApcbContext,
+    /// If set, load (and Apcb::validate) do not check V2_HEADER::version
+    /// nor GROUP_HEADER::version.
+    pub allow_any_version: bool,
+    /// If set, the Apcb records each mutating operation (see
+    /// Apcb::edit_log).  Only has an effect with the "std" feature.
+    pub record_edits: bool,
+    /// If set, save writes this unique_apcb_instance instead of
+    /// incrementing the existing one (for reproducible builds).
+    pub unique_apcb_instance: Option<u32>,
+    /// If set, load fails if V3_HEADER_EXT::header_checksum differs from
+    /// what header_checksum_fn calculates.  Has no effect if
+    /// header_checksum_fn is not set.
+    pub check_header_checksum: bool,
+    /// If set, create, save, save_no_inc, to_writer and compact_into
+    /// store the result of calling this into
+    /// V3_HEADER_EXT::header_checksum.  Otherwise, header_checksum is left
+    /// alone.  Note: The AMD docs we have do not specify how
+    /// header_checksum is calculated, so the caller has to supply that.
+    pub header_checksum_fn: Option<HeaderChecksumFn>,
+    /// If set, save (and save_no_inc, to_writer and compact_into) stores
+    /// the result of calling this on the groups (that is, everything after
+    /// the headers, up to apcb_size) into V3_HEADER_EXT::integrity_sign.
+    /// Otherwise, integrity_sign is left alone.
+    pub integrity_sign_fn: Option<IntegritySignFn>,
 }
 
 impl Default for ApcbIoOptions {
@@ -60,6 +135,12 @@ impl Default for ApcbIoOptions {
             check_checksum: true,
             check_signature_ending: true,
             context: ApcbContext::default(),
+            allow_any_version: false,
+            record_edits: false,
+            unique_apcb_instance: None,
+            check_header_checksum: false,
+            header_checksum_fn: None,
+            integrity_sign_fn: None,
         }
     }
 }
@@ -77,6 +158,24 @@ impl ApcbIoOptions {
     pub fn context(&self) -> ApcbContext {
         self.context
     }
+    pub fn allow_any_version(&self) -> bool {
+        self.allow_any_version
+    }
+    pub fn record_edits(&self) -> bool {
+        self.record_edits
+    }
+    pub fn unique_apcb_instance(&self) -> Option<u32> {
+        self.unique_apcb_instance
+    }
+    pub fn check_header_checksum(&self) -> bool {
+        self.check_header_checksum
+    }
+    pub fn header_checksum_fn(&self) -> Option<HeaderChecksumFn> {
+        self.header_checksum_fn
+    }
+    pub fn integrity_sign_fn(&self) -> Option<IntegritySignFn> {
+        self.integrity_sign_fn
+    }
     pub fn with_check_checksum(&mut self, value: bool) -> &mut Self {
         self.check_checksum = value;
         self
@@ -89,6 +188,36 @@ impl ApcbIoOptions {
         self.context = value;
         self
     }
+    pub fn with_allow_any_version(&mut self, value: bool) -> &mut Self {
+        self.allow_any_version = value;
+        self
+    }
+    pub fn with_record_edits(&mut self, value: bool) -> &mut Self {
+        self.record_edits = value;
+        self
+    }
+    pub fn with_unique_apcb_instance(&mut self, value: u32) -> &mut Self {
+        self.unique_apcb_instance = Some(value);
+        self
+    }
+    pub fn with_check_header_checksum(&mut self, value: bool) -> &mut Self {
+        self.check_header_checksum = value;
+        self
+    }
+    pub fn with_header_checksum_fn(
+        &mut self,
+        value: HeaderChecksumFn,
+    ) -> &mut Self {
+        self.header_checksum_fn = Some(value);
+        self
+    }
+    pub fn with_integrity_sign_fn(
+        &mut self,
+        value: IntegritySignFn,
+    ) -> &mut Self {
+        self.integrity_sign_fn = Some(value);
+        self
+    }
     pub fn build(&self) -> Self {
         self.clone()
     }
@@ -97,8 +226,73 @@ impl ApcbIoOptions {
 #[cfg_attr(feature = "std", derive(Clone))]
 pub struct Apcb<'a> {
     context: ApcbContext,
-    used_size: usize,
+    /// Number of bytes used by the groups (after the headers).
+    groups_used_size: usize,
     pub backing_store: PtrMut<'a, [u8]>,
+    /// Human annotations of entries ("_comment" in the serde
+    /// representation).  They are not stored in the blob.
+    #[cfg(feature = "serde")]
+    comments: BTreeMap<EntryCommentKey, String>,
+    /// Mutating operations so far, if ApcbIoOptions::record_edits was set.
+    #[cfg(feature = "std")]
+    edit_log: Option<Vec<ApcbEdit>>,
+    /// If set, save writes this unique_apcb_instance.  See
+    /// ApcbIoOptions::unique_apcb_instance.
+    pinned_unique_apcb_instance: Option<u32>,
+    /// See ApcbIoOptions::header_checksum_fn.
+    header_checksum_fn: Option<HeaderChecksumFn>,
+    /// See ApcbIoOptions::integrity_sign_fn.
+    integrity_sign_fn: Option<IntegritySignFn>,
+    /// See ApcbIoOptions::allow_any_version.
+    allow_any_version: bool,
+}
+
+/// (group_id, entry_id, instance_id, board_instance_mask)
+#[cfg(feature = "serde")]
+type EntryCommentKey = (u16, u16, u16, u16);
+
+#[cfg(feature = "serde")]
+fn entry_comment_key(header: &ENTRY_HEADER) -> EntryCommentKey {
+    (
+        header.group_id.get(),
+        header.entry_id.get(),
+        header.instance_id.get(),
+        header.board_instance_mask.get(),
+    )
+}
+
+#[cfg(feature = "serde")]
+fn entry_comment_key_of(
+    entry_id: EntryId,
+    instance_id: u16,
+    board_instance_mask: BoardInstances,
+) -> EntryCommentKey {
+    (
+        entry_id.group_id().to_u16().unwrap(),
+        entry_id.type_id(),
+        instance_id,
+        u16::from(board_instance_mask),
+    )
+}
+
+/// An entry, serialized together with its comment (if any).
+#[cfg(feature = "serde")]
+struct CommentedEntryItem<'a, 'b> {
+    entry: &'b EntryItem<'a>,
+    comment: Option<&'b str>,
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for CommentedEntryItem<'_, '_> {
+    fn serialize<S>(
+        &self,
+        serializer: S,
+    ) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.entry.serialize_with_comment(self.comment, serializer)
+    }
 }
 
 #[cfg(feature = "serde")]
@@ -135,11 +329,88 @@ impl<'a> schemars::JsonSchema for Apcb<'a> {
     }
 }
 
+#[cfg(feature = "schemars")]
+impl Apcb<'_> {
+    /// Returns the JSON Schema of the serde representation of an Apcb (for
+    /// example in order to validate configuration files in an editor).
+    pub fn json_schema_document() -> schemars::schema::RootSchema {
+        let gen = schemars::gen::SchemaGenerator::default();
+        gen.into_root_schema_for::<Apcb<'static>>()
+    }
+}
+
+#[cfg(feature = "serde-yaml")]
+impl Apcb<'_> {
+    /// Serializes the Apcb to YAML (the same representation as with any
+    /// other serde serializer).
+    pub fn to_yaml_string(&self) -> Result<String> {
+        Ok(serde_yaml::to_string(self)?)
+    }
+
+    /// Deserializes an Apcb from YAML.  The resulting Apcb owns its
+    /// buffer.
+    pub fn from_yaml_str(s: &str) -> Result<Self> {
+        Ok(serde_yaml::from_str(s)?)
+    }
+}
+
 #[cfg(feature = "serde")]
 impl<'a> Apcb<'a> {
     pub fn context(&self) -> ApcbContext {
         self.context
     }
+    /// Returns the human annotation of the given entry, if any.
+    pub fn entry_comment(
+        &self,
+        entry_id: EntryId,
+        instance_id: u16,
+        board_instance_mask: BoardInstances,
+    ) -> Option<&str> {
+        self.comments
+            .get(&entry_comment_key_of(
+                entry_id,
+                instance_id,
+                board_instance_mask,
+            ))
+            .map(|comment| comment.as_str())
+    }
+    /// Sets (or, if COMMENT is None, removes) the human annotation of the
+    /// given entry.  It's only kept in memory and in the serde
+    /// representation, not in the blob.
+    pub fn set_entry_comment(
+        &mut self,
+        entry_id: EntryId,
+        instance_id: u16,
+        board_instance_mask: BoardInstances,
+        comment: Option<String>,
+    ) {
+        let key =
+            entry_comment_key_of(entry_id, instance_id, board_instance_mask);
+        match comment {
+            Some(comment) => {
+                self.comments.insert(key, comment);
+            }
+            None => {
+                self.comments.remove(&key);
+            }
+        }
+    }
+    /// Deserializes an Apcb like Deserialize does, but also returns the
+    /// names ("STRUCT_NAME.FIELD_NAME") of all the fields that were absent
+    /// from the input and were defaulted.
+    pub fn deserialize_reporting_defaults<'de, D>(
+        deserializer: D,
+    ) -> core::result::Result<(Self, Vec<String>), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let defaulted = RefCell::new(Vec::new());
+        let apcb = Self::deserialize(ProxyDeserializer::new(
+            deserializer,
+            Hooks { defaulted: Some(&defaulted), ..Hooks::default() },
+        ))?;
+        Ok((apcb, defaulted.into_inner()))
+    }
     //    type Error = Error;
     fn try_from(serde_apcb: SerdeApcb) -> Result<Self> {
         let buf = Cow::from(vec![0xFFu8; Self::MAX_SIZE]);
@@ -179,6 +450,9 @@ impl<'a> Apcb<'a> {
             )?;
         }
         for e in serde_apcb.entries {
+            if let Some(comment) = e.comment {
+                apcb.comments.insert(entry_comment_key(&e.header), comment);
+            }
             let buf = &e.body[..];
             apcb.insert_entry(
                 EntryId::decode(
@@ -232,6 +506,16 @@ impl<'a> Serialize for Apcb<'a> {
             .map(|h| **h);
         state.serialize_field("v3_header_ext", &v3_header_ext)?;
         state.serialize_field("groups", &groups)?;
+        let entries = entries
+            .iter()
+            .map(|entry| CommentedEntryItem {
+                entry,
+                comment: self
+                    .comments
+                    .get(&entry_comment_key(entry.header))
+                    .map(|comment| comment.as_str()),
+            })
+            .collect::<Vec<_>>();
         state.serialize_field("entries", &entries)?;
         state.end()
     }
@@ -260,6 +544,8 @@ pub struct ApcbIter<'a> {
     context: ApcbContext,
     buf: &'a [u8],
     remaining_used_size: usize,
+    /// Number of groups that next() will still yield (for size_hint).
+    remaining_count: usize,
 }
 
 impl<'a> ApcbIterMut<'a> {
@@ -396,12 +682,30 @@ impl<'a> ApcbIter<'a> {
         Ok(GroupItem { context, header, buf: body, used_size: body_len })
     }
 
+    /// Walks the group headers (without consuming self) in order to find
+    /// out how many groups next() will yield.  Iteration stops at the first
+    /// inconsistent group header, so this does, too.
+    fn count_groups(&self) -> usize {
+        let mut iter = ApcbIter {
+            context: self.context,
+            buf: self.buf,
+            remaining_used_size: self.remaining_used_size,
+            remaining_count: 0,
+        };
+        let mut count = 0usize;
+        while iter.remaining_used_size > 0 && iter.next1().is_ok() {
+            count += 1;
+        }
+        count
+    }
+
     pub(crate) fn next1(&mut self) -> Result<GroupItem<'a>> {
         assert!(self.remaining_used_size != 0, "Internal error");
         let item = Self::next_item(self.context, &mut self.buf)?;
         let group_size = item.header.group_size.get() as usize;
         if group_size <= self.remaining_used_size {
             self.remaining_used_size -= group_size;
+            self.remaining_count = self.remaining_count.saturating_sub(1);
             Ok(item)
         } else {
             Err(Error::FileSystem(
@@ -411,7 +715,8 @@ impl<'a> ApcbIter<'a> {
         }
     }
     /// Validates the entries (recursively).  Also consumes iterator.
-    pub(crate) fn validate(mut self) -> Result<()> {
+    /// Unless ALLOW_ANY_VERSION is set, also checks GROUP_HEADER::version.
+    pub(crate) fn validate(mut self, allow_any_version: bool) -> Result<()> {
         while self.remaining_used_size > 0 {
             let item = self.next1()?;
             GroupId::from_u16(item.header.group_id.get()).ok_or(
@@ -420,6 +725,12 @@ impl<'a> ApcbIter<'a> {
                     "GROUP_HEADER::group_id",
                 ),
             )?;
+            if !allow_any_version && item.version() != GROUP_HEADER::VERSION {
+                return Err(Error::GroupVersionMismatch {
+                    group: item.header.group_id.get(),
+                    version: item.version(),
+                });
+            }
             item.entries().validate()?;
         }
         Ok(())
@@ -433,10 +744,93 @@ impl<'a> Iterator for ApcbIter<'a> {
         if self.remaining_used_size == 0 {
             return None;
         }
-        self.next1().ok()
+        let item = self.next1().ok();
+        if item.is_none() {
+            // Stop at the first inconsistent group header.
+            self.remaining_used_size = 0;
+            self.remaining_count = 0;
+        }
+        item
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining_count, Some(self.remaining_count))
+    }
+}
+
+impl<'a> ExactSizeIterator for ApcbIter<'a> {}
+
+/// Aggregate counts over an Apcb, as returned by Apcb::stats.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ApcbStats {
+    pub group_count: usize,
+    pub entry_count: usize,
+    /// Total number of tokens over all token entries.
+    pub token_count: usize,
+    /// Size of the APCB (including headers), in bytes.
+    pub total_size: usize,
+}
+
+/// A position within the groups of an Apcb.  In contrast to ApcbIter, this
+/// does not borrow the Apcb--so a scan can be paused and resumed later
+/// without restarting from the first group.
+/// Note: If the Apcb is modified in the mean time, the cursor is invalid.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct GroupCursor {
+    offset: usize,
+}
+
+impl GroupCursor {
+    /// Offset (in bytes) of the next group, relative to the beginning of
+    /// the groups.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the group at the cursor and advances the cursor past it.
+    pub fn next<'b>(&mut self, apcb: &'b Apcb<'_>) -> Option<GroupItem<'b>> {
+        if self.offset >= apcb.groups_used_size {
+            return None;
+        }
+        let mut buf = apcb
+            .beginning_of_groups()
+            .ok()?
+            .get(self.offset..apcb.groups_used_size)?;
+        let item = ApcbIter::next_item(apcb.context, &mut buf).ok()?;
+        let group_size = item.header.group_size.get() as usize;
+        self.offset = self.offset.checked_add(group_size)?;
+        Some(item)
     }
 }
 
+/// Returns the groups whose entries this crate knows about (that is, all
+/// GroupIds except Unknown).
+pub fn supported_groups() -> &'static [GroupId] {
+    &[
+        GroupId::Psp,
+        GroupId::Ccx,
+        GroupId::Df,
+        GroupId::Memory,
+        GroupId::Gnb,
+        GroupId::Fch,
+        GroupId::Cbs,
+        GroupId::Oem,
+        GroupId::Token,
+    ]
+}
+
+/// Returns the V2_HEADER::version that Apcb::create writes.  Loading
+/// supports this version and the (older) Naples one.
+pub fn crate_format_version() -> u16 {
+    Apcb::ROME_VERSION
+}
+
+/// Returns whether the crate was built with serde support (that is,
+/// whether Apcb implements Serialize and Deserialize).
+pub fn supports_serde() -> bool {
+    cfg!(feature = "serde")
+}
+
 impl<'a> Apcb<'a> {
     const NAPLES_VERSION: u16 = 0x20;
     const ROME_VERSION: u16 = 0x30;
@@ -549,12 +943,33 @@ impl<'a> Apcb<'a> {
         Ok(&mut self.backing_store[offset..])
     }
 
+    /// Returns an iterator over the groups.  Its length (see
+    /// ExactSizeIterator) is determined up front by walking the group
+    /// headers once.
     pub fn groups(&self) -> Result<ApcbIter<'_>> {
-        Ok(ApcbIter {
+        let mut result = ApcbIter {
             context: self.context,
             buf: self.beginning_of_groups()?,
-            remaining_used_size: self.used_size,
-        })
+            remaining_used_size: self.groups_used_size,
+            remaining_count: 0,
+        };
+        result.remaining_count = result.count_groups();
+        Ok(result)
+    }
+
+    /// Returns how many groups there are.
+    pub fn group_count(&self) -> Result<usize> {
+        Ok(self.groups()?.len())
+    }
+
+    /// Returns how many entries there are, summed over all the groups.
+    pub fn entry_count(&self) -> Result<usize> {
+        Ok(self.groups()?.map(|group| group.entries().count()).sum())
+    }
+
+    /// Returns a cursor positioned at the first group.
+    pub fn group_cursor(&self) -> GroupCursor {
+        GroupCursor::default()
     }
 
     pub fn group(&self, group_id: GroupId) -> Result<Option<GroupItem<'_>>> {
@@ -564,13 +979,140 @@ impl<'a> Apcb<'a> {
     /// Validates the contents.
     /// If ABL0_VERSION is Some, also validates against that AGESA
     /// bootloader version.
+    /// Structural problems (sizes, alignment, token entry format) are
+    /// reported as Error::FileSystemAt, which says where the problem is.
     pub fn validate(&self, abl0_version: Option<u32>) -> Result<()> {
-        self.groups()?.validate()?;
+        self.validate_structure()?;
+        self.groups()?.validate(self.allow_any_version)?;
         self.ensure_abl0_compatibility(abl0_version)
     }
 
+    /// Checks that apcb_size, the group sizes and the entry sizes are
+    /// consistent (with entries aligned to ENTRY_ALIGNMENT), and that token
+    /// entries are well-formed (and sorted if they say they are).
+    fn validate_structure(&self) -> Result<()> {
+        fn inconsistent(
+            field: &'static str,
+            group_id: Option<u16>,
+            entry_id: Option<u16>,
+            offset: usize,
+        ) -> Error {
+            Error::FileSystemAt {
+                error: FileSystemError::InconsistentHeader,
+                field,
+                group_id,
+                entry_id,
+                offset,
+            }
+        }
+        let beginning_of_groups = self.beginning_of_groups()?;
+        let groups_offset =
+            self.backing_store.len() - beginning_of_groups.len();
+        if self.header()?.apcb_size.get() as usize
+            != groups_offset + self.groups_used_size
+        {
+            return Err(inconsistent("V2_HEADER::apcb_size", None, None, 0));
+        }
+        let groups = &beginning_of_groups[..self.groups_used_size];
+        let mut group_offset = 0;
+        while group_offset < groups.len() {
+            let offset = groups_offset + group_offset;
+            let mut buf = &groups[group_offset..];
+            let group_header =
+                take_header_from_collection::<GROUP_HEADER>(&mut buf)
+                    .ok_or(inconsistent("GROUP_HEADER", None, None, offset))?;
+            let group_id = Some(group_header.group_id.get());
+            let group_size = group_header.group_size.get() as usize;
+            if group_size < size_of::<GROUP_HEADER>()
+                || group_size > groups.len() - group_offset
+            {
+                return Err(inconsistent(
+                    "GROUP_HEADER::group_size",
+                    group_id,
+                    None,
+                    offset,
+                ));
+            }
+            let group = &groups[group_offset..group_offset + group_size];
+            let mut entry_offset = size_of::<GROUP_HEADER>();
+            while entry_offset < group.len() {
+                let offset = offset + entry_offset;
+                let mut buf = &group[entry_offset..];
+                let entry_header =
+                    take_header_from_collection::<ENTRY_HEADER>(&mut buf)
+                        .ok_or(inconsistent(
+                            "ENTRY_HEADER",
+                            group_id,
+                            None,
+                            offset,
+                        ))?;
+                let entry_id = Some(entry_header.entry_id.get());
+                let entry_size = entry_header.entry_size.get() as usize;
+                let body = entry_size
+                    .checked_sub(size_of::<ENTRY_HEADER>())
+                    .and_then(|body_size| buf.get(..body_size))
+                    .ok_or(inconsistent(
+                        "ENTRY_HEADER::entry_size",
+                        group_id,
+                        entry_id,
+                        offset,
+                    ))?;
+                if entry_header.context_type == ContextType::Tokens as u8 {
+                    if entry_header.unit_size as usize
+                        != size_of::<TOKEN_ENTRY>()
+                        || entry_header.key_size != 4
+                        || entry_header.key_pos != 0
+                    {
+                        return Err(inconsistent(
+                            "ENTRY_HEADER::unit_size",
+                            group_id,
+                            entry_id,
+                            offset,
+                        ));
+                    }
+                    let tokens = LayoutVerified::<_, [TOKEN_ENTRY]>::new_slice_unaligned(body)
+                        .ok_or(inconsistent(
+                            "ENTRY_HEADER::entry_size",
+                            group_id,
+                            entry_id,
+                            offset,
+                        ))?;
+                    if entry_header.context_format
+                        == ContextFormat::SortAscending as u8
+                    {
+                        for (i, pair) in tokens.windows(2).enumerate() {
+                            if pair[0].key.get() > pair[1].key.get() {
+                                return Err(inconsistent(
+                                    "TOKEN_ENTRY::key",
+                                    group_id,
+                                    entry_id,
+                                    offset
+                                        + size_of::<ENTRY_HEADER>()
+                                        + (i + 1) * size_of::<TOKEN_ENTRY>(),
+                                ));
+                            }
+                        }
+                    }
+                }
+                entry_offset += entry_size
+                    + (ENTRY_ALIGNMENT - entry_size % ENTRY_ALIGNMENT)
+                        % ENTRY_ALIGNMENT;
+            }
+            if entry_offset != group.len() {
+                return Err(inconsistent(
+                    "GROUP_HEADER::group_size",
+                    group_id,
+                    None,
+                    offset,
+                ));
+            }
+            group_offset += group_size;
+        }
+        Ok(())
+    }
+
     pub fn groups_mut(&mut self) -> Result<ApcbIterMut<'_>> {
-        let used_size = self.used_size;
+        let used_size = self.groups_used_size;
         Ok(ApcbIterMut {
             context: self.context,
             buf: &mut *self.beginning_of_groups_mut()?,
@@ -585,12 +1127,73 @@ impl<'a> Apcb<'a> {
         Ok(self.groups_mut()?.find(|group| group.id() == group_id))
     }
 
-    /// Note: BOARD_INSTANCE_MASK needs to be exact.
+    /// Iterates over the entries of all the groups, yielding the id of the
+    /// group each entry is in along with the entry.
+    pub fn all_entries(
+        &self,
+    ) -> Result<impl Iterator<Item = (GroupId, EntryItem<'_>)>> {
+        Ok(self.groups()?.flat_map(|group| {
+            let group_id = group.id();
+            group.into_entries().map(move |entry| (group_id, entry))
+        }))
+    }
+
+    /// Like all_entries, but yields mutable entries.
+    pub fn all_entries_mut(
+        &mut self,
+    ) -> Result<impl Iterator<Item = (GroupId, EntryMutItem<'_>)>> {
+        Ok(self.groups_mut()?.flat_map(|group| {
+            let group_id = group.id();
+            group.into_entries_mut().map(move |entry| (group_id, entry))
+        }))
+    }
+
+    /// Iterates over the entries of the OEM group (if any), yielding
+    /// (entry type id, raw entry body) for each.  The OEM group is opaque
+    /// to us, so its entries are not interpreted at all--not even according
+    /// to their context_type.
+    pub fn oem_entries_raw(
+        &self,
+    ) -> Result<impl Iterator<Item = (u16, &'_ [u8])>> {
+        let mut buf: &[u8] = match self.group(GroupId::Oem)? {
+            Some(group) => &group.buf[..group.used_size],
+            None => &[],
+        };
+        Ok(core::iter::from_fn(move || {
+            let header = take_header_from_collection::<ENTRY_HEADER>(&mut buf)?;
+            let payload_size = (header.entry_size.get() as usize)
+                .checked_sub(size_of::<ENTRY_HEADER>())?;
+            let body = take_body_from_collection(
+                &mut buf,
+                payload_size,
+                ENTRY_ALIGNMENT,
+            )?;
+            Some((header.entry_id.get(), body))
+        }))
+    }
+
+    /// Deletes the entry with the given ENTRY_ID, INSTANCE_ID and
+    /// BOARD_INSTANCE_MASK.
+    /// Note: BOARD_INSTANCE_MASK needs to be exact--entries whose mask
+    /// merely overlaps it are not deleted.  See delete_entry_exact.
     pub fn delete_entry(
         &mut self,
         entry_id: EntryId,
         instance_id: u16,
         board_instance_mask: BoardInstances,
+    ) -> Result<()> {
+        self.delete_entry_exact(entry_id, instance_id, board_instance_mask)
+    }
+    /// Deletes only the entry whose board_instance_mask equals
+    /// BOARD_INSTANCE_MASK bit for bit (with the given ENTRY_ID and
+    /// INSTANCE_ID).  Entries for other (even overlapping) board masks are
+    /// left alone; if there is no exact match, this fails with
+    /// EntryNotFound.
+    pub fn delete_entry_exact(
+        &mut self,
+        entry_id: EntryId,
+        instance_id: u16,
+        board_instance_mask: BoardInstances,
     ) -> Result<()> {
         let group_id = entry_id.group_id();
         let mut group =
@@ -601,6 +1204,110 @@ impl<'a> Apcb<'a> {
             let size_diff = size_diff as i64;
             self.resize_group_by(group_id, -size_diff)?;
         }
+        self.move_entry_comment(
+            entry_id,
+            instance_id,
+            board_instance_mask,
+            None,
+        );
+        self.record_edit(ApcbEdit::DeleteEntry {
+            entry_id,
+            instance_id,
+            board_instance_mask,
+        });
+        Ok(())
+    }
+    /// Returns the (whole) board_instance_mask of the entry with the given
+    /// ENTRY_ID and INSTANCE_ID that applies to BOARD_INSTANCE_MASK (see
+    /// GroupItem::entry_compatible).
+    pub fn entry_board_mask(
+        &self,
+        entry_id: EntryId,
+        instance_id: u16,
+        board_instance_mask: BoardInstances,
+    ) -> Result<BoardInstances> {
+        let group =
+            self.group(entry_id.group_id())?.ok_or(Error::GroupNotFound)?;
+        let entry = group
+            .entry_compatible(entry_id, instance_id, board_instance_mask)
+            .ok_or(Error::EntryNotFound {
+                entry_id,
+                instance_id,
+                board_instance_mask,
+            })?;
+        Ok(entry.board_instance_mask())
+    }
+    /// Changes which boards the given entry applies to, in place (without
+    /// changing the entry body).  Fails with Error::EntryUniqueKeyViolation
+    /// if an entry with NEW_BOARD_INSTANCE_MASK already exists.
+    pub fn set_entry_board_mask(
+        &mut self,
+        entry_id: EntryId,
+        instance_id: u16,
+        old_board_instance_mask: BoardInstances,
+        new_board_instance_mask: BoardInstances,
+    ) -> Result<()> {
+        let mut group =
+            self.group_mut(entry_id.group_id())?.ok_or(Error::GroupNotFound)?;
+        group.set_entry_board_instance_mask(
+            entry_id,
+            instance_id,
+            old_board_instance_mask,
+            new_board_instance_mask,
+        )?;
+        self.move_entry_comment(
+            entry_id,
+            instance_id,
+            old_board_instance_mask,
+            Some(new_board_instance_mask),
+        );
+        self.record_edit(ApcbEdit::SetEntryBoardMask {
+            entry_id,
+            instance_id,
+            old_board_instance_mask,
+            new_board_instance_mask,
+        });
+        Ok(())
+    }
+    /// Changes the context_format of the given entry.  For token entries,
+    /// switching to ContextFormat::SortAscending sorts the tokens (and sets
+    /// key_size and key_pos accordingly).  Struct entries can only be
+    /// switched to ContextFormat::Raw.
+    pub fn set_entry_context_format(
+        &mut self,
+        entry_id: EntryId,
+        instance_id: u16,
+        board_instance_mask: BoardInstances,
+        context_format: ContextFormat,
+    ) -> Result<()> {
+        let mut group =
+            self.group_mut(entry_id.group_id())?.ok_or(Error::GroupNotFound)?;
+        let mut entry = group
+            .entry_exact_mut(entry_id, instance_id, board_instance_mask)
+            .ok_or(Error::EntryNotFound {
+                entry_id,
+                instance_id,
+                board_instance_mask,
+            })?;
+        entry.set_context_format(context_format)?;
+        self.record_edit(ApcbEdit::SetEntryContextFormat {
+            entry_id,
+            instance_id,
+            board_instance_mask,
+            context_format,
+        });
+        Ok(())
+    }
+    /// Rewrites the signature of the given group in place, leaving the rest
+    /// of the group (including its entries) as is.
+    pub fn set_group_signature(
+        &mut self,
+        group_id: GroupId,
+        signature: [u8; 4],
+    ) -> Result<()> {
+        let group = self.group_mut(group_id)?.ok_or(Error::GroupNotFound)?;
+        group.header.signature = signature;
+        self.record_edit(ApcbEdit::SetGroupSignature { group_id, signature });
         Ok(())
     }
     fn resize_group_by(
@@ -608,7 +1315,7 @@ impl<'a> Apcb<'a> {
         group_id: GroupId,
         size_diff: i64,
     ) -> Result<GroupMutItem<'_>> {
-        let old_used_size = self.used_size;
+        let old_used_size = self.groups_used_size;
         let apcb_size = self.header()?.apcb_size.get();
         if size_diff > 0 {
             let size_diff: u32 = (size_diff as u64)
@@ -674,7 +1381,7 @@ impl<'a> Apcb<'a> {
                 (old_group_size as usize)..(old_used_size - offset),
                 new_group_size as usize,
             );
-            self.used_size = new_used_size;
+            self.groups_used_size = new_used_size;
         } else if size_diff < 0 {
             let old_group_size: u32 = old_group_size
                 .try_into()
@@ -701,7 +1408,7 @@ impl<'a> Apcb<'a> {
                 (old_group_size as usize)..old_used_size,
                 new_group_size as usize,
             );
-            self.used_size = new_used_size;
+            self.groups_used_size = new_used_size;
         }
         self.group_mut(group_id)?.ok_or(Error::GroupNotFound)
     }
@@ -725,7 +1432,11 @@ impl<'a> Apcb<'a> {
             .entry_exact_mut(entry_id, instance_id, board_instance_mask)
             .is_some()
         {
-            return Err(Error::EntryUniqueKeyViolation);
+            return Err(Error::EntryUniqueKeyViolation {
+                entry_id,
+                instance_id,
+                board_instance_mask,
+            });
         }
 
         let mut entry_allocation: u16 = (size_of::<ENTRY_HEADER>() as u16)
@@ -757,22 +1468,60 @@ impl<'a> Apcb<'a> {
             payload_initializer,
             priority_mask,
         );
-        rv.map(|_| ())
+        rv?;
+        self.record_edit(ApcbEdit::InsertEntry {
+            entry_id,
+            instance_id,
+            board_instance_mask,
+        });
+        Ok(())
     }
 
-    // Security--and it would be nicer if the person using this would instead
-    // contribute a struct layout so we can use it normally
-    #[pre]
-    pub(crate) fn insert_entry(
+    /// Like internal_insert_entry, but replaces the entry with the same
+    /// (ENTRY_ID, INSTANCE_ID, BOARD_INSTANCE_MASK), if any.  Makes sure
+    /// that the new entry fits before deleting the old one, so on
+    /// OutOfSpace the old entry is still there.  The comment of the old
+    /// entry (if any) is kept for the new one.
+    #[allow(clippy::too_many_arguments)]
+    fn replace_entry(
         &mut self,
         entry_id: EntryId,
         instance_id: u16,
         board_instance_mask: BoardInstances,
         context_type: ContextType,
+        payload_size: usize,
         priority_mask: PriorityLevels,
-        payload: &[u8],
+        payload_initializer: impl Fn(&mut [u8]),
     ) -> Result<()> {
-        let payload_size = payload.len();
+        #[cfg(feature = "serde")]
+        let mut comment = None;
+        let group =
+            self.group(entry_id.group_id())?.ok_or(Error::GroupNotFound)?;
+        if let Some(entry) =
+            group.entry_exact(entry_id, instance_id, board_instance_mask)
+        {
+            let old_entry_size = usize::from(entry.header.entry_size.get());
+            let new_entry_size =
+                self.insert_entry_size_delta(entry_id, payload_size);
+            if u16::try_from(new_entry_size).is_err() {
+                return Err(Error::OutOfSpace);
+            }
+            let free_size = self
+                .beginning_of_groups()?
+                .len()
+                .checked_sub(self.groups_used_size)
+                .ok_or(Error::OutOfSpace)?;
+            if new_entry_size.saturating_sub(old_entry_size) > free_size {
+                return Err(Error::OutOfSpace);
+            }
+            #[cfg(feature = "serde")]
+            {
+                comment = self
+                    .entry_comment(entry_id, instance_id, board_instance_mask)
+                    .map(String::from);
+            }
+            self.delete_entry(entry_id, instance_id, board_instance_mask)?;
+        }
         self.internal_insert_entry(
             entry_id,
             instance_id,
@@ -780,12 +1529,108 @@ impl<'a> Apcb<'a> {
             context_type,
             payload_size,
             priority_mask,
-            |body: &mut [u8]| {
-                body.copy_from_slice(payload);
-            },
-        )
-    }
-
+            payload_initializer,
+        )?;
+        #[cfg(feature = "serde")]
+        if comment.is_some() {
+            self.set_entry_comment(
+                entry_id,
+                instance_id,
+                board_instance_mask,
+                comment,
+            );
+        }
+        Ok(())
+    }
+
+    /// Returns by how many bytes the APCB would grow if an entry with ENTRY_ID
+    /// and a body of BODY_LEN bytes were inserted: the entry header, the
+    /// body (padded to ENTRY_ALIGNMENT) and, if the entry's group does not
+    /// exist yet, the group header (see insert_group).
+    pub fn insert_entry_size_delta(
+        &self,
+        entry_id: EntryId,
+        body_len: usize,
+    ) -> usize {
+        let entry_size = size_of::<ENTRY_HEADER>().saturating_add(body_len);
+        let mut delta = entry_size.saturating_add(
+            (ENTRY_ALIGNMENT - entry_size % ENTRY_ALIGNMENT) % ENTRY_ALIGNMENT,
+        );
+        if !matches!(self.group(entry_id.group_id()), Ok(Some(_))) {
+            delta = delta.saturating_add(size_of::<GROUP_HEADER>());
+        }
+        delta
+    }
+
+    /// Checks a raw PAYLOAD that is about to be stored in an entry with
+    /// ENTRY_ID.  In particular, (Naples) parameters need their Limit
+    /// terminator--otherwise the result would not load again.
+    fn check_raw_payload(
+        entry_id: EntryId,
+        context_type: ContextType,
+        payload: &[u8],
+    ) -> Result<()> {
+        if context_type == ContextType::Struct
+            && Parameters::is_entry_compatible(entry_id, payload)
+        {
+            ParametersIter::new(payload)?;
+        }
+        Ok(())
+    }
+
+    // Security--and it would be nicer if the person using this would instead
+    // contribute a struct layout so we can use it normally
+    #[pre]
+    pub(crate) fn insert_entry(
+        &mut self,
+        entry_id: EntryId,
+        instance_id: u16,
+        board_instance_mask: BoardInstances,
+        context_type: ContextType,
+        priority_mask: PriorityLevels,
+        payload: &[u8],
+    ) -> Result<()> {
+        Self::check_raw_payload(entry_id, context_type, payload)?;
+        let payload_size = payload.len();
+        self.internal_insert_entry(
+            entry_id,
+            instance_id,
+            board_instance_mask,
+            context_type,
+            payload_size,
+            priority_mask,
+            |body: &mut [u8]| {
+                body.copy_from_slice(payload);
+            },
+        )
+    }
+
+    /// Like insert_entry, but if an entry with the same (ENTRY_ID,
+    /// INSTANCE_ID, BOARD_INSTANCE_MASK) already exists, replaces it
+    /// (instead of failing with Error::EntryUniqueKeyViolation).
+    pub fn upsert_entry(
+        &mut self,
+        entry_id: EntryId,
+        instance_id: u16,
+        board_instance_mask: BoardInstances,
+        context_type: ContextType,
+        priority_mask: PriorityLevels,
+        payload: &[u8],
+    ) -> Result<()> {
+        Self::check_raw_payload(entry_id, context_type, payload)?;
+        self.replace_entry(
+            entry_id,
+            instance_id,
+            board_instance_mask,
+            context_type,
+            payload.len(),
+            priority_mask,
+            |body: &mut [u8]| {
+                body.copy_from_slice(payload);
+            },
+        )
+    }
+
     /// Inserts a new entry (see insert_entry), puts PAYLOAD into it.  Usually
     /// that's for platform_specific_override or platform_tuning structs.
     /// Note: Currently, INSTANCE_ID is always supposed to be 0.
@@ -827,8 +1672,11 @@ impl<'a> Apcb<'a> {
 
     /// Inserts a new entry (see insert_entry), puts PAYLOAD into it.  T can be
     /// a enum of struct refs (PlatformSpecificElementRef,
-    /// PlatformTuningElementRef) or just one struct. Note: Currently,
-    /// INSTANCE_ID is always supposed to be 0.
+    /// PlatformTuningElementRef) or just one struct.  The items are packed
+    /// back to back; every one of them has to be compatible with ENTRY_ID
+    /// (see EntryCompatible::is_entry_compatible), otherwise nothing is
+    /// inserted and Error::EntryTypeMismatch is returned.
+    /// Note: Currently, INSTANCE_ID is always supposed to be 0.
     pub fn insert_struct_array_as_entry<T: EntryCompatible + AsBytes>(
         &mut self,
         entry_id: EntryId,
@@ -914,6 +1762,256 @@ impl<'a> Apcb<'a> {
         }
     }
 
+    /// Finds the entry with the given ENTRY_ID, INSTANCE_ID and exact
+    /// BOARD_INSTANCE_MASK and calls F with its header struct so it can
+    /// modify it in place (without copying the entry out and back in).
+    pub fn modify_struct<
+        H: EntryCompatible + FromBytes + AsBytes + HeaderWithTail,
+        F: FnOnce(&mut H),
+    >(
+        &mut self,
+        entry_id: EntryId,
+        instance_id: u16,
+        board_instance_mask: BoardInstances,
+        f: F,
+    ) -> Result<()> {
+        let mut group =
+            self.group_mut(entry_id.group_id())?.ok_or(Error::GroupNotFound)?;
+        let mut entry = group
+            .entry_exact_mut(entry_id, instance_id, board_instance_mask)
+            .ok_or(Error::EntryNotFound {
+                entry_id,
+                instance_id,
+                board_instance_mask,
+            })?;
+        let (header, _) =
+            entry.body_as_struct_mut::<H>().ok_or(Error::EntryTypeMismatch)?;
+        f(header);
+        self.record_edit(ApcbEdit::ModifyEntryBody {
+            entry_id,
+            instance_id,
+            board_instance_mask,
+        });
+        Ok(())
+    }
+
+    /// Sets up the serial console for the boards in BOARD_MASK: Sets the
+    /// AblSerialBaudRate token to BAUD and, in the ConsoleOutControl entry,
+    /// enables console logging and sets the abl_console_port to PORT.  The
+    /// token, the ConsoleOutControl entry and their groups are created as
+    /// necessary.
+    /// Everything that needs space is done before anything existing is
+    /// modified, so if this fails (for example with OutOfSpace, or because
+    /// the ConsoleOutControl entry has a different layout), nothing is
+    /// changed.
+    pub fn configure_serial_console(
+        &mut self,
+        board_mask: u16,
+        baud: BaudRate,
+        port: u32,
+    ) -> Result<()> {
+        let board_instance_mask = BoardInstances::from(board_mask);
+        let entry_id = EntryId::Memory(MemoryEntryId::ConsoleOutControl);
+        let configure = |console_out_control: &mut ConsoleOutControl| {
+            console_out_control
+                .abl_console_out_control
+                .set_enable_console_logging(true);
+            console_out_control
+                .abl_console_out_control
+                .set_abl_console_port(port);
+        };
+        let entry_exists = match self.group(GroupId::Memory)? {
+            Some(group) => {
+                match group.entry_exact(entry_id, 0, board_instance_mask) {
+                    Some(entry) => {
+                        entry
+                            .body_as_struct::<ConsoleOutControl>()
+                            .ok_or(Error::EntryTypeMismatch)?;
+                        true
+                    }
+                    None => false,
+                }
+            }
+            None => false,
+        };
+        let mut inserted_group = false;
+        if !entry_exists {
+            inserted_group =
+                match self.insert_group_default_signature(GroupId::Memory) {
+                    Err(Error::GroupUniqueKeyViolation) => false,
+                    Err(x) => {
+                        return Err(x);
+                    }
+                    _ => true,
+                };
+            let mut console_out_control = ConsoleOutControl::default();
+            configure(&mut console_out_control);
+            if let Err(e) = self.insert_struct_entry(
+                entry_id,
+                0,
+                board_instance_mask,
+                PriorityLevels::from_level(PriorityLevel::Normal),
+                &console_out_control,
+                &[],
+            ) {
+                if inserted_group {
+                    self.delete_group(GroupId::Memory)?;
+                }
+                return Err(e);
+            }
+        }
+
+        let token_group_exists = self.group(GroupId::Token)?.is_some();
+        let result = self
+            .tokens_mut(
+                0,
+                board_instance_mask,
+                PriorityLevels::from_level(PriorityLevel::Normal),
+                None,
+            )
+            .and_then(|mut tokens| tokens.set_abl_serial_baud_rate(baud));
+        if let Err(e) = result {
+            if !token_group_exists && self.group(GroupId::Token)?.is_some() {
+                self.delete_group(GroupId::Token)?;
+            }
+            if inserted_group {
+                self.delete_group(GroupId::Memory)?;
+            } else if !entry_exists {
+                self.delete_entry(entry_id, 0, board_instance_mask)?;
+            }
+            return Err(e);
+        }
+
+        if entry_exists {
+            self.modify_struct(entry_id, 0, board_instance_mask, configure)?;
+        }
+        Ok(())
+    }
+
+    /// Inserts an EarlyPcieConfig entry (into the existing Gnb group) with
+    /// one EarlyPcieConfigElement per item of ELEMENTS.  ELEMENTS can be
+    /// empty, in which case the entry has an empty body.
+    pub fn insert_early_pcie_config(
+        &mut self,
+        board_instance_mask: BoardInstances,
+        elements: &[EarlyPcieConfigBody],
+    ) -> Result<()> {
+        const ELEMENT_SIZE: usize = size_of::<EarlyPcieConfigElement>();
+        let payload_size = elements
+            .len()
+            .checked_mul(ELEMENT_SIZE)
+            .ok_or(Error::ArithmeticOverflow)?;
+        self.internal_insert_entry(
+            EntryId::Gnb(GnbEntryId::EarlyPcieConfig),
+            0,
+            board_instance_mask,
+            ContextType::Struct,
+            payload_size,
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            |body: &mut [u8]| {
+                for (a, element) in
+                    body.chunks_exact_mut(ELEMENT_SIZE).zip(elements)
+                {
+                    let mut item = EarlyPcieConfigElement::default();
+                    item.set_body(*element);
+                    a.copy_from_slice(item.as_bytes());
+                }
+            },
+        )
+    }
+
+    /// Returns the valid repairs of the DdrPostPackageRepair entry with
+    /// exactly BOARD_INSTANCE_MASK (and instance 0).  Invalid elements (in
+    /// particular the terminator) are skipped.
+    #[cfg(feature = "std")]
+    pub fn ddr_post_package_repair(
+        &self,
+        board_instance_mask: BoardInstances,
+    ) -> Result<Vec<DdrPostPackageRepairBody>> {
+        let entry_id = EntryId::Memory(MemoryEntryId::DdrPostPackageRepair);
+        let group = self.group(GroupId::Memory)?.ok_or(Error::GroupNotFound)?;
+        let entry = group.entry_exact(entry_id, 0, board_instance_mask).ok_or(
+            Error::EntryNotFound {
+                entry_id,
+                instance_id: 0,
+                board_instance_mask,
+            },
+        )?;
+        let elements = entry
+            .body_as_struct_array::<DdrPostPackageRepairElement>()
+            .ok_or(Error::EntryTypeMismatch)?;
+        Ok(elements.iter().filter_map(|element| element.body()).collect())
+    }
+
+    /// Replaces the DdrPostPackageRepair entry with exactly
+    /// BOARD_INSTANCE_MASK (and instance 0) by one containing REPAIRS
+    /// (which are marked valid), followed by the invalid terminator
+    /// element.  The entry is created (in the existing Memory group) if
+    /// necessary; otherwise, its priority mask is kept.
+    pub fn set_ddr_post_package_repair(
+        &mut self,
+        board_instance_mask: BoardInstances,
+        repairs: &[DdrPostPackageRepairBody],
+    ) -> Result<()> {
+        const ELEMENT_SIZE: usize = size_of::<DdrPostPackageRepairElement>();
+        let entry_id = EntryId::Memory(MemoryEntryId::DdrPostPackageRepair);
+        let group = self.group(GroupId::Memory)?.ok_or(Error::GroupNotFound)?;
+        let priority_mask =
+            match group.entry_exact(entry_id, 0, board_instance_mask) {
+                Some(entry) => PriorityLevels::from(entry.header.priority_mask),
+                None => PriorityLevels::from_level(PriorityLevel::Normal),
+            };
+        let payload_size = repairs
+            .len()
+            .checked_add(1)
+            .and_then(|count| count.checked_mul(ELEMENT_SIZE))
+            .ok_or(Error::ArithmeticOverflow)?;
+        self.replace_entry(
+            entry_id,
+            0,
+            board_instance_mask,
+            ContextType::Struct,
+            payload_size,
+            priority_mask,
+            |body: &mut [u8]| {
+                let mut chunks = body.chunks_exact_mut(ELEMENT_SIZE);
+                // Note: REPAIRS first, so zip doesn't consume an extra chunk.
+                for (repair, a) in repairs.iter().zip(&mut chunks) {
+                    let mut repair = *repair;
+                    repair.set_valid(true);
+                    let mut element = DdrPostPackageRepairElement::default();
+                    element.set_body(Some(repair));
+                    a.copy_from_slice(element.as_bytes());
+                }
+                if let Some(a) = chunks.next() {
+                    a.copy_from_slice(
+                        DdrPostPackageRepairElement::invalid().as_bytes(),
+                    );
+                }
+            },
+        )
+    }
+
+    /// Returns the elements of the MemDfeSearch entry for
+    /// BOARD_INSTANCE_MASK (with instance 0), in the layout of the given
+    /// VERSION.  See EntryItem::body_as_mem_dfe_search.
+    pub fn mem_dfe_search(
+        &self,
+        board_instance_mask: BoardInstances,
+        version: MemDfeSearchVersion,
+    ) -> Result<MemDfeSearchElements<'_>> {
+        let entry_id = EntryId::Memory(MemoryEntryId::MemDfeSearch);
+        let group = self.group(GroupId::Memory)?.ok_or(Error::GroupNotFound)?;
+        let entry = group.entry_exact(entry_id, 0, board_instance_mask).ok_or(
+            Error::EntryNotFound {
+                entry_id,
+                instance_id: 0,
+                board_instance_mask,
+            },
+        )?;
+        entry.body_as_mem_dfe_search(version)
+    }
+
     /// This inserts a Naples-style Parameters entry.
     /// Note: Keep in sync with new_tail_from_vec.
     pub fn insert_parameters_entry(
@@ -922,7 +2020,7 @@ impl<'a> Apcb<'a> {
         items: &[Parameter],
     ) -> Result<()> {
         let mut payload_size = size_of::<u32>() + size_of::<u8>(); // terminator attribute and its value
-        for parameter in items {
+        for (i, parameter) in items.iter().enumerate() {
             payload_size = payload_size
                 .checked_add(size_of::<ParameterAttributes>())
                 .ok_or(Error::ArithmeticOverflow)?;
@@ -932,7 +2030,9 @@ impl<'a> Apcb<'a> {
                 .checked_add(value_size)
                 .ok_or(Error::ArithmeticOverflow)?;
             if value_size > 8 || value >= (8u64 << value_size) {
-                return Err(Error::ParameterRange);
+                return Err(Error::ParameterRange {
+                    offset: i * size_of::<ParameterAttributes>(),
+                });
             }
         }
         self.internal_insert_entry(
@@ -971,6 +2071,76 @@ impl<'a> Apcb<'a> {
         )
     }
 
+    /// Returns the Naples-style parameters in the given entry (without the
+    /// terminator).
+    #[cfg(feature = "std")]
+    pub fn parameters(&self, entry_id: EntryId) -> Result<Vec<Parameter>> {
+        let group =
+            self.group(entry_id.group_id())?.ok_or(Error::GroupNotFound)?;
+        let entry = group
+            .entries()
+            .find(|entry| entry.id() == entry_id)
+            .ok_or(Error::EntryNotFound {
+                entry_id,
+                instance_id: 0,
+                board_instance_mask: BoardInstances::new(),
+            })?;
+        let (_, tail) = entry
+            .body_as_struct::<Parameters>()
+            .ok_or(Error::EntryTypeMismatch)?;
+        Ok(Parameters::iter(tail)?.collect())
+    }
+
+    /// Replaces the Naples-style parameters in the given entry by
+    /// PARAMETERS, creating the entry if necessary.  The terminator is
+    /// appended automatically.  Each parameter must have a value_size of 1,
+    /// 2, 4 or 8 and a value that fits into it.
+    #[cfg(feature = "std")]
+    pub fn set_parameters(
+        &mut self,
+        entry_id: EntryId,
+        parameters: &[Parameter],
+    ) -> Result<()> {
+        for (i, parameter) in parameters.iter().enumerate() {
+            let value_size = parameter.value_size()?;
+            if !matches!(value_size, 1 | 2 | 4 | 8)
+                || (value_size < 8
+                    && parameter.value()? >> (8 * value_size) != 0)
+                || parameter.token()? == ParameterTokenConfig::Limit
+            {
+                return Err(Error::ParameterRange {
+                    offset: i * size_of::<ParameterAttributes>(),
+                });
+            }
+        }
+        let mut source = parameters.to_vec();
+        source.push(Parameter::new(&ParameterAttributes::terminator(), 0xff)?);
+        let tail = Parameters::new_tail_from_vec(source)?;
+
+        let group =
+            self.group(entry_id.group_id())?.ok_or(Error::GroupNotFound)?;
+        let (instance_id, board_instance_mask, priority_mask) =
+            match group.entries().find(|entry| entry.id() == entry_id) {
+                Some(entry) => (
+                    entry.instance_id(),
+                    entry.board_instance_mask(),
+                    PriorityLevels::from(entry.header.priority_mask),
+                ),
+                None => (0, BoardInstances::new(), PriorityLevels::new()),
+            };
+        self.replace_entry(
+            entry_id,
+            instance_id,
+            board_instance_mask,
+            ContextType::Struct,
+            tail.len(),
+            priority_mask,
+            |body: &mut [u8]| {
+                body.copy_from_slice(&tail);
+            },
+        )
+    }
+
     /// Note: INSTANCE_ID is sometimes != 0.
     #[pre]
     pub fn insert_token(
@@ -986,12 +2156,31 @@ impl<'a> Apcb<'a> {
         let group = self.group(group_id)?.ok_or(Error::GroupNotFound)?;
         let entry = group
             .entry_exact(entry_id, instance_id, board_instance_mask)
-            .ok_or(Error::EntryNotFound)?;
+            .ok_or(Error::EntryNotFound {
+                entry_id,
+                instance_id,
+                board_instance_mask,
+            })?;
         let EntryItemBody::<_>::Tokens(a) = &entry.body else {
             return Err(Error::EntryTypeMismatch);
         };
+        let EntryId::Token(token_entry_id) = entry_id else {
+            return Err(Error::EntryTypeMismatch);
+        };
         if a.token(token_id).is_some() {
-            return Err(Error::TokenUniqueKeyViolation);
+            return Err(Error::TokenUniqueKeyViolation {
+                entry_id: token_entry_id,
+                token_id,
+            });
+        }
+        // Reject values that the token entry cannot represent, rather than
+        // having them silently truncated when read back.
+        if token_value & !token_entry_id.value_mask() != 0 {
+            return Err(Error::TokenValueRange {
+                entry_id: token_entry_id,
+                token_id,
+                value: token_value,
+            });
         }
         // Tokens that destroy the alignment in the container have not been
         // tested, are impossible right now anyway and have never been seen.  So
@@ -1013,7 +2202,7 @@ impl<'a> Apcb<'a> {
             token_id,
             token_value,
         );
-        if let Err(Error::EntryNotFound) = rv {
+        if let Err(Error::EntryNotFound { .. }) = rv {
             panic!(
                 "Internal error: Entry (entry_id = {entry_id:?}, \
                 instance_id = {instance_id:?}, \
@@ -1022,7 +2211,15 @@ impl<'a> Apcb<'a> {
                 but is not found after resizing group"
             );
         }
-        rv
+        rv?;
+        self.record_edit(ApcbEdit::InsertToken {
+            entry_id,
+            instance_id,
+            board_instance_mask,
+            token_id,
+            token_value,
+        });
+        Ok(())
     }
 
     pub fn delete_token(
@@ -1043,9 +2240,116 @@ impl<'a> Apcb<'a> {
             token_id,
         )?;
         self.resize_group_by(group_id, token_diff)?;
+        self.record_edit(ApcbEdit::DeleteToken {
+            entry_id,
+            instance_id,
+            board_instance_mask,
+            token_id,
+        });
+        Ok(())
+    }
+
+    /// Returns the value of the token TOKEN_ID in the entry with exactly
+    /// (ENTRY_ID, INSTANCE_ID, BOARD_INSTANCE_MASK).  If there is no such
+    /// entry or token, returns Error::TokenNotFound.
+    pub fn token_value(
+        &self,
+        entry_id: EntryId,
+        instance_id: u16,
+        board_instance_mask: BoardInstances,
+        token_id: u32,
+    ) -> Result<u32> {
+        let token_entry_id = match entry_id {
+            EntryId::Token(token_entry_id) => Some(token_entry_id),
+            _ => None,
+        };
+        let not_found =
+            || Error::TokenNotFound { entry_id: token_entry_id, token_id };
+        let group = self.group(entry_id.group_id())?.ok_or_else(not_found)?;
+        let entry = group
+            .entry_exact(entry_id, instance_id, board_instance_mask)
+            .ok_or_else(not_found)?;
+        let EntryItemBody::<_>::Tokens(tokens) = &entry.body else {
+            return Err(Error::EntryTypeMismatch);
+        };
+        let token = tokens.token(token_id).ok_or_else(not_found)?;
+        Ok(token.value())
+    }
+
+    /// Changes the value of the existing token TOKEN_ID in the entry with
+    /// exactly (ENTRY_ID, INSTANCE_ID, BOARD_INSTANCE_MASK) to
+    /// TOKEN_VALUE.  In contrast to insert_token, this never adds a token:
+    /// If there is no such entry or token, returns Error::TokenNotFound.
+    pub fn set_token_value(
+        &mut self,
+        entry_id: EntryId,
+        instance_id: u16,
+        board_instance_mask: BoardInstances,
+        token_id: u32,
+        token_value: u32,
+    ) -> Result<()> {
+        let token_entry_id = match entry_id {
+            EntryId::Token(token_entry_id) => Some(token_entry_id),
+            _ => None,
+        };
+        let not_found =
+            || Error::TokenNotFound { entry_id: token_entry_id, token_id };
+        let mut group =
+            self.group_mut(entry_id.group_id())?.ok_or_else(not_found)?;
+        let mut entry = group
+            .entry_exact_mut(entry_id, instance_id, board_instance_mask)
+            .ok_or_else(not_found)?;
+        let EntryItemBody::<_>::Tokens(tokens) = &mut entry.body else {
+            return Err(Error::EntryTypeMismatch);
+        };
+        let mut token = tokens.token_mut(token_id).ok_or_else(not_found)?;
+        token.set_value(token_value)?;
+        self.record_edit(ApcbEdit::SetToken {
+            entry_id,
+            instance_id,
+            board_instance_mask,
+            token_id,
+            token_value,
+        });
         Ok(())
     }
 
+    /// Deletes the token with the given KEY from all the token entries
+    /// (regardless of instance, board instance mask and token width).
+    /// Returns how many tokens were deleted.
+    pub fn delete_token_everywhere(&mut self, key: u32) -> Result<usize> {
+        let mut count = 0usize;
+        loop {
+            let mut found = None;
+            if let Some(group) = self.group(GroupId::Token)? {
+                for entry in group.entries() {
+                    if let EntryItemBody::<_>::Tokens(tokens) = &entry.body {
+                        if tokens.iter()?.any(|token| token.id() == key) {
+                            found = Some((
+                                entry.id(),
+                                entry.instance_id(),
+                                entry.board_instance_mask(),
+                            ));
+                            break;
+                        }
+                    }
+                }
+            }
+            match found {
+                Some((entry_id, instance_id, board_instance_mask)) => {
+                    self.delete_token(
+                        entry_id,
+                        instance_id,
+                        board_instance_mask,
+                        key,
+                    )?;
+                    count += 1;
+                }
+                None => return Ok(count),
+            }
+        }
+    }
+
     pub fn delete_group(&mut self, group_id: GroupId) -> Result<()> {
         let apcb_size = self.header()?.apcb_size.get();
         let mut groups = self.groups_mut()?;
@@ -1058,39 +2362,61 @@ impl<'a> Apcb<'a> {
                 ),
             )?,
         );
-        let buf = &mut self.beginning_of_groups_mut()?[offset..];
-        buf.copy_within(group_size..(apcb_size as usize), 0);
-        self.used_size =
-            self.used_size.checked_sub(group_size).ok_or(Error::FileSystem(
-                FileSystemError::InconsistentHeader,
-                "GROUP_HEADER::group_size",
-            ))?;
+        // Only move the groups after it (apcb_size also covers the headers
+        // before the groups, so it would run past the end of a small
+        // backing store).
+        let used_end = self.groups_used_size;
+        let buf = &mut self.beginning_of_groups_mut()?[offset..used_end];
+        buf.copy_within(group_size.., 0);
+        self.groups_used_size = self
+            .groups_used_size
+            .checked_sub(group_size)
+            .ok_or(Error::FileSystem(
+            FileSystemError::InconsistentHeader,
+            "GROUP_HEADER::group_size",
+        ))?;
+        self.forget_group_comments(group_id);
+        self.record_edit(ApcbEdit::DeleteGroup { group_id });
         Ok(())
     }
 
+    /// Inserts a group with the given GROUP_ID and its canonical signature.
+    /// Note: Groups without a canonical signature (Unknown) fail with
+    /// Error::GroupTypeMismatch; use insert_group for those.
+    pub fn insert_group_default_signature(
+        &mut self,
+        group_id: GroupId,
+    ) -> Result<GroupMutItem<'_>> {
+        let signature =
+            group_id.canonical_signature().ok_or(Error::GroupTypeMismatch)?;
+        self.insert_group(group_id, signature)
+    }
+
     pub fn insert_group(
         &mut self,
         group_id: GroupId,
         signature: [u8; 4],
     ) -> Result<GroupMutItem<'_>> {
-        // TODO: insert sorted.
-        let context = self.context;
-
-        if !match group_id {
-            GroupId::Psp => signature == *b"PSPG",
-            GroupId::Ccx => signature == *b"CCXG",
-            GroupId::Df => signature == *b"DFG ",
-            GroupId::Memory => signature == *b"MEMG",
-            GroupId::Gnb => signature == *b"GNBG",
-            GroupId::Fch => signature == *b"FCHG",
-            GroupId::Cbs => signature == *b"CBSG",
-            GroupId::Oem => signature == *b"OEMG",
-            GroupId::Token => signature == *b"TOKN",
-            GroupId::Unknown(_) => true,
-        } {
-            return Err(Error::GroupTypeMismatch);
-        }
-
+        self.internal_insert_group(group_id, signature)?;
+        self.record_edit(ApcbEdit::InsertGroup { group_id, signature });
+        self.group_mut(group_id)?.ok_or(Error::GroupNotFound)
+    }
+
+    /// Like insert_group, but does not record the edit.
+    fn internal_insert_group(
+        &mut self,
+        group_id: GroupId,
+        signature: [u8; 4],
+    ) -> Result<()> {
+        // TODO: insert sorted.
+
+        if !match group_id.canonical_signature() {
+            Some(canonical_signature) => signature == canonical_signature,
+            None => true,
+        } {
+            return Err(Error::GroupTypeMismatch);
+        }
+
         let mut groups = self.groups_mut()?;
         match groups.move_point_to(group_id) {
             Err(Error::GroupNotFound) => {}
@@ -1106,14 +2432,14 @@ impl<'a> Apcb<'a> {
         let old_apcb_size = self.header()?.apcb_size.get();
         let new_apcb_size =
             old_apcb_size.checked_add(size as u32).ok_or(Error::OutOfSpace)?;
-        let old_used_size = self.used_size;
+        let old_used_size = self.groups_used_size;
         let new_used_size =
             old_used_size.checked_add(size).ok_or(Error::OutOfSpace)?;
         if self.beginning_of_groups()?.len() < new_used_size {
             return Err(Error::OutOfSpace);
         }
         self.header_mut()?.apcb_size.set(new_apcb_size);
-        self.used_size = new_used_size;
+        self.groups_used_size = new_used_size;
 
         let mut beginning_of_group =
             &mut self.beginning_of_groups_mut()?[old_used_size..new_used_size];
@@ -1128,31 +2454,107 @@ impl<'a> Apcb<'a> {
         *header = GROUP_HEADER::default();
         header.signature = signature;
         header.group_id = group_id.to_u16().unwrap().into();
-        let body = take_body_from_collection_mut(&mut beginning_of_group, 0, 1)
-            .ok_or(Error::FileSystem(
+        Ok(())
+    }
+
+    /// Inserts a group with the given SIGNATURE and GROUP_ID whose body
+    /// (everything after the GROUP_HEADER) is BODY--for example, the
+    /// result of GroupItem::body_as_buf on a group of another APCB.
+    /// BODY has to parse as a sequence of entries of that group.
+    pub fn insert_group_raw(
+        &mut self,
+        signature: [u8; 4],
+        group_id: u16,
+        body: &[u8],
+    ) -> Result<()> {
+        let raw_group_id = group_id;
+        let group_id =
+            GroupId::from_u16(raw_group_id).ok_or(Error::GroupTypeMismatch)?;
+        let group_size: u32 = size_of::<GROUP_HEADER>()
+            .checked_add(body.len())
+            .ok_or(Error::ArithmeticOverflow)?
+            .try_into()
+            .map_err(|_| Error::ArithmeticOverflow)?;
+        let mut header = GROUP_HEADER::default();
+        header.signature = signature;
+        header.group_id.set(raw_group_id);
+        header.group_size.set(group_size);
+        let group = GroupItem {
+            context: self.context,
+            header: &header,
+            buf: body,
+            used_size: body.len(),
+        };
+        group.entries().validate()?;
+        if group
+            .entries()
+            .any(|entry| entry.header.group_id.get() != raw_group_id)
+        {
+            return Err(Error::FileSystem(
                 FileSystemError::InconsistentHeader,
-                "GROUP_HEADER",
-            ))?;
-        let body_len = body.len();
+                "ENTRY_HEADER::group_id",
+            ));
+        }
 
-        Ok(GroupMutItem { context, header, buf: body, used_size: body_len })
+        let new_used_size = self
+            .groups_used_size
+            .checked_add(group_size as usize)
+            .ok_or(Error::OutOfSpace)?;
+        if self.beginning_of_groups()?.len() < new_used_size {
+            return Err(Error::OutOfSpace);
+        }
+        self.internal_insert_group(group_id, signature)?;
+        let size_diff: i64 =
+            body.len().try_into().map_err(|_| Error::ArithmeticOverflow)?;
+        let group = self.resize_group_by(group_id, size_diff)?;
+        group.buf[..body.len()].copy_from_slice(body);
+        self.record_edit(ApcbEdit::InsertGroup { group_id, signature });
+        #[cfg(feature = "std")]
+        if self.edit_log.is_some() {
+            let group = self.group(group_id)?.ok_or(Error::GroupNotFound)?;
+            let inserted_entries = group
+                .entries()
+                .map(|entry| ApcbEdit::InsertEntry {
+                    entry_id: entry.id(),
+                    instance_id: entry.instance_id(),
+                    board_instance_mask: entry.board_instance_mask(),
+                })
+                .collect::<Vec<_>>();
+            for edit in inserted_entries {
+                self.record_edit(edit);
+            }
+        }
+        Ok(())
     }
 
+    /// Calculates the checksum byte for BYTES, which are supposed to be an
+    /// entire APCB (that is, apcb_size bytes, starting with the V2_HEADER).
+    /// The checksum byte stored in BYTES is ignored.
+    pub fn compute_checksum(bytes: &[u8]) -> u8 {
+        const CHECKSUM_OFFSET: usize =
+            core::mem::offset_of!(V2_HEADER, checksum_byte);
+        let sum = bytes
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| i != CHECKSUM_OFFSET)
+            .fold(0u8, |sum, (_, c)| sum.wrapping_add(*c));
+        0u8.wrapping_sub(sum)
+    }
+
+    /// Like compute_checksum, but for an APCB whose V2_HEADER, V3_HEADER_EXT
+    /// and groups have been split up already.
     pub(crate) fn calculate_checksum(
         header: &LayoutVerified<&'_ [u8], V2_HEADER>,
         v3_header_ext: &Option<LayoutVerified<&'_ [u8], V3_HEADER_EXT>>,
         beginning_of_groups: &[u8],
     ) -> Result<u8> {
-        let mut checksum_byte = 0u8;
-        let stored_checksum_byte = header.checksum_byte;
-        for c in header.bytes() {
-            checksum_byte = checksum_byte.wrapping_add(*c);
-        }
+        let sum = |bytes: &[u8]| {
+            bytes.iter().fold(0u8, |sum, c| sum.wrapping_add(*c))
+        };
         let mut offset = header.bytes().len();
+        let mut rest_sum = 0u8;
         if let Some(v3_header_ext) = &v3_header_ext {
-            for c in v3_header_ext.bytes() {
-                checksum_byte = checksum_byte.wrapping_add(*c);
-            }
+            rest_sum = rest_sum.wrapping_add(sum(v3_header_ext.bytes()));
             offset = offset
                 .checked_add(v3_header_ext.bytes().len())
                 .ok_or(Error::OutOfSpace)?;
@@ -1161,20 +2563,57 @@ impl<'a> Apcb<'a> {
         let beginning_of_groups_used_size = (apcb_size as usize)
             .checked_sub(offset)
             .ok_or(Error::OutOfSpace)?;
-        let beginning_of_groups = &beginning_of_groups;
-        if beginning_of_groups.len() < beginning_of_groups_used_size {
-            return Err(Error::FileSystem(
-                FileSystemError::InconsistentHeader,
-                "V2_HEADER::apcb_size",
-            ));
+        let groups = beginning_of_groups
+            .get(..beginning_of_groups_used_size)
+            .ok_or(Error::FileSystem(
+            FileSystemError::InconsistentHeader,
+            "V2_HEADER::apcb_size",
+        ))?;
+        rest_sum = rest_sum.wrapping_add(sum(groups));
+        // compute_checksum(header) is minus the sum of the header bytes
+        // (without the checksum byte), so this is minus the sum of all the
+        // bytes--as if compute_checksum had been called on the entire APCB.
+        Ok(Self::compute_checksum(header.bytes()).wrapping_sub(rest_sum))
+    }
+
+    /// Scans IMAGE (for example a dump of an entire SPI flash) for an APCB.
+    /// Returns (offset, length) of the first region that starts with the
+    /// V2_HEADER signature, whose apcb_size fits into IMAGE and whose
+    /// checksum is correct.  The latter weeds out occurrences of "APCB" in
+    /// unrelated data.
+    pub fn find_in_image(image: &[u8]) -> Option<(usize, usize)> {
+        let mut offset = 0usize;
+        while let Some(position) =
+            image.get(offset..)?.windows(4).position(|window| window == b"APCB")
+        {
+            offset += position;
+            if let Some(size) = Self::apcb_size_at(&image[offset..]) {
+                return Some((offset, size));
+            }
+            offset += 1;
+        }
+        None
+    }
+
+    /// If BUF starts with a V2_HEADER whose apcb_size fits into BUF and
+    /// whose checksum is correct, returns that apcb_size.
+    fn apcb_size_at(buf: &[u8]) -> Option<usize> {
+        let (header, _) =
+            LayoutVerified::<&[u8], V2_HEADER>::new_unaligned_from_prefix(buf)?;
+        if header.signature != *b"APCB" {
+            return None;
         }
-        for c in &beginning_of_groups[0..beginning_of_groups_used_size] {
-            checksum_byte = checksum_byte.wrapping_add(*c);
+        let header_size = usize::from(header.header_size.get());
+        let apcb_size = header.apcb_size.get() as usize;
+        if header_size < size_of::<V2_HEADER>() || apcb_size < header_size {
+            return None;
+        }
+        if Self::compute_checksum(buf.get(..apcb_size)?) == header.checksum_byte
+        {
+            Some(apcb_size)
+        } else {
+            None
         }
-        // Correct for stored_checksum_byte
-        checksum_byte = checksum_byte.wrapping_sub(stored_checksum_byte);
-        Ok((0x100u16 - u16::from(checksum_byte)) as u8) // Note: This can
-                                                        // overflow
     }
 
     /// Note: for OPTIONS, try ApcbIoOptions::default()
@@ -1213,12 +2652,12 @@ impl<'a> Apcb<'a> {
             ));
         }
         let version = header.version.get();
-        if version == Self::ROME_VERSION || version == Self::NAPLES_VERSION {
+        if version == Self::ROME_VERSION
+            || version == Self::NAPLES_VERSION
+            || options.allow_any_version
+        {
         } else {
-            return Err(Error::FileSystem(
-                FileSystemError::InconsistentHeader,
-                "V2_HEADER::version",
-            ));
+            return Err(Error::UnsupportedVersion { found: version });
         }
         let apcb_size = header.apcb_size.get();
 
@@ -1281,6 +2720,18 @@ impl<'a> Apcb<'a> {
                     "V3_HEADER_EXT::signature_ending",
                 ));
             }
+            if let (true, Some(header_checksum_fn)) =
+                (options.check_header_checksum, options.header_checksum_fn)
+            {
+                let calculated =
+                    Self::calculate_header_checksum(value, header_checksum_fn);
+                if value.header_checksum != calculated {
+                    return Err(Error::HeaderChecksum {
+                        stored: value.header_checksum,
+                        calculated,
+                    });
+                }
+            }
             Some(header_ext)
         } else {
             //// TODO: Maybe skip weird header
@@ -1308,16 +2759,36 @@ impl<'a> Apcb<'a> {
         };
         if options.check_checksum {
             if header.checksum_byte != checksum_byte {
-                return Err(Error::FileSystem(
-                    FileSystemError::InconsistentHeader,
-                    "V2_HEADER::checksum_byte",
-                ));
+                return Err(Error::Checksum {
+                    stored: header.checksum_byte,
+                    calculated: checksum_byte,
+                });
             }
         }
-        let result =
-            Self { context: options.context(), backing_store: bs, used_size };
+        let result = Self {
+            context: options.context(),
+            backing_store: bs,
+            groups_used_size: used_size,
+            #[cfg(feature = "serde")]
+            comments: BTreeMap::new(),
+            #[cfg(feature = "std")]
+            edit_log: if options.record_edits {
+                Some(Vec::new())
+            } else {
+                None
+            },
+            pinned_unique_apcb_instance: options.unique_apcb_instance(),
+            header_checksum_fn: options.header_checksum_fn(),
+            integrity_sign_fn: options.integrity_sign_fn(),
+            allow_any_version: options.allow_any_version(),
+        };
 
-        match result.groups()?.validate() {
+        // Report entries in the wrong group as such (rather than as an
+        // inconsistent header).
+        for group in result.groups()? {
+            group.into_entries().check_group_ids()?;
+        }
+        match result.groups()?.validate(options.allow_any_version) {
             Ok(_) => {}
             Err(e) => {
                 return Err(e);
@@ -1326,6 +2797,258 @@ impl<'a> Apcb<'a> {
         Ok(result)
     }
 
+    /// Reads an APCB from READER (until EOF) into a new buffer and loads it
+    /// (see load).  The buffer is at least MAX_SIZE bytes long, so there is
+    /// room for modifications.
+    #[cfg(feature = "std")]
+    pub fn from_reader<R: std::io::Read>(
+        mut reader: R,
+        options: &ApcbIoOptions,
+    ) -> Result<Apcb<'static>> {
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+        if buf.len() < Self::MAX_SIZE {
+            buf.resize(Self::MAX_SIZE, 0xFF);
+        }
+        Apcb::load(Cow::from(buf), options)
+    }
+
+    /// Writes the APCB (that is, apcb_size bytes, with up-to-date headers
+    /// like save would produce) to WRITER.  In contrast to save, this does
+    /// not change unique_apcb_instance.  See also compact_into.
+    #[cfg(feature = "std")]
+    pub fn to_writer<W: std::io::Write>(&self, mut writer: W) -> Result<()> {
+        let mut bytes = vec![0u8; self.used_size()?];
+        self.compact_into(&mut bytes)?;
+        writer.write_all(&bytes)?;
+        Ok(())
+    }
+
+    /// Like load, but for (partially) damaged blobs: Loads as many of the
+    /// groups as can be parsed, stopping at the first one that cannot be,
+    /// and reports what was skipped.  The checksum is not enforced (a
+    /// mismatch is reported instead).
+    /// Note: If any groups were skipped, V2_HEADER::apcb_size in BS is
+    /// reduced accordingly.
+    #[cfg(feature = "std")]
+    pub fn load_best_effort(
+        mut bs: PtrMut<'a, [u8]>,
+        options: &ApcbIoOptions,
+    ) -> (Option<Self>, Vec<ApcbWarning>) {
+        let mut warnings = Vec::new();
+        if let Err(e) = Self::drop_unparseable_groups(
+            bs.to_mut(),
+            options.context(),
+            &mut warnings,
+        ) {
+            warnings.push(ApcbWarning::LoadFailed(e));
+            return (None, warnings);
+        }
+        let options = ApcbIoOptions {
+            check_checksum: false,
+            check_header_checksum: false,
+            ..*options
+        };
+        match Self::load(bs, &options) {
+            Ok(apcb) => (Some(apcb), warnings),
+            Err(e) => {
+                warnings.push(ApcbWarning::LoadFailed(e));
+                (None, warnings)
+            }
+        }
+    }
+
+    /// Reduces V2_HEADER::apcb_size in BACKING_STORE such that it only
+    /// covers the groups that can be parsed.
+    #[cfg(feature = "std")]
+    fn drop_unparseable_groups(
+        backing_store: &mut [u8],
+        context: ApcbContext,
+        warnings: &mut Vec<ApcbWarning>,
+    ) -> Result<()> {
+        let buffer_size = backing_store.len();
+        let (header, rest) =
+            LayoutVerified::<&[u8], V2_HEADER>::new_unaligned_from_prefix(
+                &*backing_store,
+            )
+            .ok_or(Error::FileSystem(
+                FileSystemError::InconsistentHeader,
+                "V2_HEADER",
+            ))?;
+        let header_size = usize::from(header.header_size.get());
+        let apcb_size = header.apcb_size.get() as usize;
+        let v3_header_ext = if header_size == Self::V3_HEADER_EXT_SIZE {
+            Some(
+                LayoutVerified::<&[u8], V3_HEADER_EXT>::new_unaligned_from_prefix(
+                    rest,
+                )
+                .ok_or(Error::FileSystem(
+                    FileSystemError::InconsistentHeader,
+                    "V3_HEADER_EXT",
+                ))?
+                .0,
+            )
+        } else {
+            None
+        };
+        let groups_offset = if v3_header_ext.is_some() {
+            Self::V3_HEADER_EXT_SIZE
+        } else {
+            size_of::<V2_HEADER>()
+        };
+        if apcb_size > buffer_size {
+            warnings.push(ApcbWarning::Truncated { apcb_size, buffer_size });
+        } else {
+            let calculated = Self::calculate_checksum(
+                &header,
+                &v3_header_ext,
+                &backing_store[groups_offset..],
+            )?;
+            if header.checksum_byte != calculated {
+                warnings.push(ApcbWarning::ChecksumMismatch {
+                    stored: header.checksum_byte,
+                    calculated,
+                });
+            }
+        }
+        let used_size = apcb_size
+            .min(buffer_size)
+            .checked_sub(header_size)
+            .ok_or(Error::FileSystem(
+                FileSystemError::InconsistentHeader,
+                "V2_HEADER::header_size",
+            ))?;
+        let groups =
+            backing_store.get(groups_offset..).ok_or(Error::FileSystem(
+                FileSystemError::InconsistentHeader,
+                "V2_HEADER::header_size",
+            ))?;
+        let used_size = used_size.min(groups.len());
+        let mut good_size = 0usize;
+        while good_size < used_size {
+            let mut iter = ApcbIter {
+                context,
+                buf: &groups[good_size..used_size],
+                remaining_used_size: used_size - good_size,
+                remaining_count: 0, // only next1 is used
+            };
+            let group_size = iter.next1().and_then(|group| {
+                GroupId::from_u16(group.header.group_id.get()).ok_or(
+                    Error::FileSystem(
+                        FileSystemError::InconsistentHeader,
+                        "GROUP_HEADER::group_id",
+                    ),
+                )?;
+                group.entries().validate()?;
+                Ok(group.header.group_size.get() as usize)
+            });
+            match group_size {
+                Ok(group_size) => {
+                    good_size += group_size;
+                }
+                Err(error) => {
+                    warnings.push(ApcbWarning::GroupsSkipped {
+                        offset: groups_offset + good_size,
+                        size: used_size - good_size,
+                        error,
+                    });
+                    break;
+                }
+            }
+        }
+        let new_apcb_size = header_size + good_size;
+        if new_apcb_size != apcb_size {
+            let (mut header, _) =
+                LayoutVerified::<&mut [u8], V2_HEADER>::new_unaligned_from_prefix(
+                    backing_store,
+                )
+                .ok_or(Error::FileSystem(
+                    FileSystemError::InconsistentHeader,
+                    "V2_HEADER",
+                ))?;
+            header.apcb_size.set(
+                new_apcb_size
+                    .try_into()
+                    .map_err(|_| Error::ArithmeticOverflow)?,
+            );
+        }
+        Ok(())
+    }
+
+    /// Calculates the header_checksum of HEADER_EXT using
+    /// HEADER_CHECKSUM_FN.  The header_checksum stored in HEADER_EXT is
+    /// ignored.
+    fn calculate_header_checksum(
+        header_ext: &V3_HEADER_EXT,
+        header_checksum_fn: HeaderChecksumFn,
+    ) -> u8 {
+        let mut header_ext = *header_ext;
+        header_ext.header_checksum = 0;
+        header_checksum_fn(&header_ext)
+    }
+
+    /// Brings the headers of BYTES (an entire APCB, that is exactly
+    /// apcb_size bytes) up to date: V3_HEADER_EXT::integrity_sign (if
+    /// INTEGRITY_SIGN_FN is set), V3_HEADER_EXT::header_checksum (if
+    /// HEADER_CHECKSUM_FN is set) and, last, V2_HEADER::checksum_byte.
+    /// This is what save, save_no_inc, to_writer and compact_into all do.
+    fn finalize_bytes(
+        bytes: &mut [u8],
+        header_checksum_fn: Option<HeaderChecksumFn>,
+        integrity_sign_fn: Option<IntegritySignFn>,
+    ) -> Result<()> {
+        const CHECKSUM_OFFSET: usize =
+            core::mem::offset_of!(V2_HEADER, checksum_byte);
+        let (header, rest) =
+            LayoutVerified::<&mut [u8], V2_HEADER>::new_unaligned_from_prefix(
+                &mut *bytes,
+            )
+            .ok_or(Error::FileSystem(
+                FileSystemError::InconsistentHeader,
+                "V2_HEADER",
+            ))?;
+        if usize::from(header.header_size.get()) == Self::V3_HEADER_EXT_SIZE {
+            let (mut header_ext, groups) = LayoutVerified::<
+                &mut [u8],
+                V3_HEADER_EXT,
+            >::new_unaligned_from_prefix(
+                rest
+            )
+            .ok_or(Error::FileSystem(
+                FileSystemError::InconsistentHeader,
+                "V3_HEADER_EXT",
+            ))?;
+            if let Some(integrity_sign_fn) = integrity_sign_fn {
+                header_ext.integrity_sign = integrity_sign_fn(groups);
+            }
+            if let Some(header_checksum_fn) = header_checksum_fn {
+                header_ext.header_checksum = Self::calculate_header_checksum(
+                    &header_ext,
+                    header_checksum_fn,
+                );
+            }
+        }
+        bytes[CHECKSUM_OFFSET] = Self::compute_checksum(bytes);
+        Ok(())
+    }
+
+    /// Finalizes (see finalize_bytes) the used part of the backing store
+    /// in place.
+    fn finalize(&mut self) -> Result<()> {
+        let apcb_size = self.header()?.apcb_size.get() as usize;
+        let header_checksum_fn = self.header_checksum_fn;
+        let integrity_sign_fn = self.integrity_sign_fn;
+        #[cfg(not(feature = "std"))]
+        let bs: &mut [u8] = self.backing_store;
+        #[cfg(feature = "std")]
+        let bs: &mut [u8] = self.backing_store.to_mut();
+        let bytes = bs.get_mut(..apcb_size).ok_or(Error::FileSystem(
+            FileSystemError::InconsistentHeader,
+            "V2_HEADER::apcb_size",
+        ))?;
+        Self::finalize_bytes(bytes, header_checksum_fn, integrity_sign_fn)
+    }
+
     pub fn update_checksum(&mut self) -> Result<()> {
         self.header_mut()?.checksum_byte = 0; // make calculate_checksum's job easier
         let checksum_byte = Self::calculate_checksum(
@@ -1337,29 +3060,349 @@ impl<'a> Apcb<'a> {
         Ok(())
     }
 
+    /// Returns the used part of the backing store (that is, exactly
+    /// apcb_size bytes) as it is right now.  Nothing is finalized, so after
+    /// modifying anything, the checksums in the result are stale until
+    /// save or update_checksum.  See compact_into for a finalized copy.
+    pub fn live_bytes(&self) -> Result<&[u8]> {
+        let apcb_size = self.header()?.apcb_size.get() as usize;
+        self.backing_store.get(..apcb_size).ok_or(Error::FileSystem(
+            FileSystemError::InconsistentHeader,
+            "V2_HEADER::apcb_size",
+        ))
+    }
+
+    /// Returns the number of bytes of the backing store that are in use
+    /// (that is, V2_HEADER::apcb_size).  Deleting entries or groups makes
+    /// this shrink; the remainder of the backing store is free.
+    pub fn used_size(&self) -> Result<usize> {
+        Ok(self.header()?.apcb_size.get() as usize)
+    }
+
+    /// Copies only the used part of the backing store (see used_size) into
+    /// the beginning of OUT, updates the headers in the copy the way save
+    /// does, and returns the number of bytes written.  Like save_no_inc,
+    /// this does not change unique_apcb_instance.  Fails with OutOfSpace if
+    /// OUT is too small.
+    pub fn compact_into(&self, out: &mut [u8]) -> Result<usize> {
+        let bytes = self.live_bytes()?;
+        let out = out.get_mut(..bytes.len()).ok_or(Error::OutOfSpace)?;
+        out.copy_from_slice(bytes);
+        Self::finalize_bytes(
+            out,
+            self.header_checksum_fn,
+            self.integrity_sign_fn,
+        )?;
+        Ok(out.len())
+    }
+
+    /// Heuristically checks whether the APCB looks like it was written by
+    /// our save(): the checksum is valid, all the entry sizes are aligned
+    /// (so there's no padding between entries), the bytes after the last
+    /// struct in each modeled struct entry (the padding) are zero, and the
+    /// group sizes and the APCB size exactly cover their contents.
+    pub fn looks_canonical(&self) -> bool {
+        self.check_canonical().unwrap_or(false)
+    }
+
+    fn check_canonical(&self) -> Result<bool> {
+        let header = self.header()?;
+        let checksum_byte = Self::calculate_checksum(
+            &header,
+            &self.v3_header_ext()?,
+            self.beginning_of_groups()?,
+        )?;
+        if header.checksum_byte != checksum_byte {
+            return Ok(false);
+        }
+        let mut apcb_size = usize::from(header.header_size.get());
+        for group in self.groups()? {
+            let group_size = group.header.group_size.get() as usize;
+            let mut used_size = size_of::<GROUP_HEADER>();
+            for entry in group.entries() {
+                let entry_size = entry.header.entry_size.get() as usize;
+                if entry_size % ENTRY_ALIGNMENT != 0 {
+                    return Ok(false);
+                }
+                if let Some(trailing_bytes) = entry.trailing_bytes() {
+                    if trailing_bytes.iter().any(|&b| b != 0) {
+                        return Ok(false);
+                    }
+                }
+                used_size += entry_size;
+            }
+            if used_size != group_size {
+                return Ok(false);
+            }
+            apcb_size += group_size;
+        }
+        Ok(apcb_size == header.apcb_size.get() as usize)
+    }
+
+    /// Returns aggregate counts of groups, entries and tokens.
+    pub fn stats(&self) -> Result<ApcbStats> {
+        let mut result = ApcbStats {
+            total_size: self.header()?.apcb_size.get() as usize,
+            ..ApcbStats::default()
+        };
+        for group in self.groups()? {
+            result.group_count += 1;
+            for entry in group.entries() {
+                result.entry_count += 1;
+                if let EntryItemBody::<_>::Tokens(tokens) = &entry.body {
+                    result.token_count += tokens.iter()?.count();
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Finds entries that are duplicated verbatim across board instance
+    /// masks, and so could be collapsed into one entry (with the union of
+    /// the masks).  Entries only count as duplicates if everything but
+    /// their board_instance_mask (including instance_id and priority_mask)
+    /// is the same.  Returns, for each set of duplicates, their entry id
+    /// and their board instance masks (in storage order).
+    #[cfg(feature = "std")]
+    pub fn redundant_entries(&self) -> Result<Vec<(EntryId, Vec<u16>)>> {
+        let mut result: Vec<(EntryId, Vec<u16>)> = Vec::new();
+        let mut indices = BTreeMap::<Vec<u8>, usize>::new();
+        for (_, entry) in self.all_entries()? {
+            let mut header = entry.header.clone();
+            header.board_instance_mask.set(0);
+            let mut key = header.as_bytes().to_vec();
+            match &entry.body {
+                EntryItemBody::<_>::Struct(buf) => key.extend_from_slice(buf),
+                EntryItemBody::<_>::Tokens(_) => key.extend_from_slice(
+                    entry.body.token_entries_raw()?.as_bytes(),
+                ),
+            }
+            let board_instance_mask = entry.header.board_instance_mask.get();
+            match indices.get(&key) {
+                Some(&i) => result[i].1.push(board_instance_mask),
+                None => {
+                    indices.insert(key, result.len());
+                    result.push((entry.id(), vec![board_instance_mask]));
+                }
+            }
+        }
+        result
+            .retain(|(_, board_instance_masks)| board_instance_masks.len() > 1);
+        Ok(result)
+    }
+
+    /// Returns how OTHER differs from self.  Fields that save() recomputes
+    /// (checksum_byte, apcb_size, group_size, entry_size,
+    /// unique_apcb_instance, header_checksum, integrity_sign) are ignored.
+    /// Tokens are compared by key, so their order does not matter.
+    #[cfg(feature = "std")]
+    pub fn diff(&self, other: &Apcb<'_>) -> Result<Vec<ApcbDiffItem>> {
+        let mut result = Vec::new();
+        if self.header_bytes_for_diff()? != other.header_bytes_for_diff()? {
+            result.push(ApcbDiffItem::HeaderChanged);
+        }
+        for group in self.groups()? {
+            let group_id = group.id();
+            let Some(other_group) = other.group(group_id)? else {
+                result.push(ApcbDiffItem::GroupRemoved { group_id });
+                continue;
+            };
+            let mut header = group.header.clone();
+            header.group_size.set(0);
+            let mut other_header = other_group.header.clone();
+            other_header.group_size.set(0);
+            if header.as_bytes() != other_header.as_bytes() {
+                result.push(ApcbDiffItem::GroupChanged { group_id });
+            }
+            for entry in group.entries() {
+                let entry_id = entry.id();
+                let instance_id = entry.instance_id();
+                let board_instance_mask = entry.board_instance_mask();
+                match other_group.entry_exact(
+                    entry_id,
+                    instance_id,
+                    board_instance_mask,
+                ) {
+                    Some(other_entry) => {
+                        Self::diff_entry(&entry, &other_entry, &mut result)?
+                    }
+                    None => result.push(ApcbDiffItem::EntryRemoved {
+                        entry_id,
+                        instance_id,
+                        board_instance_mask,
+                    }),
+                }
+            }
+            for other_entry in other_group.entries() {
+                let entry_id = other_entry.id();
+                let instance_id = other_entry.instance_id();
+                let board_instance_mask = other_entry.board_instance_mask();
+                if group
+                    .entry_exact(entry_id, instance_id, board_instance_mask)
+                    .is_none()
+                {
+                    result.push(ApcbDiffItem::EntryAdded {
+                        entry_id,
+                        instance_id,
+                        board_instance_mask,
+                    });
+                }
+            }
+        }
+        for other_group in other.groups()? {
+            let group_id = other_group.id();
+            if self.group(group_id)?.is_none() {
+                result.push(ApcbDiffItem::GroupAdded { group_id });
+            }
+        }
+        Ok(result)
+    }
+
+    /// Returns the V2_HEADER and the V3_HEADER_EXT (if any) as bytes, with
+    /// the fields that diff ignores set to 0.
+    #[cfg(feature = "std")]
+    fn header_bytes_for_diff(&self) -> Result<Vec<u8>> {
+        let mut header = *self.header()?;
+        header.apcb_size.set(0);
+        header.unique_apcb_instance.set(0);
+        header.checksum_byte = 0;
+        let mut result = header.as_bytes().to_vec();
+        if let Some(header_ext) = self.v3_header_ext()? {
+            let mut header_ext = *header_ext;
+            header_ext.header_checksum = 0;
+            header_ext.integrity_sign = [0; 32];
+            result.extend_from_slice(header_ext.as_bytes());
+        }
+        Ok(result)
+    }
+
+    /// Returns whether self and OTHER have the same logical content, that
+    /// is, whether diff finds no differences (and does not fail).  This is
+    /// deliberately not PartialEq, since it ignores recomputed fields and
+    /// the order of groups, entries and tokens.
+    #[cfg(feature = "std")]
+    pub fn content_eq(&self, other: &Apcb<'_>) -> bool {
+        matches!(self.diff(other), Ok(differences) if differences.is_empty())
+    }
+
+    #[cfg(feature = "std")]
+    fn diff_entry(
+        entry: &EntryItem<'_>,
+        other_entry: &EntryItem<'_>,
+        result: &mut Vec<ApcbDiffItem>,
+    ) -> Result<()> {
+        let entry_id = entry.id();
+        let instance_id = entry.instance_id();
+        let board_instance_mask = entry.board_instance_mask();
+        let header_changed = entry.header.context_type
+            != other_entry.header.context_type
+            || entry.header.context_format != other_entry.header.context_format
+            || entry.unit_size() != other_entry.unit_size()
+            || entry.priority_mask() != other_entry.priority_mask()
+            || entry.key_size() != other_entry.key_size()
+            || entry.key_pos() != other_entry.key_pos();
+        let body_changed = match (&entry.body, &other_entry.body) {
+            (
+                EntryItemBody::<_>::Tokens(tokens),
+                EntryItemBody::<_>::Tokens(other_tokens),
+            ) => {
+                let tokens = tokens
+                    .iter()?
+                    .map(|token| (token.id(), token.value()))
+                    .collect::<BTreeMap<_, _>>();
+                let other_tokens = other_tokens
+                    .iter()?
+                    .map(|token| (token.id(), token.value()))
+                    .collect::<BTreeMap<_, _>>();
+                let token_ids = tokens.keys().chain(
+                    other_tokens.keys().filter(|key| !tokens.contains_key(key)),
+                );
+                for &token_id in token_ids {
+                    let old = tokens.get(&token_id).copied();
+                    let new = other_tokens.get(&token_id).copied();
+                    if old != new {
+                        result.push(ApcbDiffItem::TokenChanged {
+                            entry_id,
+                            instance_id,
+                            board_instance_mask,
+                            token_id,
+                            old,
+                            new,
+                        });
+                    }
+                }
+                false
+            }
+            (
+                EntryItemBody::<_>::Struct(buf),
+                EntryItemBody::<_>::Struct(other_buf),
+            ) => buf != other_buf,
+            _ => true,
+        };
+        if header_changed || body_changed {
+            result.push(ApcbDiffItem::EntryChanged {
+                entry_id,
+                instance_id,
+                board_instance_mask,
+            });
+        }
+        Ok(())
+    }
+
     /// This function does not increment the unique_apcb_instance, and thus
     /// should only be used during an initial build of the APCB. In cases where
     /// one is updating an existing apcb binary, one should always call save()
     pub fn save_no_inc(mut self) -> Result<PtrMut<'a, [u8]>> {
-        self.update_checksum()?;
+        self.finalize()?;
         Ok(self.backing_store)
     }
 
     /// User is expected to call this once after modifying anything in the apcb
     /// (including insertions and deletions). We update both the checksum
-    /// and the unique_apcb_instance.
+    /// and the unique_apcb_instance (to the value pinned by
+    /// ApcbIoOptions::with_unique_apcb_instance, if any).
     pub fn save(mut self) -> Result<PtrMut<'a, [u8]>> {
-        let unique_apcb_instance = self.unique_apcb_instance()?;
-        self.header_mut()?
-            .unique_apcb_instance
-            .set(unique_apcb_instance.wrapping_add(1));
-        self.update_checksum()?;
+        let unique_apcb_instance = match self.pinned_unique_apcb_instance {
+            Some(value) => value,
+            None => self.unique_apcb_instance()?.wrapping_add(1),
+        };
+        self.header_mut()?.unique_apcb_instance.set(unique_apcb_instance);
+        self.finalize()?;
         Ok(self.backing_store)
     }
 
     pub fn create(
+        bs: PtrMut<'a, [u8]>,
+        initial_unique_apcb_instance: u32,
+        options: &ApcbIoOptions,
+    ) -> Result<Self> {
+        Self::create_with_header(
+            bs,
+            initial_unique_apcb_instance,
+            true,
+            options,
+        )
+    }
+
+    /// Like create, but for Naples: Creates an APCB with only a V2_HEADER
+    /// (with the Naples version), without a V3_HEADER_EXT.
+    pub fn create_v2_only(
+        bs: PtrMut<'a, [u8]>,
+        initial_unique_apcb_instance: u32,
+        options: &ApcbIoOptions,
+    ) -> Result<Self> {
+        Self::create_with_header(
+            bs,
+            initial_unique_apcb_instance,
+            false,
+            options,
+        )
+    }
+
+    fn create_with_header(
         #[allow(unused_mut)] mut bs: PtrMut<'a, [u8]>,
         initial_unique_apcb_instance: u32,
+        with_v3_header_ext: bool,
         options: &ApcbIoOptions,
     ) -> Result<Self> {
         #[cfg(not(feature = "std"))]
@@ -1380,19 +3423,30 @@ impl<'a> Apcb<'a> {
             *header = Default::default();
             header.unique_apcb_instance.set(initial_unique_apcb_instance);
 
-            let v3_header_ext =
-                take_header_from_collection_mut::<V3_HEADER_EXT>(
-                    &mut backing_store,
-                )
+            if with_v3_header_ext {
+                let v3_header_ext = take_header_from_collection_mut::<
+                    V3_HEADER_EXT,
+                >(&mut backing_store)
                 .ok_or(Error::FileSystem(
                     FileSystemError::InconsistentHeader,
                     "V3_HEADER_EXT",
                 ))?;
-            *v3_header_ext = Default::default();
+                *v3_header_ext = Default::default();
+                if let Some(header_checksum_fn) = options.header_checksum_fn {
+                    v3_header_ext.header_checksum =
+                        Self::calculate_header_checksum(
+                            v3_header_ext,
+                            header_checksum_fn,
+                        );
+                }
 
-            header.header_size.set(
-                (size_of::<V2_HEADER>() + size_of::<V3_HEADER_EXT>()) as u16,
-            );
+                header.header_size.set(
+                    (size_of::<V2_HEADER>() + size_of::<V3_HEADER_EXT>())
+                        as u16,
+                );
+            } else {
+                header.version.set(Self::NAPLES_VERSION);
+            }
             header.apcb_size = (header.header_size.get() as u32).into();
         }
         let (header, rest) =
@@ -1400,9 +3454,12 @@ impl<'a> Apcb<'a> {
                 &*backing_store,
             )
             .unwrap();
-        let (v3_header_ext, rest) = LayoutVerified::<&'_ [u8], V3_HEADER_EXT>::new_unaligned_from_prefix(rest).unwrap();
-        let checksum_byte =
-            Self::calculate_checksum(&header, &Some(v3_header_ext), rest)?;
+        let checksum_byte = if with_v3_header_ext {
+            let (v3_header_ext, rest) = LayoutVerified::<&'_ [u8], V3_HEADER_EXT>::new_unaligned_from_prefix(rest).unwrap();
+            Self::calculate_checksum(&header, &Some(v3_header_ext), rest)?
+        } else {
+            Self::calculate_checksum(&header, &None, rest)?
+        };
 
         let (mut header, _) = LayoutVerified::<&'_ mut [u8], V2_HEADER>::new_unaligned_from_prefix(backing_store).unwrap();
         header.checksum_byte = checksum_byte;
@@ -1413,6 +3470,67 @@ impl<'a> Apcb<'a> {
     pub fn unique_apcb_instance(&self) -> Result<u32> {
         Ok(self.header()?.unique_apcb_instance.get())
     }
+    /// Returns the mutating operations performed so far, oldest first.
+    /// This is empty unless ApcbIoOptions::record_edits was set on
+    /// load/create.  Operations are only recorded once they succeeded.
+    /// Changes made directly through header_mut, v3_header_ext_mut,
+    /// group_mut or all_entries_mut are not recorded.
+    #[cfg(feature = "std")]
+    pub fn edit_log(&self) -> &[ApcbEdit] {
+        self.edit_log.as_deref().unwrap_or(&[])
+    }
+    #[cfg(feature = "std")]
+    pub(crate) fn record_edit(&mut self, edit: ApcbEdit) {
+        if let Some(edit_log) = &mut self.edit_log {
+            edit_log.push(edit);
+        }
+    }
+    #[cfg(not(feature = "std"))]
+    pub(crate) fn record_edit(&mut self, _edit: ApcbEdit) {}
+    /// Moves the comment (if any) of the given entry over to
+    /// NEW_BOARD_INSTANCE_MASK--or, if that is None, drops it.
+    #[cfg(feature = "serde")]
+    fn move_entry_comment(
+        &mut self,
+        entry_id: EntryId,
+        instance_id: u16,
+        board_instance_mask: BoardInstances,
+        new_board_instance_mask: Option<BoardInstances>,
+    ) {
+        let key =
+            entry_comment_key_of(entry_id, instance_id, board_instance_mask);
+        if let Some(comment) = self.comments.remove(&key) {
+            if let Some(new_board_instance_mask) = new_board_instance_mask {
+                self.comments.insert(
+                    entry_comment_key_of(
+                        entry_id,
+                        instance_id,
+                        new_board_instance_mask,
+                    ),
+                    comment,
+                );
+            }
+        }
+    }
+    #[cfg(not(feature = "serde"))]
+    fn move_entry_comment(
+        &mut self,
+        _entry_id: EntryId,
+        _instance_id: u16,
+        _board_instance_mask: BoardInstances,
+        _new_board_instance_mask: Option<BoardInstances>,
+    ) {
+    }
+    /// Drops the comments of all the entries in the given group.
+    #[cfg(feature = "serde")]
+    fn forget_group_comments(&mut self, group_id: GroupId) {
+        let group_id = group_id.to_u16().unwrap();
+        self.comments.retain(|&(comment_group_id, _, _, _), _| {
+            comment_group_id != group_id
+        });
+    }
+    #[cfg(not(feature = "serde"))]
+    fn forget_group_comments(&mut self, _group_id: GroupId) {}
     /// Constructs a attribute accessor proxy for the given combination of
     /// (INSTANCE_ID, BOARD_INSTANCE_MASK).  ENTRY_ID is inferred on access.
     /// PRIORITY_MASK is used if the entry needs to be created.
@@ -1443,6 +3561,118 @@ impl<'a> Apcb<'a> {
     ) -> Result<Tokens<'a, 'b>> {
         Tokens::new(self, instance_id, board_instance_mask)
     }
+    /// Checks that the token with the given KEY exists in one of the token
+    /// entries with exactly BOARD_INSTANCE_MASK and has the value EXPECTED.
+    pub fn assert_token(
+        &self,
+        key: u32,
+        board_instance_mask: u16,
+        expected: u32,
+    ) -> Result<()> {
+        let not_found =
+            || Error::TokenNotFound { entry_id: None, token_id: key };
+        let group = self.group(GroupId::Token)?.ok_or_else(not_found)?;
+        for entry in group.entries() {
+            if entry.header.board_instance_mask.get() != board_instance_mask {
+                continue;
+            }
+            if let EntryItemBody::<_>::Tokens(tokens) = &entry.body {
+                if let Some(token) = tokens.token(key) {
+                    let actual = token.value();
+                    return if actual == expected {
+                        Ok(())
+                    } else {
+                        Err(Error::TokenValueMismatch { key, expected, actual })
+                    };
+                }
+            }
+        }
+        Err(not_found())
+    }
+    /// Returns how many tokens there are in the token entries with the given
+    /// ENTRY_ID, summed over all instances and board instance masks.
+    pub fn token_count(&self, entry_id: EntryId) -> Result<usize> {
+        let EntryId::Token(entry_id) = entry_id else {
+            return Err(Error::EntryTypeMismatch);
+        };
+        let mut result = 0usize;
+        self.for_each_token_entry_count(|token_entry_id, _, count| {
+            if token_entry_id == entry_id {
+                result += count;
+            }
+        })?;
+        Ok(result)
+    }
+    /// Returns how many tokens there are per token width (token entry), for
+    /// the token entries with exactly BOARD_INSTANCE_MASK.
+    #[cfg(feature = "std")]
+    pub fn token_counts_by_width(
+        &self,
+        board_instance_mask: BoardInstances,
+    ) -> Result<BTreeMap<TokenEntryId, usize>> {
+        let mut result = BTreeMap::new();
+        self.for_each_token_entry_count(|token_entry_id, mask, count| {
+            if mask == board_instance_mask {
+                *result.entry(token_entry_id).or_insert(0) += count;
+            }
+        })?;
+        Ok(result)
+    }
+    /// Calls F with the token entry id, the board instance mask and the
+    /// number of tokens of each token entry.
+    fn for_each_token_entry_count(
+        &self,
+        mut f: impl FnMut(TokenEntryId, BoardInstances, usize),
+    ) -> Result<()> {
+        if let Some(group) = self.group(GroupId::Token)? {
+            for entry in group.entries() {
+                match (entry.id(), &entry.body) {
+                    (
+                        EntryId::Token(token_entry_id),
+                        EntryItemBody::<_>::Tokens(tokens),
+                    ) => {
+                        f(
+                            token_entry_id,
+                            entry.board_instance_mask(),
+                            tokens.iter()?.count(),
+                        );
+                    }
+                    _ => return Err(Error::EntryTypeMismatch),
+                }
+            }
+        }
+        Ok(())
+    }
+    /// Returns all the tokens (of all token entries) as (token entry id,
+    /// key, value), sorted by key.  This does not depend on (nor change)
+    /// the order the tokens are stored in.
+    #[cfg(feature = "std")]
+    pub fn tokens_sorted(
+        &self,
+    ) -> Result<impl Iterator<Item = (TokenEntryId, u32, u32)>> {
+        let mut result = Vec::new();
+        if let Some(group) = self.group(GroupId::Token)? {
+            for entry in group.entries() {
+                match (entry.id(), &entry.body) {
+                    (
+                        EntryId::Token(token_entry_id),
+                        EntryItemBody::<_>::Tokens(tokens),
+                    ) => {
+                        for token in tokens.iter()? {
+                            result.push((
+                                token_entry_id,
+                                token.id(),
+                                token.value(),
+                            ));
+                        }
+                    }
+                    _ => return Err(Error::EntryTypeMismatch),
+                }
+            }
+        }
+        result.sort_by_key(|&(token_entry_id, key, _)| (key, token_entry_id));
+        Ok(result.into_iter())
+    }
     /// Ensures that the APCB is compatible with the ABL0_VERSION given
     /// (which is supposed to be the version extracted from the Abl0 blob
     /// file--or None if it could not be found).
@@ -1469,4 +3699,344 @@ impl<'a> Apcb<'a> {
         }
         Ok(())
     }
+
+    /// Reports entries and tokens that silicon generation GENERATION would
+    /// not understand.  An empty result means that no problems were found.
+    #[cfg(feature = "std")]
+    pub fn check_for_silicon(
+        &self,
+        generation: SiliconGeneration,
+    ) -> Result<Vec<SiliconIncompatibility>> {
+        let mut result = Vec::new();
+        for group in self.groups()? {
+            for entry in group.entries() {
+                let entry_id = entry.id();
+                let instance_id = entry.instance_id();
+                let board_instance_mask = entry.board_instance_mask();
+                let known = match entry_id {
+                    EntryId::Psp(PspEntryId::Unknown(_))
+                    | EntryId::Ccx(CcxEntryId::Unknown(_))
+                    | EntryId::Df(DfEntryId::Unknown(_))
+                    | EntryId::Memory(MemoryEntryId::Unknown(_))
+                    | EntryId::Gnb(GnbEntryId::Unknown(_))
+                    | EntryId::Fch(FchEntryId::Unknown(_))
+                    | EntryId::Cbs(CbsEntryId::Unknown(_))
+                    | EntryId::Token(TokenEntryId::Unknown(_))
+                    | EntryId::Unknown(_, _) => false,
+                    // OEM entries are opaque to us.
+                    _ => true,
+                };
+                if !known {
+                    result.push(SiliconIncompatibility::UnknownEntry {
+                        entry_id,
+                        instance_id,
+                        board_instance_mask,
+                    });
+                    continue;
+                }
+                if let EntryId::Memory(memory_entry_id) = entry_id {
+                    let supported = match memory_entry_id.ddr_generation() {
+                        Some(4) => generation.supports_ddr4(),
+                        Some(5) => generation.supports_ddr5(),
+                        _ => true,
+                    };
+                    if !supported {
+                        result.push(
+                            SiliconIncompatibility::UnsupportedMemoryTechnology {
+                                entry_id,
+                                instance_id,
+                                board_instance_mask,
+                            },
+                        );
+                    }
+                }
+                if let (
+                    EntryId::Token(token_entry_id),
+                    EntryItemBody::<_>::Tokens(tokens),
+                ) = (entry_id, &entry.body)
+                {
+                    for token in tokens.iter()? {
+                        let known = match token_entry_id {
+                            TokenEntryId::Bool => {
+                                BoolToken::try_from(token.token).is_ok()
+                            }
+                            TokenEntryId::Byte => {
+                                ByteToken::try_from(token.token).is_ok()
+                            }
+                            TokenEntryId::Word => {
+                                WordToken::try_from(token.token).is_ok()
+                            }
+                            TokenEntryId::Dword => {
+                                DwordToken::try_from(token.token).is_ok()
+                            }
+                            TokenEntryId::Unknown(_) => false,
+                        };
+                        if !known {
+                            result.push(SiliconIncompatibility::UnknownToken {
+                                entry_id: token_entry_id,
+                                token_id: token.id(),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Returns a human-readable dump of all the groups and entries, meant
+    /// for bug reports.  For each entry, this lists its offset (from the
+    /// beginning of the APCB), its raw bytes (including the entry header)
+    /// and, if the entry is modeled, its decoded body.
+    #[cfg(feature = "std")]
+    pub fn diagnostic_dump(&self) -> Result<String> {
+        use std::fmt::Write;
+        fn write_hex(result: &mut String, offset: usize, bytes: &[u8]) {
+            for (i, line) in bytes.chunks(16).enumerate() {
+                let _ = write!(result, "    {:08x}:", offset + i * 16);
+                for byte in line {
+                    let _ = write!(result, " {byte:02x}");
+                }
+                result.push('\n');
+            }
+        }
+        let header = self.header()?;
+        let mut result = String::new();
+        let _ = writeln!(
+            result,
+            "APCB version {:#x}, size {:#x}, unique_apcb_instance {:#x}",
+            header.version.get(),
+            header.apcb_size.get(),
+            self.unique_apcb_instance()?
+        );
+        let beginning_of_groups = self.beginning_of_groups()?;
+        let mut offset = self.backing_store.len() - beginning_of_groups.len();
+        for group in self.groups()? {
+            let group_size = group.header.group_size.get() as usize;
+            let _ = writeln!(
+                result,
+                "group {:?} (signature {:?}) at {offset:#x}, size {group_size:#x}",
+                group.id(),
+                String::from_utf8_lossy(&group.signature()),
+            );
+            let mut entry_offset = offset + size_of::<GROUP_HEADER>();
+            for entry in group.entries() {
+                let entry_size = entry.header.entry_size.get() as usize;
+                let _ = writeln!(
+                    result,
+                    "  entry {:?} instance {:#x} board mask {:#06x} at {entry_offset:#x}, size {entry_size:#x}",
+                    entry.id(),
+                    entry.instance_id(),
+                    u16::from(entry.board_instance_mask()),
+                );
+                match entry.decoded_body() {
+                    Some(decoded) => {
+                        let _ = writeln!(result, "    decoded: {decoded}");
+                    }
+                    None => {
+                        let _ = writeln!(result, "    (not modeled)");
+                    }
+                }
+                let bytes = self
+                    .backing_store
+                    .get(entry_offset..entry_offset + entry_size)
+                    .ok_or(Error::FileSystem(
+                        FileSystemError::InconsistentHeader,
+                        "ENTRY_HEADER::entry_size",
+                    ))?;
+                write_hex(&mut result, entry_offset, bytes);
+                entry_offset += entry_size;
+            }
+            offset += group_size;
+        }
+        Ok(result)
+    }
+
+    /// Reorders the entries of group GROUP_ID according to CMP (stable).
+    /// Note: Insertions assume that entries are in the default order, so
+    /// sort only after all insertions are done.
+    #[cfg(feature = "std")]
+    pub fn sort_entries<F>(&mut self, group_id: GroupId, cmp: F) -> Result<()>
+    where
+        F: FnMut(&EntryItem<'_>, &EntryItem<'_>) -> core::cmp::Ordering,
+    {
+        let mut group =
+            self.group_mut(group_id)?.ok_or(Error::GroupNotFound)?;
+        group.sort_entries(cmp)?;
+        self.record_edit(ApcbEdit::SortEntries { group_id });
+        Ok(())
+    }
+
+    /// Reports the bitfields that have reserved bits set.  This covers
+    /// the entry headers, the modeled struct array and struct sequence
+    /// entries, the beep codes of ErrorOutControl entries and the known
+    /// tokens (for tokens, any value that cannot be decoded as the token's
+    /// type is reported).  An empty result means that no such bits were
+    /// found.
+    #[cfg(feature = "std")]
+    pub fn report_unknown_bits(&self) -> Result<Vec<UnknownBitsReport>> {
+        fn report(
+            entry: &EntryItem<'_>,
+            element_index: usize,
+            field: &'static str,
+            value: u64,
+            result: &mut Vec<UnknownBitsReport>,
+        ) {
+            result.push(UnknownBitsReport {
+                entry_id: entry.id(),
+                instance_id: entry.instance_id(),
+                board_instance_mask: entry.board_instance_mask(),
+                element_index,
+                field,
+                value,
+            });
+        }
+        fn report_item<T: ReservedBits>(
+            entry: &EntryItem<'_>,
+            element_index: usize,
+            item: &T,
+            result: &mut Vec<UnknownBitsReport>,
+        ) {
+            item.visit_reserved_bits(&mut |field, value| {
+                report(entry, element_index, field, value, result)
+            });
+        }
+        fn report_array<T: EntryCompatible + FromBytes + ReservedBits>(
+            entry: &EntryItem<'_>,
+            result: &mut Vec<UnknownBitsReport>,
+        ) {
+            if let Some(items) = entry.body_as_struct_array::<T>() {
+                for (element_index, item) in items.iter().enumerate() {
+                    report_item(entry, element_index, item, result);
+                }
+            }
+        }
+        let mut result = Vec::new();
+        for group in self.groups()? {
+            for entry in group.entries() {
+                report_item(&entry, 0, entry.header, &mut result);
+                report_array::<RdimmDdr4CadBusElement>(&entry, &mut result);
+                report_array::<UdimmDdr4CadBusElement>(&entry, &mut result);
+                report_array::<LrdimmDdr4CadBusElement>(&entry, &mut result);
+                report_array::<Ddr4DataBusElement>(&entry, &mut result);
+                report_array::<LrdimmDdr4DataBusElement>(&entry, &mut result);
+                report_array::<Ddr4OdtPatElement>(&entry, &mut result);
+                report_array::<LrdimmDdr4OdtPatElement>(&entry, &mut result);
+                report_array::<MaxFreqElement>(&entry, &mut result);
+                if let Some(sequence) = entry.body_as_struct_sequence::<
+                    platform_specific_override::ElementRef<'_>,
+                >() {
+                    for (element_index, item) in sequence.iter()?.enumerate()
+                    {
+                        report_item(&entry, element_index, &item, &mut result);
+                    }
+                }
+                let beep_codes = if let Some((header, _)) =
+                    entry.body_as_struct::<ErrorOutControl116>()
+                {
+                    Some(header.beep_code_table()?)
+                } else if let Some((header, _)) =
+                    entry.body_as_struct::<ErrorOutControl112>()
+                {
+                    Some(header.beep_code_table()?)
+                } else {
+                    None
+                };
+                for (element_index, item) in
+                    beep_codes.iter().flatten().enumerate()
+                {
+                    report_item(&entry, element_index, item, &mut result);
+                }
+                if let (
+                    EntryId::Token(token_entry_id),
+                    EntryItemBody::<_>::Tokens(tokens),
+                ) = (entry.id(), &entry.body)
+                {
+                    for (element_index, token) in tokens.iter()?.enumerate() {
+                        let (key, value) = (token.id(), token.value());
+                        let name = match token_entry_id {
+                            TokenEntryId::Bool => {
+                                BoolToken::undecodable_token_name(key, value)
+                            }
+                            TokenEntryId::Byte => {
+                                ByteToken::undecodable_token_name(key, value)
+                            }
+                            TokenEntryId::Word => {
+                                WordToken::undecodable_token_name(key, value)
+                            }
+                            TokenEntryId::Dword => {
+                                DwordToken::undecodable_token_name(key, value)
+                            }
+                            TokenEntryId::Unknown(_) => None,
+                        };
+                        if let Some(name) = name {
+                            report(
+                                &entry,
+                                element_index,
+                                name,
+                                value.into(),
+                                &mut result,
+                            );
+                        }
+                    }
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Returns a copy of this APCB (as saved bytes) that only has the
+    /// entries that apply to board instance BOARD_INSTANCE.
+    #[cfg(feature = "std")]
+    pub fn specialize_for_board(
+        &self,
+        board_instance: BoardInstance,
+    ) -> Result<Vec<u8>> {
+        let board_instance_mask =
+            u16::from(BoardInstances::from_instance(board_instance)?);
+        let mut foreign_entries = Vec::new();
+        for group in self.groups()? {
+            for entry in group.entries() {
+                let entry_mask = entry.board_instance_mask();
+                if u16::from(entry_mask) & board_instance_mask == 0 {
+                    foreign_entries.push((
+                        entry.id(),
+                        entry.instance_id(),
+                        entry_mask,
+                    ));
+                }
+            }
+        }
+        let mut apcb = self.clone();
+        for (entry_id, instance_id, entry_mask) in foreign_entries {
+            apcb.delete_entry(entry_id, instance_id, entry_mask)?;
+        }
+        Ok(apcb.save()?.into_owned())
+    }
+
+    /// For each board instance that has board-specific entries (that is,
+    /// entries that do not apply to all boards), returns a specialized
+    /// APCB (see specialize_for_board).
+    #[cfg(feature = "std")]
+    pub fn split_by_board(&self) -> Result<BTreeMap<BoardInstance, Vec<u8>>> {
+        let mut board_instances_mask = 0u16;
+        for group in self.groups()? {
+            for entry in group.entries() {
+                let entry_mask = entry.board_instance_mask();
+                if entry_mask != BoardInstances::all() {
+                    board_instances_mask |= u16::from(entry_mask);
+                }
+            }
+        }
+        let mut result = BTreeMap::new();
+        for board_instance in 0..16 {
+            if board_instances_mask & (1 << board_instance) != 0 {
+                result.insert(
+                    board_instance,
+                    self.specialize_for_board(board_instance)?,
+                );
+            }
+        }
+        Ok(result)
+    }
 }
@@ -3,14 +3,16 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 use crate::naples::ParameterTokenConfig;
+use crate::ondisk::memory::{MemDfeSearchElement32, MemDfeSearchElement36};
 use crate::ondisk::ENTRY_HEADER;
+use crate::ondisk::TOKEN_ENTRY;
 use crate::ondisk::{
     take_header_from_collection, take_header_from_collection_mut,
     BoardInstances, ContextFormat, ContextType, EntryCompatible, EntryId,
     HeaderWithTail, MutSequenceElementFromBytes, PriorityLevels,
     SequenceElementFromBytes,
 };
-use crate::ondisk::{Parameters, ParametersIter};
+use crate::ondisk::{MemoryEntryId, Parameters, ParametersIter};
 use crate::tokens_entry::TokensEntryBodyItem;
 use crate::types::{
     ApcbContext, Error, FileSystemError, MemDfeSearchVersion, Result,
@@ -22,7 +24,7 @@ use pre::pre;
 use zerocopy::{AsBytes, FromBytes};
 
 #[cfg(feature = "serde")]
-use crate::ondisk::{Parameter, TOKEN_ENTRY};
+use crate::ondisk::Parameter;
 #[cfg(feature = "serde")]
 use serde::de::{self, Deserialize, Deserializer, MapAccess, Visitor};
 #[cfg(feature = "serde")]
@@ -108,6 +110,28 @@ impl<'a> EntryItemBody<&'a [u8]> {
             ContextType::Parameters => Err(Error::EntryTypeMismatch),
         }
     }
+
+    /// Returns the body bytes following the header struct T, i.e. the part
+    /// of the entry this crate does not (or not fully) model.
+    /// Note: No compatibility check of T against the entry is done here.
+    pub fn tail_bytes<T: HeaderWithTail>(&self) -> Result<&'a [u8]> {
+        match self {
+            EntryItemBody::Struct(buf) => {
+                buf.get(size_of::<T>()..).ok_or(Error::EntryRange)
+            }
+            EntryItemBody::Tokens(_) => Err(Error::EntryTypeMismatch),
+        }
+    }
+
+    /// Returns the tokens of a token entry as a slice of (undecoded)
+    /// TOKEN_ENTRY, for bulk processing.
+    pub fn token_entries_raw(&self) -> Result<&'a [TOKEN_ENTRY]> {
+        match self {
+            EntryItemBody::Tokens(tokens) => tokens.raw_entries(),
+            EntryItemBody::Struct(_) => Err(Error::EntryTypeMismatch),
+        }
+    }
+
     pub(crate) fn validate(&self) -> Result<()> {
         match self {
             EntryItemBody::Tokens(tokens) => {
@@ -304,6 +328,34 @@ impl<'a> EntryMutItem<'a> {
         self.header.set_priority_mask(value);
     }
 
+    /// Sets the context_format.  For token entries, switching to
+    /// ContextFormat::SortAscending also sorts the tokens and sets up
+    /// unit_size, key_size and key_pos for that.  We don't know the sort
+    /// keys of struct entries, so these can only be switched to
+    /// ContextFormat::Raw.
+    pub(crate) fn set_context_format(
+        &mut self,
+        value: ContextFormat,
+    ) -> Result<()> {
+        match &mut self.body {
+            EntryItemBody::<_>::Tokens(tokens) => {
+                tokens.set_context_format(value)?;
+                if value == ContextFormat::SortAscending {
+                    self.header.unit_size = 8;
+                    self.header.key_size = 4;
+                    self.header.key_pos = 0;
+                }
+            }
+            EntryItemBody::<_>::Struct(_) => {
+                if value != ContextFormat::Raw {
+                    return Err(Error::EntryTypeMismatch);
+                }
+            }
+        }
+        self.header.context_format = value as u8;
+        Ok(())
+    }
+
     // Note: Because entry_id, instance_id, group_id and board_instance_mask are
     // sort keys, these cannot be mutated.
 
@@ -436,6 +488,8 @@ pub struct EntryItem<'a> {
 pub struct SerdeEntryItem {
     pub(crate) header: ENTRY_HEADER,
     pub(crate) body: Vec<u8>,
+    /// Human annotation ("_comment").  It's not stored in the blob.
+    pub(crate) comment: Option<String>,
 }
 
 #[cfg(feature = "schemars")]
@@ -527,6 +581,10 @@ impl<'a> schemars::JsonSchema for EntryItem<'a> {
             "RdimmDdr5BusElement".to_owned(),
             <Vec<memory::RdimmDdr5BusElement>>::json_schema(gen),
         );
+        obj.properties.insert(
+            "RdimmDdr5BusElement1240".to_owned(),
+            <Vec<memory::RdimmDdr5BusElement1240>>::json_schema(gen),
+        );
         obj.properties.insert(
             "ConsoleOutControl".to_owned(),
             <memory::ConsoleOutControl>::json_schema(gen),
@@ -603,6 +661,8 @@ impl<'a> schemars::JsonSchema for EntryItem<'a> {
 
         obj.properties
             .insert("parameters".to_owned(), <Parameters>::json_schema(gen));
+        obj.properties
+            .insert("_comment".to_owned(), <String>::json_schema(gen));
         schema.into()
     }
 }
@@ -621,6 +681,130 @@ impl schemars::JsonSchema for SerdeEntryItem {
     }
 }
 
+/// Invokes $m! with the list of struct body types that EntryItem knows
+/// how to decode, in the order in which they are tried.  Each item is
+/// "KIND NAME = TYPE;", where NAME is the serde field name and KIND is one
+/// of:
+/// * array: body_as_struct_array::<TYPE>.
+/// * header: body_as_struct::<TYPE>, of which only the header is of
+///   interest.
+/// * header_tail: body_as_struct::<TYPE>, with an array tail.
+/// * sequence: body_as_struct_sequence::<TYPE>.
+/// * parameters: body_as_struct::<TYPE>, with a ParametersIter tail.
+/// * mem_dfe_search(VERSION): body_as_struct_array::<TYPE>, if the
+///   ApcbContext selects that MemDfeSearchVersion.
+macro_rules! for_each_struct_body {
+    ($m:ident) => {
+        $m! {
+            array LrdimmDdr4OdtPatElement = memory::LrdimmDdr4OdtPatElement;
+            array Ddr4OdtPatElement = memory::Ddr4OdtPatElement;
+            array DdrPostPackageRepairElement = memory::DdrPostPackageRepairElement;
+            array DimmInfoSmbusElement = memory::DimmInfoSmbusElement;
+            array RdimmDdr4CadBusElement = memory::RdimmDdr4CadBusElement;
+            array UdimmDdr4CadBusElement = memory::UdimmDdr4CadBusElement;
+            array LrdimmDdr4CadBusElement = memory::LrdimmDdr4CadBusElement;
+            array Ddr4DataBusElement = memory::Ddr4DataBusElement;
+            array LrdimmDdr4DataBusElement = memory::LrdimmDdr4DataBusElement;
+            array MaxFreqElement = memory::MaxFreqElement;
+            array LrMaxFreqElement = memory::LrMaxFreqElement;
+            array Ddr5CaPinMapElement = memory::Ddr5CaPinMapElement;
+            array DdrDqPinMapElement = memory::DdrDqPinMapElement;
+            array RdimmDdr5BusElement = memory::RdimmDdr5BusElement;
+            array RdimmDdr5BusElement1240 = memory::RdimmDdr5BusElement1240;
+            header ConsoleOutControl = memory::ConsoleOutControl;
+            header NaplesConsoleOutControl = memory::NaplesConsoleOutControl;
+            header ExtVoltageControl = memory::ExtVoltageControl;
+            header ErrorOutControl116 = memory::ErrorOutControl116;
+            header ErrorOutControl112 = memory::ErrorOutControl112;
+            header SlinkConfig = df::SlinkConfig;
+            header_tail BoardIdGettingMethodGpio = psp::BoardIdGettingMethodGpio;
+            header_tail BoardIdGettingMethodEeprom = psp::BoardIdGettingMethodEeprom;
+            header_tail BoardIdGettingMethodSmbus = psp::BoardIdGettingMethodSmbus;
+            header_tail BoardIdGettingMethodCustom = psp::BoardIdGettingMethodCustom;
+            header EspiInit = fch::EspiInit;
+            array PmuBistVendorAlgorithmElement = memory::PmuBistVendorAlgorithmElement;
+            array Ddr5RawCardConfigElement = memory::Ddr5RawCardConfigElement;
+            array EspiSioInitElement = fch::EspiSioInitElement; // TODO terminator, so variant
+            array EarlyPcieConfigElement = gnb::EarlyPcieConfigElement;
+            sequence platform_specific_overrides = memory::platform_specific_override::ElementRef<'_>;
+            sequence platform_tuning = memory::platform_tuning::ElementRef<'_>;
+            parameters parameters = Parameters;
+            mem_dfe_search(Genoa2) MemDfeSearchElement32 = memory::MemDfeSearchElement32;
+            mem_dfe_search(Turin1) MemDfeSearchElement36 = memory::MemDfeSearchElement36;
+        }
+    };
+}
+
+#[cfg(feature = "std")]
+impl<'a> EntryItem<'a> {
+    /// Returns the decoded body (in Debug format) if the entry is modeled,
+    /// otherwise None.
+    pub(crate) fn decoded_body(&self) -> Option<String> {
+        use crate::{df, fch, gnb, memory, psp};
+        use std::format;
+        macro_rules! try_decode {
+            () => {};
+            (array $name:ident = $t:ty; $($rest:tt)*) => {
+                if let Some(s) = self.body_as_struct_array::<$t>() {
+                    let v = s.iter().collect::<Vec<_>>();
+                    return Some(format!("{v:?}"));
+                }
+                try_decode!($($rest)*);
+            };
+            (header $name:ident = $t:ty; $($rest:tt)*) => {
+                try_decode!(header_tail $name = $t; $($rest)*);
+            };
+            (header_tail $name:ident = $t:ty; $($rest:tt)*) => {
+                if let Some((header, s)) = self.body_as_struct::<$t>() {
+                    let v = s.iter().collect::<Vec<_>>();
+                    return Some(if v.is_empty() {
+                        format!("{header:?}")
+                    } else {
+                        format!("{header:?} {v:?}")
+                    });
+                }
+                try_decode!($($rest)*);
+            };
+            (sequence $name:ident = $t:ty; $($rest:tt)*) => {
+                if let Some(s) = self.body_as_struct_sequence::<$t>() {
+                    let v = s.iter().ok()?.collect::<Vec<_>>();
+                    return Some(format!("{v:?}"));
+                }
+                try_decode!($($rest)*);
+            };
+            (parameters $name:ident = $t:ty; $($rest:tt)*) => {
+                if let Some((_, s)) = self.body_as_struct::<$t>() {
+                    let v = ParametersIter::new(s.into_slice())
+                        .ok()?
+                        .collect::<Vec<_>>();
+                    return Some(format!("{v:?}"));
+                }
+                try_decode!($($rest)*);
+            };
+            (mem_dfe_search($version:ident) $name:ident = $t:ty; $($rest:tt)*) => {
+                if self.context.mem_dfe_search_version()
+                    == Some(MemDfeSearchVersion::$version)
+                {
+                    if let Some(s) = self.body_as_struct_array::<$t>() {
+                        let v = s.iter().collect::<Vec<_>>();
+                        return Some(format!("{v:?}"));
+                    }
+                }
+                try_decode!($($rest)*);
+            };
+        }
+        match &self.body {
+            EntryItemBody::<_>::Tokens(tokens) => {
+                let v = tokens.iter().ok()?.collect::<Vec<_>>();
+                return Some(format!("{v:?}"));
+            }
+            EntryItemBody::<_>::Struct(_) => {}
+        }
+        for_each_struct_body!(try_decode);
+        None
+    }
+}
+
 #[cfg(feature = "serde")]
 impl<'a> Serialize for EntryItem<'a> {
     fn serialize<S>(
@@ -630,16 +814,30 @@ impl<'a> Serialize for EntryItem<'a> {
     where
         S: Serializer,
     {
-        use crate::df::SlinkConfig;
-        use crate::fch;
-        use crate::gnb;
-        use crate::memory;
-        use crate::psp;
-        let mut state = serializer.serialize_struct("EntryItem", 2)?;
+        self.serialize_with_comment(None, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a> EntryItem<'a> {
+    /// Serializes like Serialize does, but also emits COMMENT (if any) as
+    /// "_comment".
+    pub(crate) fn serialize_with_comment<S>(
+        &self,
+        comment: Option<&str>,
+        serializer: S,
+    ) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use crate::{df, fch, gnb, memory, psp};
+        let mut state = serializer.serialize_struct("EntryItem", 3)?;
+        if let Some(comment) = comment {
+            state.serialize_field("_comment", comment)?;
+        }
         state.serialize_field("header", self.header)?;
 
-        // TODO: Automate this type determination instead of maintaining this
-        // manually.
+        // The struct body types are listed in for_each_struct_body.
         match &self.body {
             EntryItemBody::<_>::Tokens(tokens) => {
                 let v = tokens
@@ -649,128 +847,68 @@ impl<'a> Serialize for EntryItem<'a> {
                 state.serialize_field("tokens", &v)?;
             }
             EntryItemBody::<_>::Struct(buf) => {
-                if let Some(s) = self.body_as_struct_array::<memory::LrdimmDdr4OdtPatElement>() {
-                    let v = s.iter().collect::<Vec<_>>();
-                    state.serialize_field("LrdimmDdr4OdtPatElement", &v)?;
-                } else if let Some(s) = self.body_as_struct_array::<memory::Ddr4OdtPatElement>() {
-                    let v = s.iter().collect::<Vec<_>>();
-                    state.serialize_field("Ddr4OdtPatElement", &v)?;
-                } else if let Some(s) = self.body_as_struct_array::<memory::DdrPostPackageRepairElement>() {
-                    let v = s.iter().collect::<Vec<_>>();
-                    state.serialize_field("DdrPostPackageRepairElement", &v)?;
-                } else if let Some(s) = self.body_as_struct_array::<memory::DimmInfoSmbusElement>() {
-                    let v = s.iter().collect::<Vec<_>>();
-                    state.serialize_field("DimmInfoSmbusElement", &v)?;
-                } else if let Some(s) = self.body_as_struct_array::<memory::RdimmDdr4CadBusElement>() {
-                    let v = s.iter().collect::<Vec<_>>();
-                    state.serialize_field("RdimmDdr4CadBusElement", &v)?;
-                } else if let Some(s) = self.body_as_struct_array::<memory::UdimmDdr4CadBusElement>() {
-                    let v = s.iter().collect::<Vec<_>>();
-                    state.serialize_field("UdimmDdr4CadBusElement", &v)?;
-                } else if let Some(s) = self.body_as_struct_array::<memory::LrdimmDdr4CadBusElement>() {
-                    let v = s.iter().collect::<Vec<_>>();
-                    state.serialize_field("LrdimmDdr4CadBusElement", &v)?;
-                } else if let Some(s) = self.body_as_struct_array::<memory::Ddr4DataBusElement>() {
-                    let v = s.iter().collect::<Vec<_>>();
-                    state.serialize_field("Ddr4DataBusElement", &v)?;
-                } else if let Some(s) = self.body_as_struct_array::<memory::LrdimmDdr4DataBusElement>() {
-                    let v = s.iter().collect::<Vec<_>>();
-                    state.serialize_field("LrdimmDdr4DataBusElement", &v)?;
-                } else if let Some(s) = self.body_as_struct_array::<memory::MaxFreqElement>() {
-                    let v = s.iter().collect::<Vec<_>>();
-                    state.serialize_field("MaxFreqElement", &v)?;
-                } else if let Some(s) = self.body_as_struct_array::<memory::LrMaxFreqElement>() {
-                    let v = s.iter().collect::<Vec<_>>();
-                    state.serialize_field("LrMaxFreqElement", &v)?;
-                } else if let Some(s) = self.body_as_struct_array::<memory::Ddr5CaPinMapElement>() {
-                    let v = s.iter().collect::<Vec<_>>();
-                    state.serialize_field("Ddr5CaPinMapElement", &v)?;
-                } else if let Some(s) = self.body_as_struct_array::<memory::DdrDqPinMapElement>() {
-                    let v = s.iter().collect::<Vec<_>>();
-                    state.serialize_field("DdrDqPinMapElement", &v)?;
-                } else if let Some(s) = self.body_as_struct_array::<memory::RdimmDdr5BusElement>() {
-                    let v = s.iter().collect::<Vec<_>>();
-                    state.serialize_field("RdimmDdr5BusElement", &v)?;
-                } else if let Some((s, _)) = self.body_as_struct::<memory::ConsoleOutControl>() {
-                    state.serialize_field("ConsoleOutControl", &s)?;
-                } else if let Some((s, _)) = self.body_as_struct::<memory::NaplesConsoleOutControl>() {
-                    state.serialize_field("NaplesConsoleOutControl", &s)?;
-                } else if let Some((s, _)) = self.body_as_struct::<memory::ExtVoltageControl>() {
-                    state.serialize_field("ExtVoltageControl", &s)?;
-                } else if let Some((s, _)) = self.body_as_struct::<memory::ErrorOutControl116>() {
-                    state.serialize_field("ErrorOutControl116", &s)?;
-                } else if let Some((s, _)) = self.body_as_struct::<memory::ErrorOutControl112>() {
-                    state.serialize_field("ErrorOutControl112", &s)?;
-                } else if let Some((s, _)) = self.body_as_struct::<SlinkConfig>() {
-                    state.serialize_field("SlinkConfig", &s)?;
-                } else if let Some((header, s)) = self.body_as_struct::<psp::BoardIdGettingMethodGpio>() {
-                    let v = s.iter().collect::<Vec<_>>();
-                    let t = (header, v);
-                    state.serialize_field("BoardIdGettingMethodGpio", &t)?;
-                } else if let Some((header, s)) = self.body_as_struct::<psp::BoardIdGettingMethodEeprom>() {
-                    let v = s.iter().collect::<Vec<_>>();
-                    let t = (header, v);
-                    state.serialize_field("BoardIdGettingMethodEeprom", &t)?;
-                } else if let Some((header, s)) = self.body_as_struct::<psp::BoardIdGettingMethodSmbus>() {
-                    let v = s.iter().collect::<Vec<_>>();
-                    let t = (header, v);
-                    state.serialize_field("BoardIdGettingMethodSmbus", &t)?;
-                } else if let Some((header, s)) = self.body_as_struct::<psp::BoardIdGettingMethodCustom>() {
-                    let v = s.iter().collect::<Vec<_>>();
-                    let t = (header, v);
-                    state.serialize_field("BoardIdGettingMethodCustom", &t)?;
-                } else if let Some((header, _)) = self.body_as_struct::<fch::EspiInit>() {
-                    state.serialize_field("EspiInit", &header)?;
-                } else if let Some(s) = self.body_as_struct_array::<memory::PmuBistVendorAlgorithmElement>() {
-                    let v = s.iter().collect::<Vec<_>>();
-                    state.serialize_field("PmuBistVendorAlgorithmElement", &v)?;
-                } else if let Some(s) = self.body_as_struct_array::<memory::Ddr5RawCardConfigElement>() {
-                    let v = s.iter().collect::<Vec<_>>();
-                    state.serialize_field("Ddr5RawCardConfigElement", &v)?;
-                } else if let Some(s) = self.body_as_struct_array::<fch::EspiSioInitElement>() { // TODO terminator, so variant
-                    let v = s.iter().collect::<Vec<_>>();
-                    state.serialize_field("EspiSioInitElement", &v)?;
-                } else if let Some(s) = self.body_as_struct_array::<gnb::EarlyPcieConfigElement>() {
-                    let v = s.iter().collect::<Vec<_>>();
-                    state.serialize_field("EarlyPcieConfigElement", &v)?;
-                } else if let Some(s) =
-self.body_as_struct_sequence::<memory::platform_specific_override::ElementRef<'_>>() {
-                    let i = s.iter().unwrap();
-                    let v = i.collect::<Vec<_>>();
-                    state.serialize_field("platform_specific_overrides", &v)?;
-                } else if let Some(s) =
-self.body_as_struct_sequence::<memory::platform_tuning::ElementRef<'_>>() {
-                    let i = s.iter().unwrap();
-                    let v = i.collect::<Vec<_>>();
-                    state.serialize_field("platform_tuning", &v)?;
-                } else if let Some((_, s)) = self.body_as_struct::<Parameters>() {
-                    let parameters = ParametersIter::new(s.into_slice())
-                        .map_err(|_| serde::ser::Error::custom("could not serialize Parameters"))?;
-                    let v = parameters.collect::<Vec<_>>();
-                    state.serialize_field("parameters", &v)?;
-                } else {
-                    match self.context.mem_dfe_search_version() {
-                        Some(MemDfeSearchVersion::Genoa2) => {
-                            if let Some(s) = self.body_as_struct_array::<memory::MemDfeSearchElement32>() {
-                                    let v = s.iter().collect::<Vec<_>>();
-                                    state.serialize_field("MemDfeSearchElement32", &v)?;
-                            } else {
-                                    state.serialize_field("struct_body", &buf)?;
-                             }
-                        },
-                        Some(MemDfeSearchVersion::Turin1) => {
-                                if let Some(s) = self.body_as_struct_array::<memory::MemDfeSearchElement36>() {
-                                    let v = s.iter().collect::<Vec<_>>();
-                                    state.serialize_field("MemDfeSearchElement36", &v)?;
-                                } else {
-                                    state.serialize_field("struct_body", &buf)?;
-                                }
-                          }
-                          _ => {
-                                state.serialize_field("struct_body", &buf)?;
-                          }
-                    }
+                macro_rules! try_serialize {
+                    () => {};
+                    (array $name:ident = $t:ty; $($rest:tt)*) => {
+                        if let Some(s) = self.body_as_struct_array::<$t>() {
+                            let v = s.iter().collect::<Vec<_>>();
+                            state.serialize_field(stringify!($name), &v)?;
+                            return state.end();
+                        }
+                        try_serialize!($($rest)*);
+                    };
+                    (header $name:ident = $t:ty; $($rest:tt)*) => {
+                        if let Some((header, _)) = self.body_as_struct::<$t>() {
+                            state.serialize_field(stringify!($name), &header)?;
+                            return state.end();
+                        }
+                        try_serialize!($($rest)*);
+                    };
+                    (header_tail $name:ident = $t:ty; $($rest:tt)*) => {
+                        if let Some((header, s)) = self.body_as_struct::<$t>() {
+                            let v = s.iter().collect::<Vec<_>>();
+                            let t = (header, v);
+                            state.serialize_field(stringify!($name), &t)?;
+                            return state.end();
+                        }
+                        try_serialize!($($rest)*);
+                    };
+                    (sequence $name:ident = $t:ty; $($rest:tt)*) => {
+                        if let Some(s) = self.body_as_struct_sequence::<$t>() {
+                            let v = s
+                                .iter()
+                                .map_err(|e| serde::ser::Error::custom(format!("{e:?}")))?
+                                .collect::<Vec<_>>();
+                            state.serialize_field(stringify!($name), &v)?;
+                            return state.end();
+                        }
+                        try_serialize!($($rest)*);
+                    };
+                    (parameters $name:ident = $t:ty; $($rest:tt)*) => {
+                        if let Some((_, s)) = self.body_as_struct::<$t>() {
+                            let parameters = ParametersIter::new(s.into_slice())
+                                .map_err(|_| serde::ser::Error::custom("could not serialize Parameters"))?;
+                            let v = parameters.collect::<Vec<_>>();
+                            state.serialize_field(stringify!($name), &v)?;
+                            return state.end();
+                        }
+                        try_serialize!($($rest)*);
+                    };
+                    (mem_dfe_search($version:ident) $name:ident = $t:ty; $($rest:tt)*) => {
+                        if self.context.mem_dfe_search_version()
+                            == Some(MemDfeSearchVersion::$version)
+                        {
+                            if let Some(s) = self.body_as_struct_array::<$t>() {
+                                let v = s.iter().collect::<Vec<_>>();
+                                state.serialize_field(stringify!($name), &v)?;
+                                return state.end();
+                            }
+                        }
+                        try_serialize!($($rest)*);
+                    };
                 }
+                for_each_struct_body!(try_serialize);
+                state.serialize_field("struct_body", &buf)?;
             }
         }
         state.end()
@@ -932,6 +1070,7 @@ impl<'de> Deserialize<'de> for SerdeEntryItem {
         D: Deserializer<'de>,
     {
         enum Field {
+            Comment,
             Header,
             Tokens,
             // Body as struct array
@@ -951,6 +1090,7 @@ impl<'de> Deserialize<'de> for SerdeEntryItem {
             MemDfeSearchElement32,
             MemDfeSearchElement36,
             RdimmDdr5BusElement,
+            RdimmDdr5BusElement1240,
             EspiSioInitElement, // FIXME move to struct sequence (maybe)
             EarlyPcieConfigElement,
 
@@ -974,6 +1114,7 @@ impl<'de> Deserialize<'de> for SerdeEntryItem {
             Parameters,
         }
         const FIELDS: &[&str] = &[
+            "_comment",
             "header",
             "tokens",
             "LrdimmDdr4OdtPatElement",
@@ -992,6 +1133,7 @@ impl<'de> Deserialize<'de> for SerdeEntryItem {
             "MemDfeSearchElement32",
             "MemDfeSearchElement36",
             "RdimmDdr5BusElement",
+            "RdimmDdr5BusElement1240",
             "EspiSioInitElement",
             "EarlyPcieConfigElement",
             // Body as struct
@@ -1040,6 +1182,7 @@ impl<'de> Deserialize<'de> for SerdeEntryItem {
                         E: de::Error,
                     {
                         match value {
+                            "_comment" => Ok(Field::Comment),
                             "header" => Ok(Field::Header),
                             "tokens" => Ok(Field::Tokens),
                             "LrdimmDdr4OdtPatElement" => {
@@ -1084,6 +1227,9 @@ impl<'de> Deserialize<'de> for SerdeEntryItem {
                             "RdimmDdr5BusElement" => {
                                 Ok(Field::RdimmDdr5BusElement)
                             }
+                            "RdimmDdr5BusElement1240" => {
+                                Ok(Field::RdimmDdr5BusElement1240)
+                            }
                             "EspiSioInitElement" => {
                                 Ok(Field::EspiSioInitElement)
                             }
@@ -1159,8 +1305,17 @@ impl<'de> Deserialize<'de> for SerdeEntryItem {
                 use crate::psp;
                 let mut header: Option<ENTRY_HEADER> = None;
                 let mut body: Option<Vec<u8>> = None;
+                let mut comment: Option<String> = None;
                 while let Some(key) = map.next_key()? {
                     match key {
+                        Field::Comment => {
+                            if comment.is_some() {
+                                return Err(de::Error::duplicate_field(
+                                    "_comment",
+                                ));
+                            }
+                            comment = Some(map.next_value()?);
+                        }
                         Field::Header => {
                             if header.is_some() {
                                 return Err(de::Error::duplicate_field(
@@ -1271,6 +1426,12 @@ impl<'de> Deserialize<'de> for SerdeEntryItem {
                                 &mut body, &mut map,
                             )?;
                         }
+                        Field::RdimmDdr5BusElement1240 => {
+                            struct_vec_to_body::<
+                                memory::RdimmDdr5BusElement1240,
+                                V,
+                            >(&mut body, &mut map)?;
+                        }
 
                         Field::ConsoleOutControl => {
                             struct_to_body::<memory::ConsoleOutControl, V>(
@@ -1369,7 +1530,7 @@ impl<'de> Deserialize<'de> for SerdeEntryItem {
                     header.ok_or_else(|| de::Error::missing_field("header"))?;
                 let body =
                     body.ok_or_else(|| de::Error::missing_field("body"))?;
-                Ok(SerdeEntryItem { header, body })
+                Ok(SerdeEntryItem { header, body, comment })
             }
         }
         let mut result = deserializer.deserialize_struct(
@@ -1480,6 +1641,16 @@ impl<'a, T: 'a + Sized + FromBytes> StructArrayEntryItem<'a, T> {
     }
 }
 
+/// The elements of a MemDfeSearch entry, in the layout of the
+/// MemDfeSearchVersion they were requested for.  See
+/// EntryItem::body_as_mem_dfe_search.
+pub enum MemDfeSearchElements<'a> {
+    /// MemDfeSearchVersion::Genoa2
+    Element32(StructArrayEntryItem<'a, MemDfeSearchElement32>),
+    /// MemDfeSearchVersion::Turin1
+    Element36(StructArrayEntryItem<'a, MemDfeSearchElement36>),
+}
+
 /// Naples
 impl Parameters {
     pub fn iter(
@@ -1570,6 +1741,9 @@ impl<'a> EntryItem<'a> {
             ),
         )?;
         self.body.validate()?;
+        if let Some((_, s)) = self.body_as_struct::<Parameters>() {
+            ParametersIter::new(s.into_slice())?;
+        }
         Ok(())
     }
 
@@ -1580,6 +1754,19 @@ impl<'a> EntryItem<'a> {
         }
     }
 
+    /// Returns (up to) the first LEN bytes of the body.  That is what
+    /// EntryCompatible::is_entry_compatible looks at in order to decide
+    /// which struct the entry has--so this is useful for debugging
+    /// ambiguous entries.
+    /// Note: Token entries are never checked that way, so for them, the
+    /// result is empty.
+    pub fn body_prefix(&self, len: usize) -> &[u8] {
+        match &self.body {
+            EntryItemBody::Struct(buf) => &buf[..len.min(buf.len())],
+            _ => &[],
+        }
+    }
+
     pub fn body_as_struct<
         H: EntryCompatible + Sized + FromBytes + HeaderWithTail,
     >(
@@ -1604,6 +1791,126 @@ impl<'a> EntryItem<'a> {
         }
     }
 
+    /// If this is a (Naples) parameters entry, returns an iterator over its
+    /// parameters.  Otherwise, returns None.
+    /// Note: Parameters entries are stored with ContextType::Struct (entries
+    /// with ContextType::Parameters are refused on load), so they are
+    /// recognized by their entry id instead.
+    pub fn as_parameters(&'a self) -> Option<ParametersIter<'a>> {
+        let (_, tail) = self.body_as_struct::<Parameters>()?;
+        ParametersIter::new(tail.into_slice()).ok()
+    }
+
+    /// Returns the elements of a MemDfeSearch entry in the layout of the
+    /// given VERSION.  Fails with Error::EntryTypeMismatch if this is not a
+    /// MemDfeSearch entry, if there is no element struct for VERSION, or if
+    /// the body does not consist of elements of that layout (checked via
+    /// the size of each element header).
+    pub fn body_as_mem_dfe_search(
+        &self,
+        version: MemDfeSearchVersion,
+    ) -> Result<MemDfeSearchElements<'a>> {
+        use crate::memory::{
+            MemDfeSearchElementHeader, MemDfeSearchElementHeader12,
+        };
+        /// Checks that BUF consists of elements of type T, each starting
+        /// with a header H whose total_size is the size of H.
+        fn elements<T: Sized + FromBytes, H: Sized>(
+            buf: &[u8],
+        ) -> Result<StructArrayEntryItem<'_, T>> {
+            let header_size = size_of::<H>();
+            if buf.len() % size_of::<T>() != 0 {
+                return Err(Error::EntryTypeMismatch);
+            }
+            for element in buf.chunks_exact(size_of::<T>()) {
+                let total_size = u32::from_le_bytes(
+                    element[..4].try_into().map_err(|_| Error::EntryRange)?,
+                );
+                if total_size as usize != header_size {
+                    return Err(Error::EntryTypeMismatch);
+                }
+            }
+            Ok(StructArrayEntryItem { buf, _item: PhantomData })
+        }
+        if self.id() != EntryId::Memory(MemoryEntryId::MemDfeSearch) {
+            return Err(Error::EntryTypeMismatch);
+        }
+        let EntryItemBody::Struct(buf) = self.body else {
+            return Err(Error::EntryTypeMismatch);
+        };
+        match version {
+            MemDfeSearchVersion::Genoa2 => {
+                let elements = elements::<_, MemDfeSearchElementHeader>(buf)?;
+                Ok(MemDfeSearchElements::Element32(elements))
+            }
+            MemDfeSearchVersion::Turin1 => {
+                let elements = elements::<_, MemDfeSearchElementHeader12>(buf)?;
+                Ok(MemDfeSearchElements::Element36(elements))
+            }
+            _ => Err(Error::EntryTypeMismatch),
+        }
+    }
+
+    /// If this is a struct entry of a known type (see
+    /// for_each_struct_body), returns the bytes of the body after the last
+    /// complete struct (that is, the padding that insert_entry adds, and
+    /// anything else that is trailing).  Otherwise, returns None.
+    /// Note: Sequences and parameters are not checked.
+    pub(crate) fn trailing_bytes(&self) -> Option<&'a [u8]> {
+        use crate::{df, fch, gnb, memory, psp};
+        let EntryItemBody::Struct(buf) = self.body else {
+            return None;
+        };
+        let id = self.id();
+        /// Returns the part of BUF after the last complete ITEM_SIZE item.
+        fn after_items(buf: &[u8], item_size: usize) -> &[u8] {
+            if item_size == 0 {
+                buf
+            } else {
+                &buf[buf.len() - buf.len() % item_size..]
+            }
+        }
+        macro_rules! try_trailing {
+            () => {};
+            (array $name:ident = $t:ty; $($rest:tt)*) => {
+                if <$t>::is_entry_compatible(id, buf) {
+                    return Some(after_items(buf, size_of::<$t>()));
+                }
+                try_trailing!($($rest)*);
+            };
+            (header $name:ident = $t:ty; $($rest:tt)*) => {
+                try_trailing!(header_tail $name = $t; $($rest)*);
+            };
+            (header_tail $name:ident = $t:ty; $($rest:tt)*) => {
+                if <$t>::is_entry_compatible(id, buf)
+                    && buf.len() >= size_of::<$t>()
+                {
+                    return Some(after_items(
+                        &buf[size_of::<$t>()..],
+                        size_of::<<$t as HeaderWithTail>::TailArrayItemType<'_>>(),
+                    ));
+                }
+                try_trailing!($($rest)*);
+            };
+            (sequence $name:ident = $t:ty; $($rest:tt)*) => {
+                try_trailing!($($rest)*);
+            };
+            (parameters $name:ident = $t:ty; $($rest:tt)*) => {
+                try_trailing!($($rest)*);
+            };
+            (mem_dfe_search($version:ident) $name:ident = $t:ty; $($rest:tt)*) => {
+                if self.context.mem_dfe_search_version()
+                    == Some(MemDfeSearchVersion::$version)
+                {
+                    try_trailing!(array $name = $t;);
+                }
+                try_trailing!($($rest)*);
+            };
+        }
+        for_each_struct_body!(try_trailing);
+        None
+    }
+
     pub fn body_as_struct_array<T: EntryCompatible + Sized + FromBytes>(
         &'a self,
     ) -> Option<StructArrayEntryItem<'a, T>> {
@@ -17,6 +17,7 @@ use crate::ondisk::{
 pub use crate::ondisk::{
     BoardInstances, ContextFormat, ContextType, EntryId, PriorityLevels,
 };
+use core::cmp::Ordering;
 use core::convert::TryInto;
 use core::mem::size_of;
 use num_traits::FromPrimitive;
@@ -25,6 +26,9 @@ use pre::pre;
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct GroupItem<'a> {
+    // SerdeGroupItem has no context (the Apcb has), so serializing it would
+    // make Apcb::to_yaml_string output that from_yaml_str rejects.
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub(crate) context: ApcbContext,
     pub(crate) header: &'a GROUP_HEADER,
     #[cfg_attr(feature = "serde", serde(skip))]
@@ -116,6 +120,11 @@ impl<'a> GroupIter<'a> {
                 FileSystemError::InconsistentHeader,
                 "ENTRY_HEADER",
             ))?;
+        // Note: BUF only covers the rest of the group, so this prevents the
+        // entry from extending into the next group.
+        if payload_size > buf.len() {
+            return Err(Error::EntryRange);
+        }
         let body = match take_body_from_collection(
             &mut *buf,
             payload_size,
@@ -149,12 +158,8 @@ impl<'a> GroupIter<'a> {
                     ));
                 }
                 let entry_size = e.header.entry_size.get() as usize;
-                if self.remaining_used_size >= entry_size {
-                } else {
-                    return Err(Error::FileSystem(
-                        FileSystemError::InconsistentHeader,
-                        "ENTRY_HEADER::entry_size",
-                    ));
+                if self.remaining_used_size < entry_size {
+                    return Err(Error::EntryRange);
                 }
                 self.remaining_used_size -= entry_size;
                 Ok(e)
@@ -163,6 +168,28 @@ impl<'a> GroupIter<'a> {
         }
     }
 
+    /// Checks that all the entries have the group_id of the group, without
+    /// validating anything else.  Also consumes iterator.
+    pub(crate) fn check_group_ids(mut self) -> Result<()> {
+        let group_id = self.header.group_id.get();
+        while self.remaining_used_size > 0 {
+            let e = Self::next_item(self.context, &mut self.buf)?;
+            let entry_group_id = e.header.group_id.get();
+            if entry_group_id != group_id {
+                return Err(Error::EntryGroupIdMismatch {
+                    entry: entry_group_id,
+                    group: group_id,
+                });
+            }
+            let entry_size = e.header.entry_size.get() as usize;
+            self.remaining_used_size = self
+                .remaining_used_size
+                .checked_sub(entry_size)
+                .ok_or(Error::EntryRange)?;
+        }
+        Ok(())
+    }
+
     /// Validates the entries (recursively).  Also consumes iterator.
     pub(crate) fn validate(mut self) -> Result<()> {
         while self.remaining_used_size > 0 {
@@ -179,7 +206,7 @@ impl<'a> GroupIter<'a> {
     }
 }
 
-impl GroupItem<'_> {
+impl<'a> GroupItem<'a> {
     /// Note: ASCII
     pub fn signature(&self) -> [u8; 4] {
         self.header.signature
@@ -188,6 +215,10 @@ impl GroupItem<'_> {
     pub fn id(&self) -> GroupId {
         GroupId::from_u16(self.header.group_id.get()).unwrap()
     }
+    /// Note: Expected to be GROUP_HEADER::VERSION
+    pub fn version(&self) -> u16 {
+        self.header.version.get()
+    }
 
     /// This finds the entry with the given ID, INSTANCE_ID and compatible
     /// BOARD_INSTANCE_MASK, if any.  If you have a board_id,
@@ -197,7 +228,7 @@ impl GroupItem<'_> {
         id: EntryId,
         instance_id: u16,
         board_instance_mask: BoardInstances,
-    ) -> Option<EntryItem<'_>> {
+    ) -> Option<EntryItem<'a>> {
         self.entries().find(|entry| {
             entry.id() == id
                 && entry.instance_id() == instance_id
@@ -214,7 +245,7 @@ impl GroupItem<'_> {
         id: EntryId,
         instance_id: u16,
         board_instance_mask: BoardInstances,
-    ) -> Option<EntryItem<'_>> {
+    ) -> Option<EntryItem<'a>> {
         self.entries().find(|entry| {
             entry.id() == id
                 && entry.instance_id() == instance_id
@@ -222,7 +253,25 @@ impl GroupItem<'_> {
         })
     }
 
-    pub fn entries(&self) -> GroupIter<'_> {
+    pub fn entries(&self) -> GroupIter<'a> {
+        GroupIter {
+            context: self.context,
+            header: self.header,
+            buf: self.buf,
+            remaining_used_size: self.used_size,
+        }
+    }
+}
+
+impl<'a> GroupItem<'a> {
+    /// Returns the raw body of the group (that is, its entries, without the
+    /// GROUP_HEADER).  See Apcb::insert_group_raw.
+    pub fn body_as_buf(&self) -> &'a [u8] {
+        &self.buf[..self.used_size]
+    }
+
+    /// Like entries, but the iterator can outlive the GroupItem.
+    pub(crate) fn into_entries(self) -> GroupIter<'a> {
         GroupIter {
             context: self.context,
             header: self.header,
@@ -285,6 +334,11 @@ impl<'a> GroupMutIter<'a> {
                 FileSystemError::InconsistentHeader,
                 "ENTRY_HEADER",
             ))?;
+        // Note: BUF only covers the rest of the group, so this prevents the
+        // entry from extending into the next group.
+        if payload_size > buf.len() {
+            return Err(Error::EntryRange);
+        }
         let body = match take_body_from_collection_mut(
             &mut *buf,
             payload_size,
@@ -304,6 +358,30 @@ impl<'a> GroupMutIter<'a> {
         Ok(EntryMutItem { context, header, body })
     }
 
+    pub(crate) fn next1(&mut self) -> Result<EntryMutItem<'a>> {
+        if self.remaining_used_size == 0 {
+            panic!("Internal error");
+        }
+        match Self::next_item(self.context, &mut self.buf) {
+            Ok(e) => {
+                if e.header.group_id.get() == self.header.group_id.get() {
+                } else {
+                    return Err(Error::FileSystem(
+                        FileSystemError::InconsistentHeader,
+                        "ENTRY_HEADER::group_id",
+                    ));
+                }
+                let entry_size = e.header.entry_size.get() as usize;
+                if self.remaining_used_size < entry_size {
+                    return Err(Error::EntryRange);
+                }
+                self.remaining_used_size -= entry_size;
+                Ok(e)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
     /// Find the place BEFORE which the entry (GROUP_ID, ENTRY_ID, INSTANCE_ID,
     /// BOARD_INSTANCE_MASK) is supposed to go.
     pub(crate) fn move_insertion_point_before(
@@ -331,7 +409,7 @@ impl<'a> GroupMutIter<'a> {
                         instance_id,
                         u16::from(board_instance_mask),
                     ) {
-                        self.next().unwrap();
+                        self.next1()?;
                     } else {
                         break;
                     }
@@ -364,7 +442,11 @@ impl<'a> GroupMutIter<'a> {
                 };
             let mut buf = &mut self.buf[..remaining_used_size];
             if buf.is_empty() {
-                return Err(Error::EntryNotFound);
+                return Err(Error::EntryNotFound {
+                    entry_id,
+                    instance_id,
+                    board_instance_mask,
+                });
             }
             match Self::next_item(self.context, &mut buf) {
                 Ok(e) => {
@@ -372,7 +454,7 @@ impl<'a> GroupMutIter<'a> {
                     if (e.id(), e.instance_id(), e.board_instance_mask())
                         != (entry_id, instance_id, board_instance_mask)
                     {
-                        self.next().unwrap();
+                        self.next1()?;
                         offset = offset
                             .checked_add(entry_size.into())
                             .ok_or(Error::ArithmeticOverflow)?;
@@ -410,14 +492,14 @@ impl<'a> GroupMutIter<'a> {
 
         // Make sure that entry_allocation is large enough for the header and
         // data
-        let padding_size = (entry_allocation as usize)
+        let body_size = (entry_allocation as usize)
             .checked_sub(size_of::<ENTRY_HEADER>())
             .ok_or(Error::FileSystem(
                 FileSystemError::PayloadTooBig,
                 "ENTRY_HEADER:entry_size",
             ))?;
         let padding_size =
-            padding_size.checked_sub(payload_size).ok_or(Error::FileSystem(
+            body_size.checked_sub(payload_size).ok_or(Error::FileSystem(
                 FileSystemError::PayloadTooBig,
                 "ENTRY_HEADER:entry_size",
             ))?;
@@ -453,7 +535,7 @@ impl<'a> GroupMutIter<'a> {
         *header = ENTRY_HEADER::default();
         header.group_id.set(group_id.to_u16().unwrap());
         header.entry_id.set(entry_id);
-        header.entry_size.set(entry_allocation);
+        header.finalize(body_size)?;
         header.instance_id.set(instance_id);
         header.context_type = context_type as u8;
         header.context_format = ContextFormat::Raw as u8;
@@ -563,9 +645,90 @@ impl<'a> GroupMutItem<'a> {
             0,
         )?;
         let buf = &mut self.buf[offset..];
-        buf.copy_within(entry_size..self.used_size, offset);
+        buf.copy_within(entry_size..(self.used_size - offset), 0);
         Ok(entry_size as u32)
     }
+    /// Changes the board_instance_mask of the given entry from OLD to NEW,
+    /// moving the entry so that the entries stay in the default order.
+    pub(crate) fn set_entry_board_instance_mask(
+        &mut self,
+        entry_id: EntryId,
+        instance_id: u16,
+        old_board_instance_mask: BoardInstances,
+        new_board_instance_mask: BoardInstances,
+    ) -> Result<()> {
+        if old_board_instance_mask == new_board_instance_mask {
+            return self
+                .entry_exact_mut(entry_id, instance_id, old_board_instance_mask)
+                .map(|_| ())
+                .ok_or(Error::EntryNotFound {
+                    entry_id,
+                    instance_id,
+                    board_instance_mask: old_board_instance_mask,
+                });
+        }
+        if self
+            .entry_exact_mut(entry_id, instance_id, new_board_instance_mask)
+            .is_some()
+        {
+            return Err(Error::EntryUniqueKeyViolation {
+                entry_id,
+                instance_id,
+                board_instance_mask: new_board_instance_mask,
+            });
+        }
+        let (offset, entry_size) = self.entries_mut().move_point_to(
+            entry_id,
+            instance_id,
+            old_board_instance_mask,
+            0,
+        )?;
+        let key = (
+            entry_id.group_id().to_u16().unwrap(),
+            entry_id.type_id(),
+            instance_id,
+            u16::from(new_board_instance_mask),
+        );
+        // Find the place BEFORE which the entry should be.
+        let mut target = self.used_size;
+        let mut entry_offset = 0;
+        for entry in self.entries() {
+            let size = entry.header.entry_size.get() as usize;
+            if entry_offset != offset
+                && (
+                    entry.header.group_id.get(),
+                    entry.header.entry_id.get(),
+                    entry.instance_id(),
+                    u16::from(entry.board_instance_mask()),
+                ) >= key
+            {
+                target = entry_offset;
+                break;
+            }
+            entry_offset += size;
+        }
+        let entry = self
+            .entry_exact_mut(entry_id, instance_id, old_board_instance_mask)
+            .ok_or(Error::EntryNotFound {
+                entry_id,
+                instance_id,
+                board_instance_mask: old_board_instance_mask,
+            })?;
+        entry
+            .header
+            .board_instance_mask
+            .set(u16::from(new_board_instance_mask));
+        match target.cmp(&offset) {
+            Ordering::Greater => {
+                self.buf[offset..target].rotate_left(entry_size);
+            }
+            Ordering::Less => {
+                self.buf[target..offset + entry_size].rotate_right(entry_size);
+            }
+            Ordering::Equal => {}
+        }
+        Ok(())
+    }
     /// Resizes the given entry by SIZE_DIFF.
     #[pre("If `size_diff > 0`, caller needs to have expanded the group by `size_diff` already.  If `size_diff < 0`, caller needs to call `resize_entry_by` BEFORE resizing the group.")]
     pub(crate) fn resize_entry_by(
@@ -585,7 +748,7 @@ impl<'a> GroupMutItem<'a> {
         )?;
         let entry_size: u16 =
             entry_size.try_into().map_err(|_| Error::ArithmeticOverflow)?;
-        let entry = entries.next().ok_or(Error::EntryNotFound)?;
+        let entry = entries.next1()?;
 
         if size_diff > 0 {
             let size_diff: usize =
@@ -615,7 +778,10 @@ impl<'a> GroupMutItem<'a> {
                 .ok_or(Error::OutOfSpace)?
         };
 
-        entry.header.entry_size.set(new_entry_size);
+        let new_body_size = usize::from(new_entry_size)
+            .checked_sub(size_of::<ENTRY_HEADER>())
+            .ok_or(Error::ArithmeticOverflow)?;
+        entry.header.finalize(new_body_size)?;
         let buf = &mut self.buf[offset..];
         buf.copy_within(
             old_entry_size as usize..(old_used_size - offset),
@@ -683,7 +849,11 @@ impl<'a> GroupMutItem<'a> {
         // Ok.
         let mut entry = self
             .entry_exact_mut(entry_id, instance_id, board_instance_mask)
-            .ok_or(Error::EntryNotFound)?;
+            .ok_or(Error::EntryNotFound {
+                entry_id,
+                instance_id,
+                board_instance_mask,
+            })?;
         entry.delete_token(token_id)?;
         let mut token_size_diff: i64 =
             token_size.try_into().map_err(|_| Error::ArithmeticOverflow)?;
@@ -707,6 +877,51 @@ impl<'a> GroupMutItem<'a> {
         }
     }
 
+    /// Reorders the entries of this group according to CMP (stable).
+    /// Note: Insertions assume that entries are in the default order, so
+    /// sort only after all insertions are done.
+    #[cfg(feature = "std")]
+    pub fn sort_entries<F>(&mut self, mut cmp: F) -> Result<()>
+    where
+        F: FnMut(&EntryItem<'_>, &EntryItem<'_>) -> Ordering,
+    {
+        let original = self.buf[..self.used_size].to_vec();
+        let mut entries = GroupIter {
+            context: self.context,
+            header: self.header,
+            buf: &original,
+            remaining_used_size: self.used_size,
+        };
+        let mut items = Vec::new();
+        let mut offset = 0;
+        while entries.remaining_used_size > 0 {
+            let item = entries.next1()?;
+            let end = original.len() - entries.buf.len();
+            items.push((offset..end, item));
+            offset = end;
+        }
+        items.sort_by(|(_, a), (_, b)| cmp(a, b));
+        let mut offset = 0;
+        for (range, _) in items {
+            let end = offset + range.len();
+            self.buf[offset..end].copy_from_slice(&original[range]);
+            offset = end;
+        }
+        Ok(())
+    }
+
+    /// Reorders the entries of this group by the key KEY returns for each
+    /// (stable).  The entries themselves are not changed.
+    /// Note: See sort_entries.
+    #[cfg(feature = "std")]
+    pub fn sort_entries_by_key<K, F>(&mut self, mut key: F) -> Result<()>
+    where
+        K: Ord,
+        F: FnMut(&EntryItem<'_>) -> K,
+    {
+        self.sort_entries(|a, b| key(a).cmp(&key(b)))
+    }
+
     pub fn entries_mut(&mut self) -> GroupMutIter<'_> {
         GroupMutIter {
             context: self.context,
@@ -715,6 +930,16 @@ impl<'a> GroupMutItem<'a> {
             remaining_used_size: self.used_size,
         }
     }
+
+    /// Like entries_mut, but the iterator can outlive the GroupMutItem.
+    pub(crate) fn into_entries_mut(self) -> GroupMutIter<'a> {
+        GroupMutIter {
+            context: self.context,
+            header: self.header,
+            buf: self.buf,
+            remaining_used_size: self.used_size,
+        }
+    }
 }
 
 impl<'a> Iterator for GroupMutIter<'a> {
@@ -724,14 +949,8 @@ impl<'a> Iterator for GroupMutIter<'a> {
         if self.remaining_used_size == 0 {
             return None;
         }
-        match Self::next_item(self.context, &mut self.buf) {
-            Ok(e) => {
-                assert!(e.header.group_id.get() == self.header.group_id.get());
-                let entry_size = e.header.entry_size.get() as usize;
-                assert!(self.remaining_used_size >= entry_size);
-                self.remaining_used_size -= entry_size;
-                Some(e)
-            }
+        match self.next1() {
+            Ok(e) => Some(e),
             Err(_) => None,
         }
     }
@@ -282,6 +282,8 @@ mod group;
 mod naples;
 mod ondisk;
 #[cfg(feature = "serde")]
+mod serde_proxy;
+#[cfg(feature = "serde")]
 mod serializers;
 mod struct_accessors;
 mod struct_variants_enum;
@@ -289,13 +291,30 @@ mod tests;
 mod token_accessors;
 mod tokens_entry;
 mod types;
+pub use apcb::crate_format_version;
+pub use apcb::supported_groups;
+pub use apcb::supports_serde;
 pub use apcb::Apcb;
 pub use apcb::ApcbIoOptions;
+pub use apcb::ApcbStats;
+pub use apcb::GroupCursor;
+pub use apcb::HeaderChecksumFn;
+pub use apcb::IntegritySignFn;
 pub use entry::EntryItemBody;
+pub use entry::MemDfeSearchElements;
 pub use ondisk::*;
+#[cfg(feature = "serde")]
+pub use serde_proxy::deserialize_with_unknown_passthrough;
 pub use types::ApcbContext;
+pub use types::ApcbDiffItem;
+pub use types::ApcbEdit;
+pub use types::ApcbWarning;
 pub use types::Error;
 pub use types::FileSystemError;
 pub use types::MemDfeSearchVersion;
+pub use types::OrderedPriorityLevel;
 pub use types::PriorityLevel;
 pub use types::Result;
+pub use types::SiliconGeneration;
+pub use types::SiliconIncompatibility;
+pub use types::UnknownBitsReport;
@@ -9,7 +9,15 @@ use crate::types::Result;
 use modular_bitfield::prelude::*;
 
 #[derive(
-    Debug, PartialEq, num_derive::FromPrimitive, Clone, Copy, BitfieldSpecifier,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    num_derive::FromPrimitive,
+    Clone,
+    Copy,
+    BitfieldSpecifier,
 )]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
@@ -39,6 +47,12 @@ impl Default for ParameterTimePoint {
     }
 }
 
+impl core::fmt::Display for ParameterTimePoint {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self, f)
+    }
+}
+
 #[derive(
     Debug, PartialEq, num_derive::FromPrimitive, Clone, Copy, BitfieldSpecifier,
 )]
@@ -422,6 +436,12 @@ impl Default for ParameterTokenConfig {
     }
 }
 
+impl core::fmt::Display for ParameterTokenConfig {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self, f)
+    }
+}
+
 impl Getter<Result<ParameterTokenConfig>> for ParameterTokenConfig {
     fn get1(self) -> Result<Self> {
         Ok(self)
@@ -13,6 +13,7 @@ pub use crate::naples::{ParameterTimePoint, ParameterTokenConfig};
 use crate::struct_accessors::{make_accessors, Getter, Setter};
 use crate::token_accessors::{make_token_accessors, Tokens, TokensMut};
 use crate::types::Error;
+use crate::types::FileSystemError;
 use crate::types::PriorityLevel;
 use crate::types::Result;
 use core::clone::Clone;
@@ -31,7 +32,7 @@ use paste::paste;
 use zerocopy::byteorder::LittleEndian;
 use zerocopy::{AsBytes, FromBytes, LayoutVerified, Unaligned, U16, U32, U64};
 
-#[cfg(feature = "serde")]
+#[cfg(feature = "std")]
 use byteorder::WriteBytesExt;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -289,6 +290,23 @@ macro_rules! make_array_accessors {
     };
 }
 
+/// Implements TryFrom<&[u8]> for the given (FromBytes) element structs, for
+/// decoding a single element outside of an entry.  The slice has to have
+/// exactly the size of the struct.
+macro_rules! impl_try_from_bytes {
+    ($($struct_:ty),* $(,)?) => {
+        $(
+            impl<'a> core::convert::TryFrom<&'a [u8]> for $struct_ {
+                type Error = crate::types::Error;
+                fn try_from(buf: &'a [u8]) -> crate::types::Result<Self> {
+                    <Self as zerocopy::FromBytes>::read_from(buf)
+                        .ok_or(crate::types::Error::EntryTypeMismatch)
+                }
+            }
+        )*
+    };
+}
+
 make_array_accessors!(u8, u8);
 #[cfg(feature = "serde-hex")]
 make_array_accessors!(SerdeHex8, u8);
@@ -381,8 +399,8 @@ make_accessors! {
         // ENTRY_HEADER's fields.
         _reserved_7 || #[serde(default)] [SerdeHex32; 2] : [LU32; 2],
         pub data_offset || SerdeHex16 : LU16, // 0x58
-        // This is unused--but make it optional in case we do need to
-        // calculate it in the future.
+        // This is unused by AMD Rome--but see
+        // ApcbIoOptions::header_checksum_fn.
         pub header_checksum || #[serde(default)] SerdeHex8 : u8,
         _reserved_8 || #[serde(default)] SerdeHex8 : u8,
         _reserved_9 || #[serde(default)] [SerdeHex32; 3] : [LU32; 3],
@@ -431,6 +449,46 @@ pub enum GroupId {
     Unknown(u16),
 }
 
+impl GroupId {
+    /// Returns the signature the group with this id usually has, if known.
+    pub fn canonical_signature(&self) -> Option<[u8; 4]> {
+        match self {
+            GroupId::Psp => Some(*b"PSPG"),
+            GroupId::Ccx => Some(*b"CCXG"),
+            GroupId::Df => Some(*b"DFG "),
+            GroupId::Memory => Some(*b"MEMG"),
+            GroupId::Gnb => Some(*b"GNBG"),
+            GroupId::Fch => Some(*b"FCHG"),
+            GroupId::Cbs => Some(*b"CBSG"),
+            GroupId::Oem => Some(*b"OEMG"),
+            GroupId::Token => Some(*b"TOKN"),
+            GroupId::Unknown(_) => None,
+        }
+    }
+    /// Returns the symbolic name (without the numeric id).
+    fn name(&self) -> &'static str {
+        match self {
+            GroupId::Psp => "Psp",
+            GroupId::Ccx => "Ccx",
+            GroupId::Df => "Df",
+            GroupId::Memory => "Memory",
+            GroupId::Gnb => "Gnb",
+            GroupId::Fch => "Fch",
+            GroupId::Cbs => "Cbs",
+            GroupId::Oem => "Oem",
+            GroupId::Token => "Token",
+            GroupId::Unknown(_) => "Unknown",
+        }
+    }
+}
+
+/// For example "Memory (0x1704)".
+impl core::fmt::Display for GroupId {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} (0x{:04X})", self.name(), self.to_u16().unwrap())
+    }
+}
+
 impl ToPrimitive for GroupId {
     fn to_i64(&self) -> Option<i64> {
         Some(match self {
@@ -481,6 +539,61 @@ impl FromPrimitive for GroupId {
     }
 }
 
+/// Schema of the group_id fields of GROUP_HEADER and ENTRY_HEADER in the
+/// serde representation (a plain number, or a hex string with the
+/// "serde-hex" feature), listing the known group ids.
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for GroupId {
+    fn schema_name() -> String {
+        String::from("GroupId")
+    }
+    fn json_schema(
+        _gen: &mut schemars::gen::SchemaGenerator,
+    ) -> schemars::schema::Schema {
+        #[cfg(not(feature = "serde-hex"))]
+        use schemars::schema::NumberValidation;
+        use schemars::schema::{InstanceType, Metadata, SchemaObject};
+        let known_group_ids = [
+            GroupId::Psp,
+            GroupId::Ccx,
+            GroupId::Df,
+            GroupId::Memory,
+            GroupId::Gnb,
+            GroupId::Fch,
+            GroupId::Cbs,
+            GroupId::Oem,
+            GroupId::Token,
+        ];
+        let description = known_group_ids
+            .iter()
+            .map(|group_id| group_id.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let metadata = Some(Box::new(Metadata {
+            description: Some(format!("Known group ids: {description}")),
+            ..Default::default()
+        }));
+        #[cfg(feature = "serde-hex")]
+        let schema = SchemaObject {
+            instance_type: Some(InstanceType::String.into()),
+            metadata,
+            ..Default::default()
+        };
+        #[cfg(not(feature = "serde-hex"))]
+        let schema = SchemaObject {
+            instance_type: Some(InstanceType::Integer.into()),
+            metadata,
+            number: Some(Box::new(NumberValidation {
+                minimum: Some(0.0),
+                maximum: Some(f64::from(u16::MAX)),
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+        schema.into()
+    }
+}
+
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum PspEntryId {
     BoardIdGettingMethod,
@@ -694,6 +807,54 @@ pub enum MemoryEntryId {
     Unknown(u16),
 }
 
+impl MemoryEntryId {
+    /// Returns the DDR generation (4 or 5) this entry is specific to, if
+    /// any.
+    pub fn ddr_generation(&self) -> Option<u8> {
+        match self {
+            Self::PsUdimmDdr4OdtPat
+            | Self::PsUdimmDdr4CadBus
+            | Self::PsUdimmDdr4DataBus
+            | Self::PsUdimmDdr4MaxFreq
+            | Self::PsUdimmDdr4StretchFreq
+            | Self::PsRdimmDdr4OdtPat
+            | Self::PsRdimmDdr4CadBus
+            | Self::PsRdimmDdr4DataBus
+            | Self::PsRdimmDdr4MaxFreq
+            | Self::PsRdimmDdr4StretchFreq
+            | Self::Ps3dsRdimmDdr4MaxFreq
+            | Self::Ps3dsRdimmDdr4StretchFreq
+            | Self::Ps3dsRdimmDdr4DataBus
+            | Self::PsLrdimmDdr4OdtPat
+            | Self::PsLrdimmDdr4CadBus
+            | Self::PsLrdimmDdr4DataBus
+            | Self::PsLrdimmDdr4MaxFreq
+            | Self::PsLrdimmDdr4StretchFreq
+            | Self::PsSodimmDdr4OdtPat
+            | Self::PsSodimmDdr4CadBus
+            | Self::PsSodimmDdr4DataBus
+            | Self::PsSodimmDdr4MaxFreq
+            | Self::PsSodimmDdr4StretchFreq
+            | Self::PsDramdownDdr4OdtPat
+            | Self::PsDramdownDdr4CadBus
+            | Self::PsDramdownDdr4DataBus
+            | Self::PsDramdownDdr4MaxFreq
+            | Self::PsDramdownDdr4StretchFreq => Some(4),
+            Self::Ddr5CaPinMap
+            | Self::PsRdimmDdr5Bus
+            | Self::PsRdimmDdr5MaxFreq
+            | Self::PsRdimmDdr5StretchFreq
+            | Self::PsRdimmDdr5MaxFreqC1
+            | Self::Ps3dsRdimmDdr5MaxFreq
+            | Self::Ps3dsRdimmDdr5StretchFreq
+            | Self::PsLrdimmDdr5MaxFreq
+            | Self::PsLrdimmDdr5StretchFreq
+            | Self::Ddr5RawCardConfig => Some(5),
+            _ => None,
+        }
+    }
+}
+
 impl ToPrimitive for MemoryEntryId {
     fn to_i64(&self) -> Option<i64> {
         Some(match self {
@@ -1065,7 +1226,7 @@ impl FromPrimitive for RawEntryId {
     }
 }
 
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
@@ -1086,6 +1247,17 @@ impl serde::de::Expected for TokenEntryId {
     }
 }
 
+/// For example "Byte (0x01)".
+impl core::fmt::Display for TokenEntryId {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Unknown(_) => write!(f, "Unknown"),
+            x => write!(f, "{x:?}"),
+        }?;
+        write!(f, " (0x{:02X})", self.to_u16().unwrap())
+    }
+}
+
 impl ToPrimitive for TokenEntryId {
     fn to_i64(&self) -> Option<i64> {
         Some(match self {
@@ -1125,6 +1297,20 @@ impl FromPrimitive for TokenEntryId {
     }
 }
 
+impl TokenEntryId {
+    /// Returns the mask of the bits of a token value that tokens in this
+    /// token entry can hold.
+    pub fn value_mask(&self) -> u32 {
+        match self {
+            Self::Bool => 0x1,
+            Self::Byte => 0xFF,
+            Self::Word => 0xFFFF,
+            Self::Dword => 0xFFFF_FFFF,
+            Self::Unknown(_) => 0xFFFF_FFFF,
+        }
+    }
+}
+
 // Note: Keep front part synced with GroupId for easier understanding.
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum EntryId {
@@ -1189,6 +1375,51 @@ impl EntryId {
             }
         }
     }
+    /// Returns a single stable integer identifying the entry, suitable as a
+    /// key (for example in external databases).
+    /// It's (group_id << 16) | type_id.
+    pub fn as_u32(&self) -> u32 {
+        (u32::from(self.group_id().to_u16().unwrap()) << 16)
+            | u32::from(self.type_id())
+    }
+    /// Inverse of as_u32.
+    pub fn from_u32(value: u32) -> Self {
+        Self::decode((value >> 16) as u16, value as u16)
+    }
+}
+
+/// For example "Memory/PsRdimmDdr5MaxFreq (0x1704/0x8E)".
+impl core::fmt::Display for EntryId {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let group_id = self.group_id();
+        write!(f, "{}/", group_id.name())?;
+        match self {
+            Self::Psp(PspEntryId::Unknown(_))
+            | Self::Ccx(CcxEntryId::Unknown(_))
+            | Self::Df(DfEntryId::Unknown(_))
+            | Self::Memory(MemoryEntryId::Unknown(_))
+            | Self::Gnb(GnbEntryId::Unknown(_))
+            | Self::Fch(FchEntryId::Unknown(_))
+            | Self::Cbs(CbsEntryId::Unknown(_))
+            | Self::Oem(OemEntryId::Unknown(_))
+            | Self::Token(TokenEntryId::Unknown(_))
+            | Self::Unknown(_, _) => write!(f, "Unknown"),
+            Self::Psp(x) => write!(f, "{x:?}"),
+            Self::Ccx(x) => write!(f, "{x:?}"),
+            Self::Df(x) => write!(f, "{x:?}"),
+            Self::Memory(x) => write!(f, "{x:?}"),
+            Self::Gnb(x) => write!(f, "{x:?}"),
+            Self::Fch(x) => write!(f, "{x:?}"),
+            Self::Cbs(x) => write!(f, "{x:?}"),
+            Self::Token(x) => write!(f, "{x:?}"),
+        }?;
+        write!(
+            f,
+            " (0x{:04X}/0x{:02X})",
+            group_id.to_u16().unwrap(),
+            self.type_id()
+        )
+    }
 }
 
 make_accessors! {
@@ -1198,7 +1429,7 @@ make_accessors! {
     #[repr(C, packed)]
     pub struct GROUP_HEADER {
         pub(crate) signature || FourCC : [u8; 4],
-        pub(crate) group_id || SerdeHex16 : LU16,
+        pub(crate) group_id || #[cfg_attr(feature = "schemars", schemars(with = "GroupId"))] SerdeHex16 : LU16,
         pub(crate) header_size || SerdeHex16 : LU16, // == sizeof(GROUP_HEADER)
         pub(crate) version || SerdeHex16 : LU16,     // == 0 << 4 | 1
         _reserved_ || #[serde(default)] SerdeHex16 : LU16,
@@ -1228,13 +1459,18 @@ pub enum ContextType {
     Tokens = 2, // then, entry_id means something else
 }
 
+impl GROUP_HEADER {
+    /// The only version of the group header that we know of.
+    pub const VERSION: u16 = 0x01;
+}
+
 impl Default for GROUP_HEADER {
     fn default() -> Self {
         Self {
             signature: *b"    ",   // probably invalid
             group_id: 0u16.into(), // probably invalid
             header_size: (size_of::<Self>() as u16).into(),
-            version: 0x01u16.into(),
+            version: Self::VERSION.into(),
             _reserved_: 0u16.into(),
             group_size: (size_of::<Self>() as u32).into(), // probably invalid
         }
@@ -1412,6 +1648,25 @@ impl PriorityLevels {
         }
         result
     }
+    /// Returns the strongest level that is set, if any.  The order is
+    /// HardForce > High > Medium > EventLogging > Low > Normal.
+    pub fn highest(&self) -> Option<PriorityLevel> {
+        if self.hard_force() {
+            Some(PriorityLevel::HardForce)
+        } else if self.high() {
+            Some(PriorityLevel::High)
+        } else if self.medium() {
+            Some(PriorityLevel::Medium)
+        } else if self.event_logging() {
+            Some(PriorityLevel::EventLogging)
+        } else if self.low() {
+            Some(PriorityLevel::Low)
+        } else if self.normal() {
+            Some(PriorityLevel::Normal)
+        } else {
+            None
+        }
+    }
 }
 
 make_bitfield_serde! {
@@ -1485,6 +1740,20 @@ impl BoardInstances {
         }
         Ok(result)
     }
+    /// Returns the mask with all the given INSTANCES set.  Like
+    /// from_instance, fails for instances > 15.
+    pub fn from_instances(instances: &[BoardInstance]) -> Result<Self> {
+        let mut mask = 0u16;
+        for &instance in instances {
+            mask |= u16::from(Self::from_instance(instance)?);
+        }
+        Ok(Self::from(mask))
+    }
+    /// Iterates over the instances that are set, in ascending order.
+    pub fn instances(&self) -> impl Iterator<Item = BoardInstance> {
+        let mask = u16::from(*self);
+        (0..16).filter(move |instance| mask & (1 << instance) != 0)
+    }
 }
 
 impl_bitfield_primitive_conversion!(BoardInstances, 0xffff, u16);
@@ -1672,13 +1941,15 @@ pub mod gnb {
             matches!(entry_id, EntryId::Gnb(GnbEntryId::EarlyPcieConfig))
         }
     }
+
+    impl_try_from_bytes!(EarlyPcieConfigElement);
 }
 
 make_accessors! {
     #[derive(FromBytes, AsBytes, Unaligned, Clone, Debug)]
     #[repr(C, packed)]
     pub(crate) struct ENTRY_HEADER {
-        pub(crate) group_id || SerdeHex16 : LU16, // should be equal to the group's group_id
+        pub(crate) group_id || #[cfg_attr(feature = "schemars", schemars(with = "GroupId"))] SerdeHex16 : LU16, // should be equal to the group's group_id
         pub(crate) entry_id || SerdeHex16 : LU16, // meaning depends on context_type
         // The value of the field is automatically calculated on deserialization.
         pub(crate) entry_size || #[serde(default)] SerdeHex16 : LU16, // including header
@@ -1711,6 +1982,22 @@ impl Default for ENTRY_HEADER {
     }
 }
 
+impl ENTRY_HEADER {
+    /// Sets entry_size such that it covers the header and BODY_LEN Byte of
+    /// body (including any padding) after it.
+    pub fn finalize(&mut self, body_len: usize) -> Result<()> {
+        let entry_size = size_of::<Self>()
+            .checked_add(body_len)
+            .and_then(|entry_size| u16::try_from(entry_size).ok())
+            .ok_or(Error::FileSystem(
+                FileSystemError::PayloadTooBig,
+                "ENTRY_HEADER::entry_size",
+            ))?;
+        self.entry_size.set(entry_size);
+        Ok(())
+    }
+}
+
 pub const ENTRY_ALIGNMENT: usize = 4;
 
 #[derive(FromBytes, AsBytes, Clone, Unaligned)]
@@ -1768,6 +2055,43 @@ pub trait EntryCompatible {
     }
 }
 
+/// Structs with bitfield-typed fields, the raw values of which could have
+/// reserved bits set.  See Apcb::report_unknown_bits.
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+pub(crate) trait ReservedBits {
+    /// Calls F with the name ("STRUCT_NAME.FIELD_NAME") and raw value of
+    /// each bitfield-typed field that has reserved bits set.
+    fn visit_reserved_bits(&self, f: &mut dyn FnMut(&'static str, u64));
+}
+
+macro_rules! impl_reserved_bits {
+    ($struct_:ident, [$($field:ident),* $(,)?]) => {
+        impl ReservedBits for $struct_ {
+            fn visit_reserved_bits(
+                &self,
+                f: &mut dyn FnMut(&'static str, u64),
+            ) {
+                $(
+                    // The getter only fails if the raw value has bits set
+                    // that its type does not model.
+                    if self.$field().is_err() {
+                        f(
+                            concat!(
+                                stringify!($struct_),
+                                ".",
+                                stringify!($field)
+                            ),
+                            u64::from(self.$field),
+                        );
+                    }
+                )*
+            }
+        }
+    };
+}
+
+impl_reserved_bits!(ENTRY_HEADER, [priority_mask]);
+
 // Starting here come the actual Entry formats (struct )
 
 make_bitfield_serde! {
@@ -1806,42 +2130,66 @@ impl ParameterAttributes {
 }
 
 /// For Naples.
+#[derive(Clone)]
 pub struct ParametersIter<'a> {
     keys: &'a [u8],
     values: &'a [u8],
 }
 
 impl<'a> ParametersIter<'a> {
+    /// OFFSET is the offset of BUF within the parameters (for errors).
     pub(crate) fn next_attributes(
         buf: &mut &[u8],
+        offset: usize,
     ) -> Result<ParameterAttributes> {
         match take_header_from_collection::<u32>(buf) {
             Some(attributes) => {
                 let attributes = ParameterAttributes::from_u32(*attributes)
-                    .ok_or(Error::ParameterRange)?;
+                    .ok_or(Error::ParameterRange { offset })?;
+                // Unknown time points or tokens would make the getters panic.
+                if attributes.time_point_or_err().is_err()
+                    || attributes.token_or_err().is_err()
+                {
+                    return Err(Error::ParameterRange { offset });
+                }
                 Ok(attributes)
             }
-            None => Err(Error::ParameterRange),
+            None => Err(Error::ParameterRange { offset }),
         }
     }
+    /// Fails with ParameterRange if there's no Limit terminator (with
+    /// the offset at which the parameters ran out).
     pub fn new(buf: &'a [u8]) -> Result<Self> {
         let beginning = buf;
         let mut buf = buf;
         loop {
-            match Self::next_attributes(&mut buf) {
-                Err(e) => return Err(e),
-                Ok(attributes) => {
-                    if attributes.token() == ParameterTokenConfig::Limit {
-                        return Ok(Self {
-                            keys: beginning, /* TODO: split before buf would
-                                              * be enough. */
-                            values: buf,
-                        });
-                    }
-                }
+            let offset = beginning.len() - buf.len();
+            let attributes = Self::next_attributes(&mut buf, offset)?;
+            if attributes.token() == ParameterTokenConfig::Limit {
+                return Ok(Self {
+                    keys: beginning, /* TODO: split before buf would
+                                      * be enough. */
+                    values: buf,
+                });
             }
         }
     }
+    /// Groups the parameters by the time point at which the firmware applies
+    /// them.  Within a time point, the stored order is kept.
+    #[cfg(feature = "std")]
+    pub fn parameters_by_time_point(
+        &self,
+    ) -> Result<std::collections::BTreeMap<ParameterTimePoint, Vec<Parameter>>>
+    {
+        let mut result = std::collections::BTreeMap::new();
+        for parameter in self.clone() {
+            result
+                .entry(parameter.time_point()?)
+                .or_insert_with(Vec::new)
+                .push(parameter);
+        }
+        Ok(result)
+    }
     fn read_u8(raw_value: &[u8]) -> Option<u8> {
         <[u8; 1]>::try_from(raw_value).ok().map(u8::from_le_bytes)
     }
@@ -1859,7 +2207,7 @@ impl<'a> ParametersIter<'a> {
 impl Iterator for ParametersIter<'_> {
     type Item = Parameter;
     fn next(&mut self) -> Option<<Self as Iterator>::Item> {
-        let attributes = Self::next_attributes(&mut self.keys).ok()?;
+        let attributes = Self::next_attributes(&mut self.keys, 0).ok()?;
         if attributes.token() == ParameterTokenConfig::Limit {
             return None;
         }
@@ -1982,11 +2330,24 @@ impl Default for Parameter {
     }
 }
 
+impl core::fmt::Display for Parameter {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "time_point={} token={} size={} value={:#x}",
+            self.time_point,
+            self.token,
+            self.value_size.get(),
+            self.value.get()
+        )
+    }
+}
+
 impl Parameters {
     /// Create a new Parameters Tail with the items from SOURCE.
     /// Note that the last entry in SOURCE must be
     /// Parameter::new(&ParameterAttributes::terminator(), 0xff).
-    #[cfg(feature = "serde")]
+    #[cfg(feature = "std")]
     pub(crate) fn new_tail_from_vec(source: Vec<Parameter>) -> Result<Vec<u8>> {
         //let iter = source.into_iter();
         //let total_size: usize = source.map(|x|
@@ -1994,25 +2355,27 @@ impl Parameters {
         let mut result = Vec::<u8>::new(); // with_capacity(total_size);
         for parameter in &source {
             let raw_attributes = u32::from(parameter.attributes()?);
+            let offset = result.len();
             result
                 .write_u32::<byteorder::LittleEndian>(raw_attributes)
-                .map_err(|_| Error::ParameterRange)?;
+                .map_err(|_| Error::ParameterRange { offset })?;
         }
-        for parameter in &source {
+        for (i, parameter) in source.iter().enumerate() {
             let value = parameter.value()?;
+            let offset = i * size_of::<u32>();
             match parameter.value_size()? {
                 1 => result
                     .write_u8(value as u8)
-                    .map_err(|_| Error::ParameterRange)?,
+                    .map_err(|_| Error::ParameterRange { offset })?,
                 2 => result
                     .write_u16::<byteorder::LittleEndian>(value as u16)
-                    .map_err(|_| Error::ParameterRange)?,
+                    .map_err(|_| Error::ParameterRange { offset })?,
                 4 => result
                     .write_u32::<byteorder::LittleEndian>(value as u32)
-                    .map_err(|_| Error::ParameterRange)?,
+                    .map_err(|_| Error::ParameterRange { offset })?,
                 8 => result
                     .write_u64::<byteorder::LittleEndian>(value)
-                    .map_err(|_| Error::ParameterRange)?,
+                    .map_err(|_| Error::ParameterRange { offset })?,
                 _ => Err(Error::EntryTypeMismatch)?,
             }
         }
@@ -2025,21 +2388,111 @@ pub mod df {
     use crate::struct_accessors::{make_accessors, Getter, Setter};
     use crate::types::Result;
 
-    #[derive(Debug, PartialEq, FromPrimitive, ToPrimitive, Copy, Clone)]
+    #[derive(Debug, Copy, Clone)]
     #[non_exhaustive]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
     #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
     pub enum SlinkRegionInterleavingSize {
         #[cfg_attr(feature = "serde", serde(rename = "256 B"))]
-        _256B = 0,
+        _256B,
         #[cfg_attr(feature = "serde", serde(rename = "512 B"))]
-        _512B = 1,
+        _512B,
         #[cfg_attr(feature = "serde", serde(rename = "1024 B"))]
-        _1024B = 2,
+        _1024B,
         #[cfg_attr(feature = "serde", serde(rename = "2048 B"))]
-        _2048B = 3,
-        Auto = 7,
+        _2048B,
+        Auto,
+        /// Values 4, 5 and 6 (or anything else) are not specified.
+        /// They are preserved as-is, and (de)serialized as plain numbers.
+        /// Reserved never holds the value of a named variant:
+        /// FromPrimitive maps those to the named variant, deserialization
+        /// rejects them, and comparisons are done by value.
+        #[cfg_attr(feature = "serde", serde(untagged))]
+        Reserved(
+            #[cfg_attr(
+                feature = "serde",
+                serde(
+                    deserialize_with = "SlinkRegionInterleavingSize::deserialize_reserved"
+                )
+            )]
+            u8,
+        ),
+    }
+
+    impl PartialEq for SlinkRegionInterleavingSize {
+        fn eq(&self, other: &Self) -> bool {
+            self.to_i64() == other.to_i64()
+        }
+    }
+
+    impl Eq for SlinkRegionInterleavingSize {}
+
+    impl ToPrimitive for SlinkRegionInterleavingSize {
+        fn to_i64(&self) -> Option<i64> {
+            Some(match self {
+                Self::_256B => 0,
+                Self::_512B => 1,
+                Self::_1024B => 2,
+                Self::_2048B => 3,
+                Self::Auto => 7,
+                Self::Reserved(x) => (*x).into(),
+            })
+        }
+        fn to_u64(&self) -> Option<u64> {
+            Some(self.to_i64()? as u64)
+        }
+    }
+
+    impl FromPrimitive for SlinkRegionInterleavingSize {
+        fn from_u64(value: u64) -> Option<Self> {
+            Some(match value {
+                0 => Self::_256B,
+                1 => Self::_512B,
+                2 => Self::_1024B,
+                3 => Self::_2048B,
+                7 => Self::Auto,
+                x => Self::Reserved(x.try_into().ok()?),
+            })
+        }
+        fn from_i64(value: i64) -> Option<Self> {
+            if value >= 0 {
+                let value: u64 = value.try_into().ok()?;
+                Self::from_u64(value)
+            } else {
+                None
+            }
+        }
+    }
+
+    impl SlinkRegionInterleavingSize {
+        /// Returns the interleaving size in Bytes, or None if it's not a
+        /// fixed size (Auto or reserved).
+        pub fn bytes(&self) -> Option<u16> {
+            match self {
+                Self::_256B => Some(256),
+                Self::_512B => Some(512),
+                Self::_1024B => Some(1024),
+                Self::_2048B => Some(2048),
+                Self::Auto | Self::Reserved(_) => None,
+            }
+        }
+        #[cfg(feature = "serde")]
+        fn deserialize_reserved<'de, D>(
+            deserializer: D,
+        ) -> core::result::Result<u8, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            let value = <u8 as Deserialize>::deserialize(deserializer)?;
+            match Self::from_u8(value) {
+                Some(Self::Reserved(_)) => Ok(value),
+                _ => Err(serde::de::Error::custom(format!(
+                    "SlinkRegionInterleavingSize value {} is not Reserved",
+                    value
+                ))),
+            }
+        }
     }
 
     make_accessors! {
@@ -2061,7 +2514,9 @@ pub mod df {
                 alignment: 0,
                 socket: 0,
                 phys_nbio_map: 0,
-                interleaving: SlinkRegionInterleavingSize::_256B as u8,
+                interleaving: SlinkRegionInterleavingSize::_256B
+                    .to_u8()
+                    .unwrap(),
                 _reserved_: [0; 4],
             }
         }
@@ -2100,6 +2555,40 @@ pub mod df {
         }
     }
 
+    // Provisional: No public AGESA or PPR source for this layout has been
+    // found yet. Until it is confirmed, XgmiPhyOverride entries are not
+    // decoded automatically (they stay opaque bytes in the serde output);
+    // callers can still opt in with
+    // `body_as_struct_array::<XgmiPhyOverrideElement>()`.
+    make_accessors! {
+        #[derive(Default, FromBytes, AsBytes, Unaligned, PartialEq, Debug, Copy, Clone)]
+        #[repr(C, packed)]
+        pub struct XgmiPhyOverrideElement {
+            lane || SerdeHex8 : u8 | pub get u8 : pub set u8,
+            parameter_id || SerdeHex8 : u8 | pub get u8 : pub set u8,
+            _reserved_ || #[serde(default)] SerdeHex16 : LU16,
+            value || SerdeHex32 : LU32 | pub get u32 : pub set u32,
+        }
+    }
+
+    impl XgmiPhyOverrideElement {
+        pub fn new(lane: u8, parameter_id: u8, value: u32) -> Self {
+            Self { lane, parameter_id, value: value.into(), ..Self::default() }
+        }
+    }
+
+    impl EntryCompatible for XgmiPhyOverrideElement {
+        fn is_entry_compatible(entry_id: EntryId, _prefix: &[u8]) -> bool {
+            matches!(entry_id, EntryId::Df(DfEntryId::XgmiPhyOverride))
+        }
+    }
+
+    impl HeaderWithTail for XgmiPhyOverrideElement {
+        type TailArrayItemType<'de> = ();
+    }
+
+    impl_try_from_bytes!(XgmiPhyOverrideElement);
+
     #[cfg(test)]
     mod tests {
         use super::*;
@@ -2113,6 +2602,10 @@ pub mod df {
             assert!(offset_of!(SlinkRegion, socket) == 9);
             assert!(offset_of!(SlinkRegion, phys_nbio_map) == 10);
             assert!(offset_of!(SlinkRegion, interleaving) == 11);
+            const_assert!(size_of::<XgmiPhyOverrideElement>() == 8);
+            assert!(offset_of!(XgmiPhyOverrideElement, lane) == 0);
+            assert!(offset_of!(XgmiPhyOverrideElement, parameter_id) == 1);
+            assert!(offset_of!(XgmiPhyOverrideElement, value) == 4);
         }
 
         #[test]
@@ -2128,6 +2621,33 @@ pub mod df {
             assert!(slink_config.regions[2].socket == 1);
             assert!(slink_config.regions[3].socket == 1);
         }
+
+        #[test]
+        fn test_slink_region_interleaving() {
+            let mut region = SlinkRegion::default();
+            assert_eq!(
+                region.interleaving().unwrap(),
+                SlinkRegionInterleavingSize::_256B
+            );
+            assert_eq!(region.interleaving().unwrap().bytes(), Some(256));
+
+            region.set_interleaving(SlinkRegionInterleavingSize::Auto);
+            assert_eq!(region.interleaving, 7);
+            assert_eq!(
+                region.interleaving().unwrap(),
+                SlinkRegionInterleavingSize::Auto
+            );
+            assert_eq!(region.interleaving().unwrap().bytes(), None);
+
+            region.interleaving = 5;
+            assert_eq!(
+                region.interleaving().unwrap(),
+                SlinkRegionInterleavingSize::Reserved(5)
+            );
+            assert_eq!(region.interleaving().unwrap().bytes(), None);
+            region.set_interleaving(SlinkRegionInterleavingSize::Reserved(6));
+            assert_eq!(region.interleaving, 6);
+        }
     }
 }
 
@@ -2309,9 +2829,13 @@ pub mod memory {
             enable_mem_test_verbose_logging || bool : BU8 | pub get bool : pub set bool,
             enable_mem_basic_output_logging || bool : BU8 | pub get bool : pub set bool,
             _reserved_ || #[serde(default)] SerdeHex16 : LU16,
-            abl_console_port || SerdeHex32 : LU32 | pub get u32 : pub set u32,
+            abl_console_port || #[serde(default = "serde_default_abl_console_port")] SerdeHex32 : LU32 | pub get u32 : pub set u32,
         }
     }
+    #[cfg(feature = "serde")]
+    fn serde_default_abl_console_port() -> SerdeHex32 {
+        AblConsoleOutControl::default().abl_console_port.into()
+    }
     impl Default for AblConsoleOutControl {
         fn default() -> Self {
             Self {
@@ -2343,10 +2867,207 @@ pub mod memory {
         }
     }
 
+    /// Interpretation of AblConsoleOutControl::abl_console_port.
+    #[derive(Debug, PartialEq, Eq, Copy, Clone)]
+    pub enum ConsolePortKind {
+        /// The POST code port (0x80).
+        Port80,
+        /// A UART, by its (legacy I/O or FCH MMIO) base address.
+        Uart(u32),
+        /// Any other port, by its raw value.
+        Other(u32),
+    }
+
+    impl ConsolePortKind {
+        /// Legacy COM1-COM4 I/O ports and FCH UART0-UART3 MMIO bases.
+        const UART_BASES: [u32; 8] = [
+            0x3f8,
+            0x2f8,
+            0x3e8,
+            0x2e8,
+            0xfedc_9000,
+            0xfedc_a000,
+            0xfedc_e000,
+            0xfedc_f000,
+        ];
+        pub fn from_port(port: u32) -> Self {
+            if port == 0x80 {
+                Self::Port80
+            } else if Self::UART_BASES.contains(&port) {
+                Self::Uart(port)
+            } else {
+                Self::Other(port)
+            }
+        }
+        pub fn port(&self) -> u32 {
+            match *self {
+                Self::Port80 => 0x80,
+                Self::Uart(port) | Self::Other(port) => port,
+            }
+        }
+    }
+
     impl AblConsoleOutControl {
         pub fn new() -> Self {
             Self::default()
         }
+        pub fn console_port_kind(&self) -> ConsolePortKind {
+            ConsolePortKind::from_port(self.abl_console_port.get())
+        }
+        /// Sets abl_console_port to the port of VALUE.  Fails (without
+        /// changing anything) if VALUE is not what console_port_kind would
+        /// return for that port--for example Uart(0x80) or Other(0x3f8)--so
+        /// that the value always reads back as it was set.
+        pub fn set_console_port_kind(
+            &mut self,
+            value: ConsolePortKind,
+        ) -> Result<()> {
+            let port = value.port();
+            if ConsolePortKind::from_port(port) != value {
+                return Err(Error::EntryRange);
+            }
+            self.abl_console_port.set(port);
+            Ok(())
+        }
+        pub fn with_console_port_kind(
+            &mut self,
+            value: ConsolePortKind,
+        ) -> Result<&mut Self> {
+            self.set_console_port_kind(value)?;
+            Ok(self)
+        }
+    }
+
+    /// The logging flags of AblConsoleOutControl, as a set.
+    #[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+    pub struct ConsoleLogFlags(u16);
+
+    impl ConsoleLogFlags {
+        pub const CONSOLE: Self = Self(1 << 0);
+        pub const MEM_FLOW: Self = Self(1 << 1);
+        pub const MEM_SETREG: Self = Self(1 << 2);
+        pub const MEM_GETREG: Self = Self(1 << 3);
+        pub const MEM_STATUS: Self = Self(1 << 4);
+        pub const MEM_PMU: Self = Self(1 << 5);
+        pub const MEM_PMU_SRAM_READ: Self = Self(1 << 6);
+        pub const MEM_PMU_SRAM_WRITE: Self = Self(1 << 7);
+        pub const MEM_TEST_VERBOSE: Self = Self(1 << 8);
+        pub const MEM_BASIC_OUTPUT: Self = Self(1 << 9);
+        /// All the memory logging flags (that is, everything but CONSOLE).
+        pub const ALL_MEM: Self = Self(0b11_1111_1110);
+        pub const ALL: Self = Self(0b11_1111_1111);
+
+        /// Names of the flags, by bit index.  These are the same as the
+        /// names of the respective AblConsoleOutControl fields.
+        const NAMES: [&'static str; 10] = [
+            "enable_console_logging",
+            "enable_mem_flow_logging",
+            "enable_mem_setreg_logging",
+            "enable_mem_getreg_logging",
+            "enable_mem_status_logging",
+            "enable_mem_pmu_logging",
+            "enable_mem_pmu_sram_read_logging",
+            "enable_mem_pmu_sram_write_logging",
+            "enable_mem_test_verbose_logging",
+            "enable_mem_basic_output_logging",
+        ];
+
+        pub fn empty() -> Self {
+            Self(0)
+        }
+        pub fn bits(&self) -> u16 {
+            self.0
+        }
+        pub fn is_empty(&self) -> bool {
+            self.0 == 0
+        }
+        pub fn contains(&self, other: Self) -> bool {
+            self.0 & other.0 == other.0
+        }
+        pub fn insert(&mut self, other: Self) {
+            self.0 |= other.0;
+        }
+        pub fn remove(&mut self, other: Self) {
+            self.0 &= !other.0;
+        }
+        /// Returns the flag with the given NAME (see names()), if any.
+        pub fn from_name(name: &str) -> Option<Self> {
+            Self::NAMES.iter().position(|&x| x == name).map(|i| Self(1 << i))
+        }
+        /// Iterates over the names of the enabled flags.
+        pub fn names(&self) -> impl Iterator<Item = &'static str> {
+            let bits = self.0;
+            Self::NAMES
+                .iter()
+                .enumerate()
+                .filter(move |&(i, _)| bits & (1 << i) != 0)
+                .map(|(_, &name)| name)
+        }
+        pub fn from_abl(control: &AblConsoleOutControl) -> Result<Self> {
+            let mut result = Self::empty();
+            for (i, &field) in control.log_flag_fields().iter().enumerate() {
+                if field.get1()? {
+                    result.0 |= 1 << i;
+                }
+            }
+            Ok(result)
+        }
+        /// Sets the logging flags of CONTROL to exactly the ones in SELF.
+        pub fn apply_to(&self, control: &mut AblConsoleOutControl) {
+            for (i, field) in
+                control.log_flag_fields_mut().into_iter().enumerate()
+            {
+                field.set1(self.0 & (1 << i) != 0);
+            }
+        }
+    }
+
+    impl core::ops::BitOr for ConsoleLogFlags {
+        type Output = Self;
+        fn bitor(self, other: Self) -> Self {
+            Self(self.0 | other.0)
+        }
+    }
+
+    impl AblConsoleOutControl {
+        fn log_flag_fields(&self) -> [BU8; 10] {
+            [
+                self.enable_console_logging,
+                self.enable_mem_flow_logging,
+                self.enable_mem_setreg_logging,
+                self.enable_mem_getreg_logging,
+                self.enable_mem_status_logging,
+                self.enable_mem_pmu_logging,
+                self.enable_mem_pmu_sram_read_logging,
+                self.enable_mem_pmu_sram_write_logging,
+                self.enable_mem_test_verbose_logging,
+                self.enable_mem_basic_output_logging,
+            ]
+        }
+        fn log_flag_fields_mut(&mut self) -> [&mut BU8; 10] {
+            [
+                &mut self.enable_console_logging,
+                &mut self.enable_mem_flow_logging,
+                &mut self.enable_mem_setreg_logging,
+                &mut self.enable_mem_getreg_logging,
+                &mut self.enable_mem_status_logging,
+                &mut self.enable_mem_pmu_logging,
+                &mut self.enable_mem_pmu_sram_read_logging,
+                &mut self.enable_mem_pmu_sram_write_logging,
+                &mut self.enable_mem_test_verbose_logging,
+                &mut self.enable_mem_basic_output_logging,
+            ]
+        }
+        pub fn log_flags(&self) -> Result<ConsoleLogFlags> {
+            ConsoleLogFlags::from_abl(self)
+        }
+        pub fn set_log_flags(&mut self, value: ConsoleLogFlags) {
+            value.apply_to(self)
+        }
+        pub fn with_log_flags(&mut self, value: ConsoleLogFlags) -> &mut Self {
+            self.set_log_flags(value);
+            self
+        }
     }
 
     make_accessors! {
@@ -2449,7 +3170,7 @@ pub mod memory {
         pub struct NaplesAblConsoleOutControl {
             enable_console_logging || bool : BU8 | pub get bool : pub set bool,
             _reserved_0 || #[serde(default)] [SerdeHex8; 3] : [u8; 3],
-            abl_console_port || SerdeHex32 : U32<LittleEndian> | pub get u32 : pub set u32,
+            abl_console_port || #[serde(default = "serde_default_naples_abl_console_port")] SerdeHex32 : U32<LittleEndian> | pub get u32 : pub set u32,
             enable_mem_flow_logging || bool : BU8 | pub get bool : pub set bool,
             enable_mem_setreg_logging || bool : BU8 | pub get bool : pub set bool,
             enable_mem_getreg_logging || bool : BU8 | pub get bool : pub set bool,
@@ -2460,6 +3181,10 @@ pub mod memory {
             enable_mem_test_verbose_logging || bool : BU8 | pub get bool : pub set bool,
         }
     }
+    #[cfg(feature = "serde")]
+    fn serde_default_naples_abl_console_port() -> SerdeHex32 {
+        NaplesAblConsoleOutControl::default().abl_console_port.into()
+    }
     impl Default for NaplesAblConsoleOutControl {
         fn default() -> Self {
             Self {
@@ -2586,6 +3311,14 @@ pub mod memory {
         _32Bit = 4,
     }
 
+    /// Location of one of the ports used by ExtVoltageControl.
+    #[derive(Debug, Default, PartialEq, Copy, Clone)]
+    pub struct PortSpec {
+        pub port_type: PortType,
+        pub port: u32,
+        pub size: PortSize,
+    }
+
     make_accessors! {
         #[derive(FromBytes, AsBytes, Unaligned, PartialEq, Debug, Copy, Clone)]
         #[repr(C, packed)]
@@ -2658,6 +3391,18 @@ pub mod memory {
         pub fn new_disabled() -> Self {
             Self::default()
         }
+        /// "input": From the point of view of the PSP.
+        pub fn with_input_port_spec(&mut self, value: PortSpec) -> &mut Self {
+            self.with_input_port_type(value.port_type)
+                .with_input_port(value.port)
+                .with_input_port_size(value.size)
+        }
+        /// "output": From the point of view of the PSP.
+        pub fn with_output_port_spec(&mut self, value: PortSpec) -> &mut Self {
+            self.with_output_port_type(value.port_type)
+                .with_output_port(value.port)
+                .with_output_port_size(value.size)
+        }
     }
 
     make_bitfield_serde!(
@@ -2756,6 +3501,21 @@ pub mod memory {
         _20Ohm = 31,
     }
 
+    /// Command timing of the CAD bus elements (their `slow_mode` field).
+    #[derive(Debug, Clone, Copy, PartialEq, FromPrimitive, ToPrimitive)]
+    #[non_exhaustive]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    #[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+    #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+    pub enum CommandTimingMode {
+        /// One clock cycle per command (fast); `slow_mode` off.
+        #[cfg_attr(feature = "serde", serde(rename = "1T"))]
+        T1 = 0,
+        /// Two clock cycles per command (slow); `slow_mode` on.
+        #[cfg_attr(feature = "serde", serde(rename = "2T"))]
+        T2 = 1,
+    }
+
     pub type CadBusAddressCommandDriveStrength = CadBusClkDriveStrength;
     pub type CadBusCkeDriveStrength = CadBusClkDriveStrength;
     pub type CadBusCsOdtDriveStrength = CadBusClkDriveStrength;
@@ -2815,6 +3575,60 @@ pub mod memory {
         0b0000_0001_0101_0101_0101_0101_0111_1000,
         u32
     );
+    impl DdrRates {
+        /// The rates that can be enabled, by bit index.
+        const RATES: [(u32, DdrSpeed); 13] = [
+            (3, DdrSpeed::Ddr400),
+            (4, DdrSpeed::Ddr533),
+            (5, DdrSpeed::Ddr667),
+            (6, DdrSpeed::Ddr800),
+            (8, DdrSpeed::Ddr1066),
+            (10, DdrSpeed::Ddr1333),
+            (12, DdrSpeed::Ddr1600),
+            (14, DdrSpeed::Ddr1866),
+            (16, DdrSpeed::Ddr2133),
+            (18, DdrSpeed::Ddr2400),
+            (20, DdrSpeed::Ddr2667),
+            (22, DdrSpeed::Ddr2933),
+            (24, DdrSpeed::Ddr3200),
+        ];
+        /// Returns rates with all the supported rates up to (and including)
+        /// MAX enabled.  If MAX is an "unsupported" sentinel, no rate is
+        /// enabled.
+        pub fn capped_at(max: DdrSpeed) -> Self {
+            let mut bits = 0u32;
+            if max.is_unsupported() {
+                return Self::from(bits);
+            }
+            for (bit, speed) in Self::RATES {
+                if speed as u32 <= max as u32 {
+                    bits |= 1 << bit;
+                }
+            }
+            Self::from(bits)
+        }
+        /// Iterates over the enabled rates, in ascending order.
+        pub fn enabled_rates(&self) -> impl Iterator<Item = DdrSpeed> {
+            let bits = u32::from(*self);
+            Self::RATES
+                .into_iter()
+                .filter(move |&(bit, _)| bits & (1 << bit) != 0)
+                .map(|(_, speed)| speed)
+        }
+        /// Checks that no rate above MAX (for example the speed cap of
+        /// a MaxFreqElement) is enabled.  If MAX is an "unsupported"
+        /// sentinel, no rate may be enabled.
+        pub fn validate_max(&self, max: DdrSpeed) -> Result<()> {
+            if self
+                .enabled_rates()
+                .any(|speed| max.is_unsupported() || speed as u32 > max as u32)
+            {
+                Err(Error::EntryRange)
+            } else {
+                Ok(())
+            }
+        }
+    }
 
     make_bitfield_serde! {
         #[bitfield(bits = 32)]
@@ -2970,7 +3784,7 @@ pub mod memory {
 
             gear_down_mode || bool : BLU16 | pub get bool : pub set bool,
             _reserved_ || #[serde(default)] SerdeHex16 : LU16,
-            slow_mode || bool : BLU16 | pub get bool : pub set bool, // (probably) 2T is slow, 1T is fast
+            slow_mode || bool : BLU16 | pub get bool : pub set bool, // 2T if set, 1T otherwise; see command_timing
             _reserved_2 || #[serde(default)] SerdeHex16 : LU16,
             address_command_control || SerdeHex32 : LU32 | pub get u32 : pub set u32, // 24 bit; often all used bytes are equal
 
@@ -3036,6 +3850,19 @@ pub mod memory {
         }
     }
 
+    impl RdimmDdr4CadBusElement {
+        pub fn command_timing(&self) -> Result<CommandTimingMode> {
+            Ok(if self.slow_mode()? {
+                CommandTimingMode::T2
+            } else {
+                CommandTimingMode::T1
+            })
+        }
+        pub fn set_command_timing(&mut self, value: CommandTimingMode) {
+            self.set_slow_mode(value == CommandTimingMode::T2)
+        }
+    }
+
     impl EntryCompatible for RdimmDdr4CadBusElement {
         fn is_entry_compatible(entry_id: EntryId, _prefix: &[u8]) -> bool {
             match entry_id {
@@ -3097,7 +3924,7 @@ pub mod memory {
 
             gear_down_mode || SerdeHex16 : LU16 | pub get u16 : pub set u16,
             _reserved_ || #[serde(default)] SerdeHex16 : LU16,
-            slow_mode || SerdeHex16 : LU16 | pub get u16 : pub set u16,
+            slow_mode || SerdeHex16 : LU16 | pub get u16 : pub set u16, // 2T if 1, 1T if 0; see command_timing
             _reserved_2 || #[serde(default)] SerdeHex16 : LU16,
             address_command_control || SerdeHex32 : LU32 | pub get u32 : pub set u32, // 24 bit; often all used bytes are equal
 
@@ -3131,6 +3958,16 @@ pub mod memory {
         }
     }
 
+    impl UdimmDdr4CadBusElement {
+        pub fn command_timing(&self) -> Result<CommandTimingMode> {
+            CommandTimingMode::from_u16(self.slow_mode()?)
+                .ok_or(Error::EntryTypeMismatch)
+        }
+        pub fn set_command_timing(&mut self, value: CommandTimingMode) {
+            self.set_slow_mode(value.to_u16().unwrap())
+        }
+    }
+
     impl EntryCompatible for UdimmDdr4CadBusElement {
         fn is_entry_compatible(entry_id: EntryId, _prefix: &[u8]) -> bool {
             match entry_id {
@@ -3192,7 +4029,7 @@ pub mod memory {
 
             gear_down_mode || SerdeHex16 : LU16 | pub get u16 : pub set u16,
             _reserved_ || #[serde(default)] SerdeHex16 : LU16,
-            slow_mode || SerdeHex16 : LU16 | pub get u16 : pub set u16,
+            slow_mode || SerdeHex16 : LU16 | pub get u16 : pub set u16, // 2T if 1, 1T if 0; see command_timing
             _reserved_2 || #[serde(default)] SerdeHex16 : LU16,
             address_command_control || SerdeHex32 : LU32 | pub get u32 : pub set u32, // 24 bit; often all used bytes are equal
 
@@ -3258,6 +4095,16 @@ pub mod memory {
         }
     }
 
+    impl LrdimmDdr4CadBusElement {
+        pub fn command_timing(&self) -> Result<CommandTimingMode> {
+            CommandTimingMode::from_u16(self.slow_mode()?)
+                .ok_or(Error::EntryTypeMismatch)
+        }
+        pub fn set_command_timing(&mut self, value: CommandTimingMode) {
+            self.set_slow_mode(value.to_u16().unwrap())
+        }
+    }
+
     impl EntryCompatible for LrdimmDdr4CadBusElement {
         fn is_entry_compatible(entry_id: EntryId, _prefix: &[u8]) -> bool {
             matches!(
@@ -3932,9 +4779,149 @@ pub mod memory {
         }
     }
 
+    impl RdimmDdr5BusElement {
+        /// Creates an element for TARGET_MEMCLK (in MHz) with AMD's
+        /// default payload.
+        pub fn new(target_memclk: u32, dimm_slots_per_channel: u8) -> Self {
+            Self {
+                header: RdimmDdr5BusElementHeader {
+                    target_memclk: target_memclk.into(),
+                    dimm_slots_per_channel,
+                    ..RdimmDdr5BusElementHeader::default()
+                },
+                payload: RdimmDdr5BusElementPayload::default(),
+            }
+        }
+    }
+
+    /// Returns whether PREFIX consists of PsRdimmDdr5Bus elements of
+    /// ELEMENT_SIZE Byte each--that is, whether every one of those elements
+    /// starts with a RdimmDdr5BusElementHeader whose total_size is the size
+    /// of that header.
+    fn has_rdimm_ddr5_bus_element_size(
+        prefix: &[u8],
+        element_size: usize,
+    ) -> bool {
+        prefix.len() % element_size == 0
+            && prefix.chunks_exact(element_size).all(|element| {
+                match RdimmDdr5BusElementHeader::read_from_prefix(element) {
+                    Some(header) => {
+                        header.total_size.get() as usize
+                            == size_of::<RdimmDdr5BusElementHeader>()
+                    }
+                    None => false,
+                }
+            })
+    }
+
+    /// Returns whether the PsRdimmDdr5Bus body (starting with) PREFIX
+    /// consists of RdimmDdr5BusElement1240, as opposed to
+    /// RdimmDdr5BusElement.  Both start with the same header and payload,
+    /// and we know nothing about the contents of the (Turin) payload
+    /// extension, so the element size is determined by checking where the
+    /// element headers are (the body size alone is ambiguous for bodies
+    /// that are a multiple of both 136 and 1240 Byte).  If the headers
+    /// line up for both layouts, the older layout wins.
+    fn is_rdimm_ddr5_bus_1240_body(prefix: &[u8]) -> bool {
+        !has_rdimm_ddr5_bus_element_size(
+            prefix,
+            size_of::<RdimmDdr5BusElement>(),
+        ) && has_rdimm_ddr5_bus_element_size(
+            prefix,
+            size_of::<RdimmDdr5BusElement1240>(),
+        )
+    }
+
     impl EntryCompatible for RdimmDdr5BusElement {
-        fn is_entry_compatible(entry_id: EntryId, _prefix: &[u8]) -> bool {
+        fn is_entry_compatible(entry_id: EntryId, prefix: &[u8]) -> bool {
+            matches!(entry_id, EntryId::Memory(MemoryEntryId::PsRdimmDdr5Bus))
+                && !is_rdimm_ddr5_bus_1240_body(prefix)
+        }
+        fn skip_step(entry_id: EntryId, prefix: &[u8]) -> Option<(u16, usize)> {
+            if Self::is_entry_compatible(entry_id, prefix) {
+                Some((0, size_of::<Self>()))
+            } else {
+                None
+            }
+        }
+    }
+
+    make_accessors! {
+        /// Turin extension of RdimmDdr5BusElementPayload.
+        ///
+        /// Placeholder: Only its size is known; there is no public
+        /// documentation of its contents.  Therefore, they are kept
+        /// verbatim (and the split into fields below means nothing).
+        #[derive(FromBytes, AsBytes, Unaligned, PartialEq, Debug, Default, Copy, Clone)]
+        #[repr(C, packed)]
+        pub struct RdimmDdr5BusElementPayloadExt1104 {
+            _reserved_0 || #[serde(default)] [SerdeHex32; 26] : [LU32; 26],
+            _reserved_1 || #[serde(default)] [SerdeHex32; 25] : [LU32; 25],
+            _reserved_2 || #[serde(default)] [SerdeHex32; 25] : [LU32; 25],
+            _reserved_3 || #[serde(default)] [SerdeHex32; 25] : [LU32; 25],
+            _reserved_4 || #[serde(default)] [SerdeHex32; 25] : [LU32; 25],
+            _reserved_5 || #[serde(default)] [SerdeHex32; 25] : [LU32; 25],
+            _reserved_6 || #[serde(default)] [SerdeHex32; 25] : [LU32; 25],
+            _reserved_7 || #[serde(default)] [SerdeHex32; 25] : [LU32; 25],
+            _reserved_8 || #[serde(default)] [SerdeHex32; 25] : [LU32; 25],
+            _reserved_9 || #[serde(default)] [SerdeHex32; 25] : [LU32; 25],
+            _reserved_10 || #[serde(default)] [SerdeHex32; 25] : [LU32; 25],
+        }
+    }
+
+    impl Getter<Result<RdimmDdr5BusElementPayloadExt1104>>
+        for RdimmDdr5BusElementPayloadExt1104
+    {
+        fn get1(self) -> Result<RdimmDdr5BusElementPayloadExt1104> {
+            Ok(self)
+        }
+    }
+
+    impl Setter<RdimmDdr5BusElementPayloadExt1104>
+        for RdimmDdr5BusElementPayloadExt1104
+    {
+        fn set1(&mut self, value: RdimmDdr5BusElementPayloadExt1104) {
+            *self = value
+        }
+    }
+
+    make_accessors! {
+        /// Turin layout of the PsRdimmDdr5Bus element (1240 Byte).  See
+        /// RdimmDdr5BusElementPayloadExt1104 for the caveats.
+        #[derive(FromBytes, AsBytes, Unaligned, PartialEq, Debug, Default, Copy, Clone)]
+        #[repr(C, packed)]
+        pub struct RdimmDdr5BusElement1240 {
+            header: RdimmDdr5BusElementHeader,
+            payload: RdimmDdr5BusElementPayload,
+            payload_ext: RdimmDdr5BusElementPayloadExt1104,
+        }
+    }
+
+    impl RdimmDdr5BusElement1240 {
+        /// Creates an element for TARGET_MEMCLK (in MHz) with AMD's
+        /// default payload.
+        pub fn new(target_memclk: u32, dimm_slots_per_channel: u8) -> Self {
+            let RdimmDdr5BusElement { header, payload } =
+                RdimmDdr5BusElement::new(target_memclk, dimm_slots_per_channel);
+            Self {
+                header,
+                payload,
+                payload_ext: RdimmDdr5BusElementPayloadExt1104::default(),
+            }
+        }
+    }
+
+    impl EntryCompatible for RdimmDdr5BusElement1240 {
+        fn is_entry_compatible(entry_id: EntryId, prefix: &[u8]) -> bool {
             matches!(entry_id, EntryId::Memory(MemoryEntryId::PsRdimmDdr5Bus))
+                && is_rdimm_ddr5_bus_1240_body(prefix)
+        }
+        fn skip_step(entry_id: EntryId, prefix: &[u8]) -> Option<(u16, usize)> {
+            if Self::is_entry_compatible(entry_id, prefix) {
+                Some((0, size_of::<Self>()))
+            } else {
+                None
+            }
         }
     }
 
@@ -4248,6 +5235,26 @@ pub mod memory {
         UnsupportedMilan = 4401, // and Turin
     }
 
+    impl DdrSpeed {
+        /// Returns whether this is one of the "unsupported" sentinels
+        /// (UnsupportedRome or UnsupportedMilan) rather than a speed.
+        pub fn is_unsupported(&self) -> bool {
+            matches!(self, Self::UnsupportedRome | Self::UnsupportedMilan)
+        }
+        /// Returns the "unsupported" sentinel that GENERATION expects.
+        pub fn unsupported(
+            generation: crate::types::SiliconGeneration,
+        ) -> Self {
+            use crate::types::SiliconGeneration;
+            match generation {
+                SiliconGeneration::Naples | SiliconGeneration::Rome => {
+                    Self::UnsupportedRome
+                }
+                _ => Self::UnsupportedMilan,
+            }
+        }
+    }
+
     // Usually an array of those is used
     // Note: This structure is not used for LR DRAM
     make_accessors! {
@@ -4257,10 +5264,15 @@ pub mod memory {
             dimm_slots_per_channel || DimmsPerChannel : u8 | pub get DimmsPerChannel : pub set DimmsPerChannel,
             _reserved_ || #[serde(default)] SerdeHex8 : u8,
             conditions || [SerdeHex16; 4] : [LU16; 4], // number of dimm on a channel, number of single-rank dimm, number of dual-rank dimm, number of quad-rank dimm
-            speeds || [SerdeHex16; 3] : [LU16; 3], // speed limit with voltage 1.5 V, 1.35 V, 1.25 V // FIXME make accessible
+            speeds || [SerdeHex16; 3] : [LU16; 3], // speed limit with voltage 1.5 V, 1.35 V, 1.25 V
         }
     }
     impl MaxFreqElement {
+        fn speed_at(&self, index: usize) -> Result<Option<DdrSpeed>> {
+            let speed = DdrSpeed::from_u16(self.speeds[index].get())
+                .ok_or(Error::EntryTypeMismatch)?;
+            Ok(if speed.is_unsupported() { None } else { Some(speed) })
+        }
         pub fn dimm_count(&self) -> Result<u16> {
             Ok(self.conditions[0].get())
         }
@@ -4285,13 +5297,61 @@ pub mod memory {
         pub fn set_quad_rank_count(&mut self, value: u16) {
             self.conditions[3].set(value);
         }
-        pub fn speed(&self) -> Result<DdrSpeed> {
-            DdrSpeed::from_u16(self.speeds[0].get())
-                .ok_or(Error::EntryTypeMismatch)
+        /// Returns the speed limit, or None if the configuration is
+        /// unsupported.
+        pub fn speed(&self) -> Result<Option<DdrSpeed>> {
+            self.speed_at(0)
         }
         pub fn set_speed(&mut self, value: DdrSpeed) {
             self.speeds[0].set(value.to_u16().unwrap())
         }
+        /// Returns the speed limit at 1.35 V, or None if the configuration
+        /// is unsupported.
+        /// Note: For DDR5, only speed is meaningful; this is always the
+        /// unsupported sentinel (so None) there.
+        pub fn speed_1_35v(&self) -> Result<Option<DdrSpeed>> {
+            self.speed_at(1)
+        }
+        pub fn set_speed_1_35v(&mut self, value: DdrSpeed) {
+            self.speeds[1].set(value.to_u16().unwrap())
+        }
+        /// Returns the speed limit at 1.25 V, or None if the configuration
+        /// is unsupported.
+        /// Note: For DDR5, only speed is meaningful; this is always the
+        /// unsupported sentinel (so None) there.
+        pub fn speed_1_25v(&self) -> Result<Option<DdrSpeed>> {
+            self.speed_at(2)
+        }
+        pub fn set_speed_1_25v(&mut self, value: DdrSpeed) {
+            self.speeds[2].set(value.to_u16().unwrap())
+        }
+
+        /// Checks that the dimm count does not exceed what
+        /// dimm_slots_per_channel allows.
+        pub fn validate(&self) -> Result<()> {
+            let max_dimm_count = match self.dimm_slots_per_channel()? {
+                DimmsPerChannel::NoSlot => 0,
+                DimmsPerChannel::DontCare => return Ok(()),
+                DimmsPerChannel::Specific(selector) => {
+                    if selector.four_dimms() {
+                        4
+                    } else if selector.three_dimms() {
+                        3
+                    } else if selector.two_dimms() {
+                        2
+                    } else if selector.one_dimm() {
+                        1
+                    } else {
+                        0
+                    }
+                }
+            };
+            if self.dimm_count()? > max_dimm_count {
+                Err(Error::EntryRange)
+            } else {
+                Ok(())
+            }
+        }
 
         /// Note: unsupported_speed differs between Rome and Milan--so pass
         /// UnsupportedRome or UnsupportedMilan here as appropriate.
@@ -4419,11 +5479,18 @@ pub mod memory {
         }
     }
 
+    // Provisional: There is no documentation of the layout of the LRDIMM
+    // DDR4 stretch frequency entry, and it was not checked against a real
+    // blob.  It's assumed to use the max frequency layout, like it is for
+    // the other DIMM types.
+    pub type LrStretchFreqElement = LrMaxFreqElement;
+
     impl EntryCompatible for LrMaxFreqElement {
         fn is_entry_compatible(entry_id: EntryId, _prefix: &[u8]) -> bool {
             matches!(
                 entry_id,
                 EntryId::Memory(MemoryEntryId::PsLrdimmDdr4MaxFreq)
+                    | EntryId::Memory(MemoryEntryId::PsLrdimmDdr4StretchFreq)
             )
         }
     }
@@ -4767,6 +5834,61 @@ Clone)]
     define_ErrorOutControl!(ErrorOutControl116, 3, 1); // Milan
     define_ErrorOutControl!(ErrorOutControl112, 0, 0);
 
+    /// Copies all the fields (except for the padding, which differs between
+    /// the two layouts) from one ErrorOutControl variant to the other.
+    macro_rules! convert_ErrorOutControl {
+        ($from:ident, $to:ident) => {
+            impl From<&$from> for $to {
+                fn from(src: &$from) -> Self {
+                    Self {
+                        enable_error_reporting: src.enable_error_reporting,
+                        enable_error_reporting_gpio: src
+                            .enable_error_reporting_gpio,
+                        enable_error_reporting_beep_codes: src
+                            .enable_error_reporting_beep_codes,
+                        enable_using_handshake: src.enable_using_handshake,
+                        input_port: src.input_port,
+                        output_delay: src.output_delay,
+                        output_port: src.output_port,
+                        stop_on_first_fatal_error: src
+                            .stop_on_first_fatal_error,
+                        _reserved_: src._reserved_,
+                        input_port_size: src.input_port_size,
+                        output_port_size: src.output_port_size,
+                        input_port_type: src.input_port_type,
+                        output_port_type: src.output_port_type,
+                        clear_acknowledgement: src.clear_acknowledgement,
+                        error_reporting_gpio: src.error_reporting_gpio,
+                        beep_code_table: src.beep_code_table,
+                        enable_heart_beat: src.enable_heart_beat,
+                        enable_power_good_gpio: src.enable_power_good_gpio,
+                        power_good_gpio: src.power_good_gpio,
+                        _reserved_end: src._reserved_end,
+                        ..Self::default()
+                    }
+                }
+            }
+        };
+    }
+
+    convert_ErrorOutControl!(ErrorOutControl116, ErrorOutControl112);
+    convert_ErrorOutControl!(ErrorOutControl112, ErrorOutControl116);
+
+    /// Converts a Milan ErrorOutControl to the layout used by the other
+    /// processor generations.
+    pub fn convert_error_out_control(
+        src: &ErrorOutControl116,
+    ) -> ErrorOutControl112 {
+        src.into()
+    }
+
+    /// Converts an ErrorOutControl to the Milan layout.
+    pub fn convert_error_out_control_to_116(
+        src: &ErrorOutControl112,
+    ) -> ErrorOutControl116 {
+        src.into()
+    }
+
     make_bitfield_serde! {
         #[bitfield(bits = 32)]
         #[repr(u32)]
@@ -4926,6 +6048,49 @@ Clone)]
         }
     }
 
+    impl_reserved_bits!(
+        RdimmDdr4CadBusElement,
+        [ddr_rates, vdd_io, dimm0_ranks, dimm1_ranks,]
+    );
+    impl_reserved_bits!(
+        UdimmDdr4CadBusElement,
+        [ddr_rates, vdd_io, dimm0_ranks, dimm1_ranks,]
+    );
+    impl_reserved_bits!(
+        LrdimmDdr4CadBusElement,
+        [ddr_rates, vdd_io, dimm0_ranks, dimm1_ranks,]
+    );
+    impl_reserved_bits!(
+        Ddr4DataBusElement,
+        [ddr_rates, vdd_io, dimm0_ranks, dimm1_ranks,]
+    );
+    impl_reserved_bits!(
+        LrdimmDdr4DataBusElement,
+        [ddr_rates, vdd_io, dimm0_ranks, dimm1_ranks,]
+    );
+    impl_reserved_bits!(
+        Ddr4OdtPatElement,
+        [
+            dimm_rank_bitmaps,
+            cs0_odt_patterns,
+            cs1_odt_patterns,
+            cs2_odt_patterns,
+            cs3_odt_patterns,
+        ]
+    );
+    impl_reserved_bits!(
+        LrdimmDdr4OdtPatElement,
+        [
+            dimm_rank_bitmaps,
+            cs0_odt_patterns,
+            cs1_odt_patterns,
+            cs2_odt_patterns,
+            cs3_odt_patterns,
+        ]
+    );
+    impl_reserved_bits!(MaxFreqElement, [dimm_slots_per_channel]);
+    impl_reserved_bits!(ErrorOutControlBeepCode, [peak_attr]);
+
     /*
         #[derive(BitfieldSpecifier, Debug, PartialEq)]
         #[bits = 1]
@@ -5119,6 +6284,28 @@ Clone)]
         }
     }
 
+    impl DdrDqPinMapElement {
+        /// Returns the pin that LANE's BIT is mapped to.
+        pub fn pin_for(&self, lane: usize, bit: usize) -> Result<u8> {
+            let lanes = self.lanes;
+            let lane = lanes.get(lane).ok_or(Error::EntryRange)?;
+            lane.pins.get(bit).copied().ok_or(Error::EntryRange)
+        }
+        /// Maps LANE's BIT to PIN.
+        pub fn set_pin_for(
+            &mut self,
+            lane: usize,
+            bit: usize,
+            pin: u8,
+        ) -> Result<()> {
+            let mut lanes = self.lanes;
+            let lane = lanes.get_mut(lane).ok_or(Error::EntryRange)?;
+            *lane.pins.get_mut(bit).ok_or(Error::EntryRange)? = pin;
+            self.lanes = lanes;
+            Ok(())
+        }
+    }
+
     impl EntryCompatible for DdrDqPinMapElement {
         fn is_entry_compatible(entry_id: EntryId, _prefix: &[u8]) -> bool {
             matches!(entry_id, EntryId::Memory(MemoryEntryId::DdrDqPinMap))
@@ -5180,6 +6367,26 @@ Clone)]
         type TailArrayItemType<'de> = ();
     }
 
+    impl PmuBistVendorAlgorithmElement {
+        /// Returns the indices of the enabled BIST algorithms.
+        pub fn algorithms(&self) -> impl Iterator<Item = u8> {
+            let mask = self.algorithm_bit_mask.get();
+            (0..16u8).filter(move |index| mask & (1 << index) != 0)
+        }
+        /// Enables exactly the BIST algorithms with the given INDICES.
+        pub fn set_algorithms(&mut self, indices: &[u8]) -> Result<()> {
+            let mut mask = 0u16;
+            for &index in indices {
+                if index >= 16 {
+                    return Err(Error::EntryRange);
+                }
+                mask |= 1 << index;
+            }
+            self.algorithm_bit_mask.set(mask);
+            Ok(())
+        }
+    }
+
     impl Default for PmuBistVendorAlgorithmElement {
         fn default() -> Self {
             Self {
@@ -5198,6 +6405,22 @@ Clone)]
         }
     }
 
+    /// Rank type, as used in the dimm_type bitmap of
+    /// Ddr5RawCardConfigElementHeader32.  The value is the bit index.
+    ///
+    /// Provisional: These bit assignments are not confirmed by an AGESA or
+    /// PPR source yet.
+    #[derive(Debug, PartialEq, FromPrimitive, ToPrimitive, Copy, Clone)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    #[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+    #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+    pub enum Ddr5DimmType {
+        Rdimm = 0,
+        Lrdimm = 1,
+        #[cfg_attr(feature = "serde", serde(rename = "3DS"))]
+        ThreeDs = 2,
+    }
+
     make_accessors! {
         // FIXME default
         #[derive(Default, FromBytes, AsBytes, Unaligned, PartialEq, Debug, Copy, Clone)]
@@ -5223,6 +6446,85 @@ Clone)]
             pub _reserved_8 || #[serde(default)] u8 : u8,
         }
     }
+
+    impl Ddr5RawCardConfigElementHeader32 {
+        /// Populates the header the same way it would be populated from
+        /// the (DDR5) SPD of the DIMM.
+        #[allow(clippy::too_many_arguments)]
+        pub fn from_spd(
+            mem_clk: DdrSpeed,
+            dimm_type: u8,
+            dev_width: u8,
+            rcd_manufacturer_id: u32,
+            rcd_generation: u8,
+            raw_card_dev: u32,
+            dram_die_stepping_revision: u32,
+            dram_density: u8,
+        ) -> Self {
+            Self {
+                total_size: (size_of::<Self>() as u32).into(),
+                mem_clk: mem_clk.to_u32().unwrap().into(),
+                dimm_type,
+                dev_width,
+                rcd_manufacturer_id: rcd_manufacturer_id.into(),
+                rcd_generation,
+                raw_card_dev: raw_card_dev.into(),
+                dram_die_stepping_revision: dram_die_stepping_revision.into(),
+                dram_density,
+                ..Self::default()
+            }
+        }
+
+        /// Decodes the dimm_type bitmap (see Ddr5DimmType, whose bit
+        /// assignments are provisional).
+        pub fn dimm_types(&self) -> impl Iterator<Item = Ddr5DimmType> {
+            let mask = self.dimm_type;
+            (0..8u8)
+                .filter(move |index| mask & (1 << index) != 0)
+                .filter_map(Ddr5DimmType::from_u8)
+        }
+        /// Sets the dimm_type bitmap to exactly the given TYPES.
+        pub fn set_dimm_types(&mut self, types: &[Ddr5DimmType]) {
+            self.dimm_type = types
+                .iter()
+                .fold(0u8, |mask, &ty| mask | (1 << ty.to_u8().unwrap()));
+        }
+        pub fn with_dimm_types(&mut self, types: &[Ddr5DimmType]) -> &mut Self {
+            self.set_dimm_types(types);
+            self
+        }
+
+        /// Decodes the dev_width bitmap into SDRAM IO widths (4, 8, 16, 32).
+        /// Provisional: That bit 0 is x4, bit 1 is x8 and so on is not
+        /// confirmed by an AGESA or PPR source yet.
+        pub fn dev_widths(&self) -> impl Iterator<Item = u8> {
+            let mask = self.dev_width;
+            (0..4u8)
+                .filter(move |index| mask & (1 << index) != 0)
+                .map(|index| 4 << index)
+        }
+        /// Sets the dev_width bitmap to exactly the given SDRAM IO WIDTHS
+        /// (each of which has to be one of 4, 8, 16, 32).
+        pub fn set_dev_widths(&mut self, widths: &[u8]) -> Result<()> {
+            let mut mask = 0u8;
+            for &width in widths {
+                mask |= match width {
+                    4 => 0b0001,
+                    8 => 0b0010,
+                    16 => 0b0100,
+                    32 => 0b1000,
+                    _ => return Err(Error::EntryRange),
+                };
+            }
+            self.dev_width = mask;
+            Ok(())
+        }
+        pub fn with_dev_widths(&mut self, widths: &[u8]) -> Result<&mut Self> {
+            self.set_dev_widths(widths)?;
+            Ok(self)
+        }
+    }
+
     impl Getter<Result<Ddr5RawCardConfigElementHeader32>>
         for Ddr5RawCardConfigElementHeader32
     {
@@ -5750,6 +7052,19 @@ Clone)]
         _97_5P = 0x00,
     }
 
+    impl Ddr5RawCardVref {
+        /// Returns the Vref as a percentage (of VDD).
+        pub fn percent(&self) -> f32 {
+            97.5 - f32::from(self.to_u8().unwrap()) * 0.5
+        }
+        /// Returns the Vref that is nearest to the percentage P (of VDD).
+        /// P is clamped to the supported range 35.0 % ... 97.5 %.
+        pub fn nearest_percent(p: f32) -> Self {
+            let encoding = ((97.5 - p.clamp(35.0, 97.5)) * 2.0 + 0.5) as u8;
+            Self::from_u8(encoding).unwrap()
+        }
+    }
+
     make_accessors! {
         // FIXME default
         #[derive(Default, FromBytes, AsBytes, Unaligned, PartialEq, Debug, Copy, Clone)]
@@ -5945,8 +7260,75 @@ Clone)]
         type TailArrayItemType<'de> = ();
     }
 
+    impl_try_from_bytes!(
+        DimmInfoSmbusElement,
+        RdimmDdr4CadBusElement,
+        UdimmDdr4CadBusElement,
+        LrdimmDdr4CadBusElement,
+        Ddr4DataBusElement,
+        LrdimmDdr4DataBusElement,
+        RdimmDdr5BusElement,
+        RdimmDdr5BusElement1240,
+        MemDfeSearchElement32,
+        MemDfeSearchElement36,
+        MaxFreqElement,
+        LrMaxFreqElement,
+        Ddr4OdtPatElement,
+        LrdimmDdr4OdtPatElement,
+        DdrPostPackageRepairElement,
+        DdrDqPinMapElement,
+        Ddr5CaPinMapElement,
+        PmuBistVendorAlgorithmElement,
+        Ddr5RawCardConfigElement,
+    );
+
+    /// Set of memory channels whose MEMCLK outputs are disabled, as encoded
+    /// by platform_specific_override::MemclkMap (one bit per channel).
+    #[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+    pub struct MemClkDisableMap {
+        pub channels: u32,
+    }
+
+    impl MemClkDisableMap {
+        pub fn builder() -> Self {
+            Self::default()
+        }
+        pub fn build(&self) -> Self {
+            *self
+        }
+        /// Iterates over the indices of the disabled channels, in ascending
+        /// order.
+        pub fn disabled_channels(&self) -> impl Iterator<Item = u8> {
+            let channels = self.channels;
+            (0..32u8).filter(move |&i| channels & (1 << i) != 0)
+        }
+        pub fn is_disabled(&self, channel: u8) -> bool {
+            channel < 32 && self.channels & (1 << channel) != 0
+        }
+        pub fn set_disabled_channels(&mut self, channels: &[u8]) -> Result<()> {
+            let mut mask = 0u32;
+            for &channel in channels {
+                if channel >= 32 {
+                    return Err(Error::EntryRange);
+                }
+                mask |= 1 << channel;
+            }
+            self.channels = mask;
+            Ok(())
+        }
+        pub fn with_disabled_channels(
+            &mut self,
+            channels: &[u8],
+        ) -> Result<&mut Self> {
+            self.set_disabled_channels(channels)?;
+            Ok(self)
+        }
+    }
+
     pub mod platform_specific_override {
-        use super::{EntryId, Error, MemoryEntryId};
+        use super::{
+            EntryId, Error, MemClkDisableMap, MemoryEntryId, ReservedBits,
+        };
         crate::struct_variants_enum::collect_EntryCompatible_impl_into_enum! {
                         // See AMD #44065
 
@@ -6426,6 +7808,26 @@ Clone)]
                                     ..Self::default()
                                 })
                             }
+                            /// Channels whose connection map is zero (that is, all
+                            /// of whose MEMCLK outputs are disabled).
+                            pub fn memclk_disable_map(&self) -> MemClkDisableMap {
+                                let mut result = MemClkDisableMap::default();
+                                for (i, &connection) in self.connections.iter().enumerate() {
+                                    if connection == 0 {
+                                        result.channels |= 1 << i;
+                                    }
+                                }
+                                result
+                            }
+                            /// Disables all MEMCLK outputs of the channels in MAP.
+                            /// Other channels are left alone.
+                            pub fn disable_channels(&mut self, map: MemClkDisableMap) -> Result<()> {
+                                for channel in map.disabled_channels() {
+                                    let connection = self.connections.get_mut(usize::from(channel)).ok_or(Error::EntryRange)?;
+                                    *connection = 0;
+                                }
+                                Ok(())
+                            }
                         }
 
                         make_accessors! {
@@ -7037,6 +8439,50 @@ Clone)]
                         // TODO: conditional overrides, actions.
                 }
 
+        macro_rules! impl_element_reserved_bits {
+            ($($struct_:ident: [$($field:ident),*]),* $(,)?) => {
+                $(
+                    impl_reserved_bits!($struct_, [$($field),*]);
+                )*
+                impl ReservedBits for ElementRef<'_> {
+                    fn visit_reserved_bits(
+                        &self,
+                        f: &mut dyn FnMut(&'static str, u64),
+                    ) {
+                        match self {
+                            $(
+                                Self::$struct_(item) => {
+                                    item.visit_reserved_bits(f)
+                                }
+                            )*
+                            _ => {}
+                        }
+                    }
+                }
+            };
+        }
+        impl_element_reserved_bits! {
+            CkeTristateMap: [dimms],
+            OdtTristateMap: [dimms],
+            CsTristateMap: [dimms],
+            MaxDimmsPerChannel: [dimms],
+            MaxDimmsPerChannel6: [channels, dimms],
+            MemclkMap: [dimms],
+            MaxChannelsPerSocket: [dimms],
+            MemBusSpeed: [dimms],
+            MaxCsPerChannel: [dimms],
+            MemTechnology: [dimms],
+            WriteLevellingSeedDelay: [dimms],
+            RxEnSeed: [dimms],
+            LrDimmNoCs6Cs7Routing: [dimms],
+            SolderedDownSodimm: [dimms],
+            LvDimmForce1V5: [dimms],
+            MinimumRwDataEyeWidth: [dimms],
+            SolderedDownDimmsPerChannel: [dimms],
+            MemPowerPolicy: [dimms],
+            MotherboardLayers: [dimms],
+        }
+
         impl EntryCompatible for ElementRef<'_> {
             fn is_entry_compatible(entry_id: EntryId, _prefix: &[u8]) -> bool {
                 // Also supports empty chunks, so don't check prefix.
@@ -7343,6 +8789,12 @@ Clone)]
             const_assert!(size_of::<RdimmDdr5BusElementHeader>() == 12);
             const_assert!(size_of::<RdimmDdr5BusElementPayload>() == 124);
             const_assert!(size_of::<RdimmDdr5BusElement>() == 12 + 124);
+            const_assert!(
+                size_of::<RdimmDdr5BusElementPayloadExt1104>() == 1104
+            );
+            const_assert!(size_of::<RdimmDdr5BusElement1240>() == 1240);
+            assert!(offset_of!(RdimmDdr5BusElement1240, payload) == 12);
+            assert!(offset_of!(RdimmDdr5BusElement1240, payload_ext) == 136);
             const_assert!(size_of::<MemDfeSearchElementHeader12>() == 12);
             const_assert!(size_of::<MemDfeSearchElementPayload12>() == 12);
             const_assert!(size_of::<MemDfeSearchElementPayloadExt12>() == 12);
@@ -7418,6 +8870,520 @@ Clone)]
             );
         }
 
+        #[test]
+        fn test_ddr5_raw_card_config_header_from_spd() {
+            let header = Ddr5RawCardConfigElementHeader32::from_spd(
+                DdrSpeed::Ddr4800,
+                0b10,
+                0b01,
+                0x80ce,
+                0x2,
+                0x11,
+                0x1,
+                0x8,
+            );
+            let mut manual = Ddr5RawCardConfigElementHeader32::default();
+            manual.set_mem_clk(DdrSpeed::Ddr4800);
+            manual.total_size = 32.into();
+            manual.dimm_type = 0b10;
+            manual.dev_width = 0b01;
+            manual.rcd_manufacturer_id = 0x80ce.into();
+            manual.rcd_generation = 0x2;
+            manual.raw_card_dev = 0x11.into();
+            manual.dram_die_stepping_revision = 0x1.into();
+            manual.dram_density = 0x8;
+            assert_eq!(header, manual);
+            assert_eq!(header.mem_clk().unwrap(), DdrSpeed::Ddr4800);
+        }
+
+        #[test]
+        fn test_rdimm_ddr5_bus_element_new() {
+            let element = RdimmDdr5BusElement::new(2400, 1);
+            assert_eq!(element.header.target_memclk.get(), 2400);
+            assert_eq!(element.header.dimm_slots_per_channel, 1);
+            assert_eq!(element.header.total_size.get(), 12);
+            assert_eq!(element.payload, RdimmDdr5BusElementPayload::default());
+        }
+
+        #[test]
+        fn test_rdimm_ddr5_bus_element_1240_compatibility() {
+            let id = EntryId::Memory(MemoryEntryId::PsRdimmDdr5Bus);
+            let element = RdimmDdr5BusElement1240::new(2400, 1);
+            assert_eq!(element.header.target_memclk.get(), 2400);
+            assert_eq!(
+                element.payload_ext,
+                RdimmDdr5BusElementPayloadExt1104::default()
+            );
+            let blob = element.as_bytes();
+            assert!(RdimmDdr5BusElement1240::is_entry_compatible(id, blob));
+            assert!(!RdimmDdr5BusElement::is_entry_compatible(id, blob));
+            assert_eq!(
+                RdimmDdr5BusElement1240::skip_step(id, blob),
+                Some((0, 1240))
+            );
+            let old = [RdimmDdr5BusElement::new(2400, 1); 2];
+            assert!(!RdimmDdr5BusElement1240::is_entry_compatible(
+                id,
+                old.as_bytes()
+            ));
+            assert_eq!(
+                RdimmDdr5BusElement::skip_step(id, old.as_bytes()),
+                Some((0, 136))
+            );
+            // 17 * 1240 == 155 * 136
+            let turin = [RdimmDdr5BusElement1240::new(2400, 1); 17];
+            assert!(RdimmDdr5BusElement1240::is_entry_compatible(
+                id,
+                turin.as_bytes()
+            ));
+            assert!(!RdimmDdr5BusElement::is_entry_compatible(
+                id,
+                turin.as_bytes()
+            ));
+            let old = [RdimmDdr5BusElement::new(2400, 1); 155];
+            assert!(!RdimmDdr5BusElement1240::is_entry_compatible(
+                id,
+                old.as_bytes()
+            ));
+            assert!(RdimmDdr5BusElement::is_entry_compatible(
+                id,
+                old.as_bytes()
+            ));
+        }
+
+        #[test]
+        fn test_ddr_dq_pin_map_pin_for() {
+            let mut element = DdrDqPinMapElement::default();
+            assert_eq!(element.pin_for(2, 5).unwrap(), 21);
+            element.set_pin_for(2, 5, 20).unwrap();
+            element.set_pin_for(2, 4, 21).unwrap();
+            assert_eq!(element.pin_for(2, 5).unwrap(), 20);
+            assert_eq!(element.pin_for(2, 4).unwrap(), 21);
+            assert_eq!(element.pin_for(3, 5).unwrap(), 29);
+            assert!(matches!(element.pin_for(8, 0), Err(Error::EntryRange)));
+            assert!(matches!(element.pin_for(0, 8), Err(Error::EntryRange)));
+            assert!(matches!(
+                element.set_pin_for(0, 8, 0),
+                Err(Error::EntryRange)
+            ));
+        }
+
+        #[test]
+        fn test_pmu_bist_vendor_algorithms() {
+            let mut element = PmuBistVendorAlgorithmElement::default();
+            assert!(element.algorithms().next().is_none());
+            element.set_algorithms(&[0, 2, 5]).unwrap();
+            assert_eq!(element.algorithm_bit_mask().unwrap(), 0b10_0101);
+            let mut algorithms = element.algorithms();
+            assert_eq!(algorithms.next(), Some(0));
+            assert_eq!(algorithms.next(), Some(2));
+            assert_eq!(algorithms.next(), Some(5));
+            assert_eq!(algorithms.next(), None);
+            assert!(matches!(
+                element.set_algorithms(&[16]),
+                Err(Error::EntryRange)
+            ));
+        }
+
+        #[test]
+        fn test_cad_bus_command_timing() {
+            let mut rdimm = RdimmDdr4CadBusElement::default();
+            assert_eq!(rdimm.command_timing().unwrap(), CommandTimingMode::T1);
+            rdimm.set_command_timing(CommandTimingMode::T2);
+            assert!(rdimm.slow_mode().unwrap());
+            assert_eq!(rdimm.command_timing().unwrap(), CommandTimingMode::T2);
+            rdimm.set_slow_mode(false);
+            assert_eq!(rdimm.command_timing().unwrap(), CommandTimingMode::T1);
+
+            let mut udimm = UdimmDdr4CadBusElement::default();
+            assert_eq!(udimm.command_timing().unwrap(), CommandTimingMode::T1);
+            udimm.set_command_timing(CommandTimingMode::T2);
+            assert_eq!(udimm.slow_mode().unwrap(), 1);
+            udimm.set_slow_mode(2);
+            assert!(udimm.command_timing().is_err());
+
+            let mut lrdimm = LrdimmDdr4CadBusElement::default();
+            lrdimm.set_slow_mode(1);
+            assert_eq!(lrdimm.command_timing().unwrap(), CommandTimingMode::T2);
+            lrdimm.set_command_timing(CommandTimingMode::T1);
+            assert_eq!(lrdimm.slow_mode().unwrap(), 0);
+        }
+
+        #[test]
+        fn test_ddr5_raw_card_config_header_bitmaps() {
+            let mut header = Ddr5RawCardConfigElementHeader32 {
+                dimm_type: 0b101,
+                dev_width: 0b0110,
+                ..Default::default()
+            };
+            let mut types = header.dimm_types();
+            assert_eq!(types.next(), Some(Ddr5DimmType::Rdimm));
+            assert_eq!(types.next(), Some(Ddr5DimmType::ThreeDs));
+            assert_eq!(types.next(), None);
+            let mut widths = header.dev_widths();
+            assert_eq!(widths.next(), Some(8));
+            assert_eq!(widths.next(), Some(16));
+            assert_eq!(widths.next(), None);
+
+            let built = Ddr5RawCardConfigElementHeader32::builder()
+                .with_dimm_types(&[Ddr5DimmType::Lrdimm])
+                .with_dev_widths(&[4, 32])
+                .unwrap()
+                .build();
+            assert_eq!(built.dimm_type, 0b10);
+            assert_eq!(built.dev_width, 0b1001);
+            assert!(matches!(
+                header.set_dev_widths(&[12]),
+                Err(Error::EntryRange)
+            ));
+        }
+
+        #[test]
+        fn test_ddr_speed_unsupported() {
+            assert!(DdrSpeed::UnsupportedRome.is_unsupported());
+            assert!(DdrSpeed::UnsupportedMilan.is_unsupported());
+            assert!(!DdrSpeed::Ddr3200.is_unsupported());
+            assert_eq!(
+                DdrSpeed::unsupported(crate::types::SiliconGeneration::Rome),
+                DdrSpeed::UnsupportedRome
+            );
+            assert_eq!(
+                DdrSpeed::unsupported(crate::types::SiliconGeneration::Milan),
+                DdrSpeed::UnsupportedMilan
+            );
+            assert_eq!(
+                DdrSpeed::unsupported(crate::types::SiliconGeneration::Turin),
+                DdrSpeed::UnsupportedMilan
+            );
+
+            let mut element = MaxFreqElement::new(
+                DdrSpeed::UnsupportedMilan,
+                DimmsPerChannel::Specific(
+                    DimmsPerChannelSelector::new().with_one_dimm(true),
+                ),
+                1,
+                1,
+                0,
+                0,
+                DdrSpeed::Ddr3200,
+            );
+            assert_eq!(element.speed().unwrap(), Some(DdrSpeed::Ddr3200));
+            element.set_speed(DdrSpeed::UnsupportedRome);
+            assert_eq!(element.speed().unwrap(), None);
+            element.set_speed(DdrSpeed::UnsupportedMilan);
+            assert_eq!(element.speed().unwrap(), None);
+        }
+
+        #[test]
+        fn test_max_freq_element_voltage_speeds() {
+            // DDR5: Only the first speed is meaningful.
+            let mut element = MaxFreqElement::new(
+                DdrSpeed::UnsupportedMilan,
+                DimmsPerChannel::Specific(
+                    DimmsPerChannelSelector::new().with_one_dimm(true),
+                ),
+                1,
+                1,
+                0,
+                0,
+                DdrSpeed::Ddr4800,
+            );
+            assert_eq!(element.speed().unwrap(), Some(DdrSpeed::Ddr4800));
+            assert_eq!(element.speed_1_35v().unwrap(), None);
+            assert_eq!(element.speed_1_25v().unwrap(), None);
+            assert_eq!(element.speeds[1].get(), 4401);
+            assert_eq!(element.speeds[2].get(), 4401);
+
+            element.set_speed_1_35v(DdrSpeed::Ddr2933);
+            element.set_speed_1_25v(DdrSpeed::Ddr2667);
+            assert_eq!(element.speed().unwrap(), Some(DdrSpeed::Ddr4800));
+            assert_eq!(element.speed_1_35v().unwrap(), Some(DdrSpeed::Ddr2933));
+            assert_eq!(element.speed_1_25v().unwrap(), Some(DdrSpeed::Ddr2667));
+
+            element.set_speed_1_25v(DdrSpeed::UnsupportedRome);
+            assert_eq!(element.speed_1_25v().unwrap(), None);
+            element.speeds[2].set(1234);
+            assert!(matches!(
+                element.speed_1_25v(),
+                Err(Error::EntryTypeMismatch)
+            ));
+        }
+
+        #[test]
+        fn test_max_freq_element_validate() {
+            let element = MaxFreqElement::new(
+                DdrSpeed::UnsupportedMilan,
+                DimmsPerChannel::Specific(
+                    DimmsPerChannelSelector::new().with_two_dimms(true),
+                ),
+                2,
+                2,
+                0,
+                0,
+                DdrSpeed::Ddr3200,
+            );
+            assert!(element.validate().is_ok());
+            let element = MaxFreqElement::new(
+                DdrSpeed::UnsupportedMilan,
+                DimmsPerChannel::Specific(
+                    DimmsPerChannelSelector::new().with_one_dimm(true),
+                ),
+                4,
+                4,
+                0,
+                0,
+                DdrSpeed::Ddr3200,
+            );
+            assert!(matches!(element.validate(), Err(Error::EntryRange)));
+        }
+
+        #[test]
+        fn test_console_port_kind() {
+            let mut control = AblConsoleOutControl::new();
+            assert_eq!(control.console_port_kind(), ConsolePortKind::Port80);
+            control
+                .set_console_port_kind(ConsolePortKind::Uart(0x3f8))
+                .unwrap();
+            assert_eq!(control.abl_console_port().unwrap(), 0x3f8);
+            assert_eq!(
+                control.console_port_kind(),
+                ConsolePortKind::Uart(0x3f8)
+            );
+            control.set_abl_console_port(0x1234);
+            assert_eq!(
+                control.console_port_kind(),
+                ConsolePortKind::Other(0x1234)
+            );
+        }
+
+        #[test]
+        fn test_console_port_kind_round_trip() {
+            let mut control = AblConsoleOutControl::new();
+            for port in
+                [0x80, 0x3f8, 0x2e8, 0xfedc_9000, 0xfedc_f000, 0, 0x1234]
+            {
+                let kind = ConsolePortKind::from_port(port);
+                assert_eq!(kind.port(), port);
+                control.set_console_port_kind(kind).unwrap();
+                assert_eq!(control.abl_console_port().unwrap(), port);
+                assert_eq!(control.console_port_kind(), kind);
+            }
+            control.set_abl_console_port(0x1234);
+            for kind in [
+                ConsolePortKind::Uart(0x80),
+                ConsolePortKind::Uart(0x1234),
+                ConsolePortKind::Other(0x80),
+                ConsolePortKind::Other(0x3f8),
+            ] {
+                assert!(matches!(
+                    control.set_console_port_kind(kind),
+                    Err(Error::EntryRange)
+                ));
+                assert_eq!(control.abl_console_port().unwrap(), 0x1234);
+            }
+        }
+
+        #[test]
+        fn test_memclk_disable_map() {
+            let map = MemClkDisableMap::builder()
+                .with_disabled_channels(&[3, 1])
+                .unwrap()
+                .build();
+            assert_eq!(map.channels, 0b1010);
+            let mut channels = map.disabled_channels();
+            assert_eq!(channels.next(), Some(1));
+            assert_eq!(channels.next(), Some(3));
+            assert_eq!(channels.next(), None);
+            assert!(matches!(
+                MemClkDisableMap::builder().with_disabled_channels(&[32]),
+                Err(Error::EntryRange)
+            ));
+
+            let mut memclk_map = platform_specific_override::MemclkMap::new(
+                platform_specific_override::SocketIds::ALL,
+                platform_specific_override::ChannelIds::Any,
+                [0xff; 8],
+            )
+            .unwrap();
+            assert_eq!(memclk_map.memclk_disable_map().channels, 0);
+            memclk_map.disable_channels(map).unwrap();
+            assert_eq!(memclk_map.memclk_disable_map(), map);
+        }
+
+        #[test]
+        fn test_console_log_flags() {
+            let mut control = AblConsoleOutControl::new();
+            assert_eq!(
+                control.log_flags().unwrap(),
+                ConsoleLogFlags::CONSOLE
+                    | ConsoleLogFlags::MEM_FLOW
+                    | ConsoleLogFlags::MEM_SETREG
+            );
+            let mut flags =
+                ConsoleLogFlags::from_name("enable_console_logging").unwrap()
+                    | ConsoleLogFlags::from_name("enable_mem_pmu_logging")
+                        .unwrap();
+            assert!(ConsoleLogFlags::from_name("enable_everything").is_none());
+            flags.insert(ConsoleLogFlags::MEM_STATUS);
+            control.set_log_flags(flags);
+            assert!(control.enable_console_logging().unwrap());
+            assert!(!control.enable_mem_flow_logging().unwrap());
+            assert!(control.enable_mem_status_logging().unwrap());
+            assert!(control.enable_mem_pmu_logging().unwrap());
+            let flags = ConsoleLogFlags::from_abl(&control).unwrap();
+            let mut names = flags.names();
+            assert_eq!(names.next(), Some("enable_console_logging"));
+            assert_eq!(names.next(), Some("enable_mem_status_logging"));
+            assert_eq!(names.next(), Some("enable_mem_pmu_logging"));
+            assert_eq!(names.next(), None);
+
+            control.with_log_flags(ConsoleLogFlags::ALL_MEM);
+            assert!(!control.enable_console_logging().unwrap());
+            assert_eq!(control.log_flags().unwrap().names().count(), 9);
+        }
+
+        #[test]
+        fn test_ddr_rates_capped_at() {
+            let rates = DdrRates::capped_at(DdrSpeed::Ddr3200);
+            assert_eq!(u32::from(rates), 0b1_0101_0101_0101_0101_0111_1000);
+            assert!(rates.ddr3200());
+            assert!(rates.validate_max(DdrSpeed::Ddr3200).is_ok());
+
+            let rates = DdrRates::capped_at(DdrSpeed::Ddr2400);
+            assert!(rates.ddr2400());
+            assert!(!rates.ddr2667());
+            assert!(!rates.ddr2933());
+            assert!(!rates.ddr3200());
+            assert_eq!(u32::from(rates) >> 19, 0);
+            assert_eq!(rates.enabled_rates().last(), Some(DdrSpeed::Ddr2400));
+            assert!(rates.validate_max(DdrSpeed::Ddr2400).is_ok());
+            assert!(matches!(
+                rates.validate_max(DdrSpeed::Ddr2133),
+                Err(Error::EntryRange)
+            ));
+        }
+
+        #[test]
+        fn test_ddr_rates_capped_at_unsupported() {
+            for max in [DdrSpeed::UnsupportedRome, DdrSpeed::UnsupportedMilan] {
+                let rates = DdrRates::capped_at(max);
+                assert_eq!(u32::from(rates), 0);
+                assert!(rates.validate_max(max).is_ok());
+                assert!(matches!(
+                    DdrRates::capped_at(DdrSpeed::Ddr400).validate_max(max),
+                    Err(Error::EntryRange)
+                ));
+            }
+        }
+
+        #[test]
+        fn test_convert_error_out_control() {
+            let mut beep_code_table =
+                ErrorOutControl116::new().beep_code_table().unwrap();
+            beep_code_table[0].set_peak_map(0x42);
+            let mut src = ErrorOutControl116::new();
+            src.with_enable_error_reporting(true)
+                .with_input_port(0x84)
+                .with_output_port(0x3f8)
+                .with_output_port_type(PortType::FchMmio)
+                .with_error_reporting_gpio(Some(Gpio::new(42, 1, 2)))
+                .with_beep_code_table(beep_code_table)
+                .with_power_good_gpio(Some(Gpio::new(7, 0, 1)));
+            let dst = convert_error_out_control(&src);
+            assert!(dst.enable_error_reporting().unwrap());
+            assert_eq!(dst.input_port().unwrap(), 0x84);
+            assert_eq!(dst.output_port().unwrap(), 0x3f8);
+            assert_eq!(dst.output_port_type().unwrap(), PortType::FchMmio);
+            assert_eq!(
+                dst.error_reporting_gpio().unwrap(),
+                Some(Gpio::new(42, 1, 2))
+            );
+            assert_eq!(dst.beep_code_table().unwrap(), beep_code_table);
+            assert_eq!(
+                dst.power_good_gpio().unwrap(),
+                Some(Gpio::new(7, 0, 1))
+            );
+            assert_eq!(convert_error_out_control_to_116(&dst), src);
+        }
+
+        #[test]
+        fn test_ddr5_raw_card_vref_percent() {
+            assert_eq!(Ddr5RawCardVref::_50_0P.percent(), 50.0);
+            assert_eq!(Ddr5RawCardVref::_35_0P.percent(), 35.0);
+            assert_eq!(Ddr5RawCardVref::_97_5P.percent(), 97.5);
+            assert_eq!(
+                Ddr5RawCardVref::nearest_percent(49.8),
+                Ddr5RawCardVref::_50_0P
+            );
+            assert_eq!(
+                Ddr5RawCardVref::nearest_percent(50.3),
+                Ddr5RawCardVref::_50_5P
+            );
+            assert_eq!(
+                Ddr5RawCardVref::nearest_percent(10.0),
+                Ddr5RawCardVref::_35_0P
+            );
+            assert_eq!(
+                Ddr5RawCardVref::nearest_percent(100.0),
+                Ddr5RawCardVref::_97_5P
+            );
+        }
+
+        #[test]
+        fn test_ext_voltage_control_builder() {
+            let input = PortSpec {
+                port_type: PortType::FchHtIo,
+                port: 0x84,
+                size: PortSize::_8Bit,
+            };
+            let output = PortSpec {
+                port_type: PortType::PcieMmio,
+                port: 0xfed8_0000,
+                size: PortSize::_32Bit,
+            };
+            let built = ExtVoltageControl::builder()
+                .with_enabled(true)
+                .with_input_port_spec(input)
+                .with_output_port_spec(output)
+                .with_clear_acknowledgement(true)
+                .build();
+            let positional = ExtVoltageControl::new_enabled(
+                PortType::FchHtIo,
+                0x84,
+                PortSize::_8Bit,
+                PortType::PcieMmio,
+                0xfed8_0000,
+                PortSize::_32Bit,
+                true,
+            );
+            assert_eq!(built.as_bytes(), positional.as_bytes());
+        }
+
+        #[test]
+        fn test_element_try_from_bytes() {
+            let element = DimmInfoSmbusElement::new_slot(
+                1,
+                2,
+                0,
+                0xa0,
+                Some(0x94),
+                None,
+                Some(3),
+            )
+            .unwrap();
+            let buf = element.as_bytes();
+            let decoded = DimmInfoSmbusElement::try_from(buf).unwrap();
+            assert_eq!(decoded.socket_id, 1);
+            assert_eq!(decoded.channel_id, 2);
+            assert_eq!(decoded.i2c_mux_address(), Some(0x94));
+            assert_eq!(decoded.mux_control_address(), None);
+            assert_eq!(decoded.mux_channel(), Some(3));
+            assert!(matches!(
+                DimmInfoSmbusElement::try_from(&buf[1..]),
+                Err(Error::EntryTypeMismatch)
+            ));
+        }
+
         #[test]
         fn test_platform_specific_overrides() {
             use platform_specific_override::{
@@ -7751,19 +9717,48 @@ pub mod fch {
         }
     }
 
+    impl EspiSioInitElement {
+        pub fn new(
+            io_port: u16,
+            access_width: EspiSioAccessWidth,
+            data_mask: u32,
+            data_or: u32,
+        ) -> Result<Self> {
+            Ok(Self {
+                io_port: io_port.into(),
+                access_width: access_width
+                    .to_u16()
+                    .ok_or(Error::EntryTypeMismatch)?
+                    .into(),
+                data_mask: data_mask.into(),
+                data_or: data_or.into(),
+            })
+        }
+    }
+
     impl EntryCompatible for EspiSioInitElement {
         fn is_entry_compatible(entry_id: EntryId, _prefix: &[u8]) -> bool {
             matches!(entry_id, EntryId::Fch(FchEntryId::EspiSioInit))
         }
+        fn skip_step(entry_id: EntryId, prefix: &[u8]) -> Option<(u16, usize)> {
+            if Self::is_entry_compatible(entry_id, prefix) {
+                Some((0, size_of::<Self>()))
+            } else {
+                None
+            }
+        }
     }
 
     impl HeaderWithTail for EspiSioInitElement {
         type TailArrayItemType<'de> = ();
     }
 
+    impl_try_from_bytes!(EspiSioInitElement);
+
     #[cfg(test)]
     mod tests {
         use super::*;
+        use static_assertions::const_assert;
         #[test]
         fn test_struct_sizes() {
             assert!(offset_of!(EspiInit, espi_enabled) == 0);
@@ -7789,7 +9784,15 @@ pub mod fch {
             assert!(offset_of!(EspiInit, cpu_temp_mmio_base) == 100);
             assert!(offset_of!(EspiInit, rtc_time_mmio_base) == 104);
             assert!(offset_of!(EspiInit, bus_master_enabled) == 108);
-            assert!(size_of::<EspiInit>() == 112); // 109
+            const_assert!(size_of::<EspiInit>() == 112); // 109
+        }
+        #[test]
+        fn test_espi_sio_init_element_layout() {
+            const_assert!(size_of::<EspiSioInitElement>() == 12);
+            assert!(offset_of!(EspiSioInitElement, io_port) == 0);
+            assert!(offset_of!(EspiSioInitElement, access_width) == 2);
+            assert!(offset_of!(EspiSioInitElement, data_mask) == 4);
+            assert!(offset_of!(EspiSioInitElement, data_or) == 8);
         }
     }
 }
@@ -7922,6 +9925,8 @@ pub mod psp {
         }
     }
 
+    impl_try_from_bytes!(IdApcbMapping, IdRevApcbMapping);
+
     make_accessors! {
         #[derive(FromBytes, AsBytes, Unaligned, PartialEq, Debug, Copy, Clone)]
         #[repr(C, packed)]
@@ -8146,33 +10151,173 @@ pub mod psp {
     }
 }
 
+/// Provisional: The encodings 10 to 14 (230400, 460800, 576000, 921600 and
+/// 1500000 Baud, which only Turin supports) are not confirmed by an
+/// authoritative source yet.
+///
+/// Unknown never holds the value of a named variant: FromPrimitive maps
+/// those to the named variant, deserialization rejects them, and
+/// comparisons are done by value.
 #[allow(non_camel_case_types, non_snake_case)]
-#[derive(Debug, PartialEq, FromPrimitive, ToPrimitive, Copy, Clone)]
+#[derive(Debug, Copy, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum BaudRate {
     #[cfg_attr(feature = "serde", serde(rename = "2400 Baud"))]
-    _2400Baud = 0,
+    _2400Baud,
     #[cfg_attr(feature = "serde", serde(rename = "3600 Baud"))]
-    _3600Baud = 1,
+    _3600Baud,
     #[cfg_attr(feature = "serde", serde(rename = "4800 Baud"))]
-    _4800Baud = 2,
+    _4800Baud,
     #[cfg_attr(feature = "serde", serde(rename = "7200 Baud"))]
-    _7200Baud = 3,
+    _7200Baud,
     #[cfg_attr(feature = "serde", serde(rename = "9600 Baud"))]
-    _9600Baud = 4,
+    _9600Baud,
     #[cfg_attr(feature = "serde", serde(rename = "19200 Baud"))]
-    _19200Baud = 5,
+    _19200Baud,
     #[cfg_attr(feature = "serde", serde(rename = "38400 Baud"))]
-    _38400Baud = 6,
+    _38400Baud,
     #[cfg_attr(feature = "serde", serde(rename = "57600 Baud"))]
-    _57600Baud = 7,
+    _57600Baud,
     #[cfg_attr(feature = "serde", serde(rename = "115200 Baud"))]
-    _115200Baud = 8,
-    /// Note: This variant needs a custom AgesaBootloader.
+    _115200Baud,
     #[cfg_attr(feature = "serde", serde(rename = "3000000 Baud"))]
-    _3000000Baud = 9,
+    _3000000Baud,
+    #[cfg_attr(feature = "serde", serde(rename = "230400 Baud"))]
+    _230400Baud,
+    #[cfg_attr(feature = "serde", serde(rename = "460800 Baud"))]
+    _460800Baud,
+    #[cfg_attr(feature = "serde", serde(rename = "576000 Baud"))]
+    _576000Baud,
+    #[cfg_attr(feature = "serde", serde(rename = "921600 Baud"))]
+    _921600Baud,
+    #[cfg_attr(feature = "serde", serde(rename = "1500000 Baud"))]
+    _1500000Baud,
+    Unknown(
+        #[cfg_attr(
+            feature = "serde",
+            serde(deserialize_with = "BaudRate::deserialize_unknown")
+        )]
+        u32,
+    ),
+}
+
+impl ToPrimitive for BaudRate {
+    fn to_i64(&self) -> Option<i64> {
+        Some(match self {
+            Self::_2400Baud => 0,
+            Self::_3600Baud => 1,
+            Self::_4800Baud => 2,
+            Self::_7200Baud => 3,
+            Self::_9600Baud => 4,
+            Self::_19200Baud => 5,
+            Self::_38400Baud => 6,
+            Self::_57600Baud => 7,
+            Self::_115200Baud => 8,
+            Self::_3000000Baud => 9,
+            Self::_230400Baud => 10,
+            Self::_460800Baud => 11,
+            Self::_576000Baud => 12,
+            Self::_921600Baud => 13,
+            Self::_1500000Baud => 14,
+            Self::Unknown(x) => (*x).into(),
+        })
+    }
+    fn to_u64(&self) -> Option<u64> {
+        Some(self.to_i64()? as u64)
+    }
+}
+
+impl FromPrimitive for BaudRate {
+    fn from_u64(value: u64) -> Option<Self> {
+        Some(match value {
+            0 => Self::_2400Baud,
+            1 => Self::_3600Baud,
+            2 => Self::_4800Baud,
+            3 => Self::_7200Baud,
+            4 => Self::_9600Baud,
+            5 => Self::_19200Baud,
+            6 => Self::_38400Baud,
+            7 => Self::_57600Baud,
+            8 => Self::_115200Baud,
+            9 => Self::_3000000Baud,
+            10 => Self::_230400Baud,
+            11 => Self::_460800Baud,
+            12 => Self::_576000Baud,
+            13 => Self::_921600Baud,
+            14 => Self::_1500000Baud,
+            x => Self::Unknown(x.try_into().ok()?),
+        })
+    }
+    fn from_i64(value: i64) -> Option<Self> {
+        if value >= 0 {
+            let value: u64 = value.try_into().ok()?;
+            Self::from_u64(value)
+        } else {
+            None
+        }
+    }
+}
+
+/// Compares by value, so Unknown(8) (which cannot be constructed by
+/// FromPrimitive or by deserialization anyway) equals _115200Baud.
+impl PartialEq for BaudRate {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_i64() == other.to_i64()
+    }
+}
+
+impl Eq for BaudRate {}
+
+impl BaudRate {
+    const BPS: [(BaudRate, u32); 15] = [
+        (Self::_2400Baud, 2400),
+        (Self::_3600Baud, 3600),
+        (Self::_4800Baud, 4800),
+        (Self::_7200Baud, 7200),
+        (Self::_9600Baud, 9600),
+        (Self::_19200Baud, 19200),
+        (Self::_38400Baud, 38400),
+        (Self::_57600Baud, 57600),
+        (Self::_115200Baud, 115200),
+        (Self::_3000000Baud, 3000000),
+        (Self::_230400Baud, 230400),
+        (Self::_460800Baud, 460800),
+        (Self::_576000Baud, 576000),
+        (Self::_921600Baud, 921600),
+        (Self::_1500000Baud, 1500000),
+    ];
+    /// Returns the BaudRate with the given bit rate (in bit/s), if any.
+    pub fn from_bps(bps: u32) -> Option<Self> {
+        Self::BPS
+            .into_iter()
+            .find(|&(_, x)| x == bps)
+            .map(|(baud_rate, _)| baud_rate)
+    }
+    /// Returns the bit rate in bit/s, or None for Unknown.
+    pub fn bps(&self) -> Option<u32> {
+        Self::BPS
+            .into_iter()
+            .find(|&(baud_rate, _)| baud_rate == *self)
+            .map(|(_, bps)| bps)
+    }
+    #[cfg(feature = "serde")]
+    fn deserialize_unknown<'de, D>(
+        deserializer: D,
+    ) -> core::result::Result<u32, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = <u32 as Deserialize>::deserialize(deserializer)?;
+        match Self::from_u32(value) {
+            Some(Self::Unknown(_)) => Ok(value),
+            _ => Err(serde::de::Error::custom(format!(
+                "BaudRate value {} is not Unknown",
+                value
+            ))),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, FromPrimitive, ToPrimitive, Copy, Clone)]
@@ -11320,6 +13465,88 @@ mod tests {
     use super::*;
     use static_assertions::const_assert;
 
+    /// Returns whether T::default() consists of the bytes EXPECTED, and
+    /// whether EXPECTED parses back into T::default().
+    fn roundtrip_default<T: Default + FromBytes + AsBytes + PartialEq>(
+        expected: &[u8],
+    ) -> bool {
+        let value = T::default();
+        value.as_bytes() == expected && T::read_from(expected) == Some(value)
+    }
+
+    #[test]
+    fn test_roundtrip_default() {
+        use fch::EspiSioInitElement;
+        use memory::{
+            AblConsoleOutControl, ConsoleOutControl, Ddr4OdtPatElement,
+            DdrDqPinMapElement, DimmInfoSmbusElement, ExtVoltageControl, Gpio,
+            LrMaxFreqElement, MaxFreqElement,
+        };
+        use psp::{
+            BoardIdGettingMethodCustom, BoardIdGettingMethodEeprom,
+            BoardIdGettingMethodGpio, BoardIdGettingMethodSmbus,
+        };
+
+        assert!(roundtrip_default::<EspiSioInitElement>(&[0; 12]));
+        let abl_console_out_control = [
+            1, 1, 1, 0, 0, 0, 0, 0, 0, 0, // logging
+            0, 0, // reserved
+            0x80, 0, 0, 0, // abl_console_port
+        ];
+        assert!(roundtrip_default::<AblConsoleOutControl>(
+            &abl_console_out_control
+        ));
+        let mut console_out_control = [0u8; 20];
+        console_out_control[..16].copy_from_slice(&abl_console_out_control);
+        console_out_control[16..18].copy_from_slice(&[1, 1]); // breakpoints
+        assert!(roundtrip_default::<ConsoleOutControl>(&console_out_control));
+        assert!(roundtrip_default::<Ddr4OdtPatElement>(&[
+            0x21, 0, 0, 0, // dimm_rank_bitmaps
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, // patterns
+        ]));
+        let mut pins = [0u8; 64];
+        for (i, pin) in pins.iter_mut().enumerate() {
+            *pin = (i % 32) as u8;
+        }
+        assert!(roundtrip_default::<DdrDqPinMapElement>(&pins));
+        assert!(roundtrip_default::<DimmInfoSmbusElement>(&[0; 8]));
+        assert!(roundtrip_default::<ExtVoltageControl>(&[
+            0, 0, 0, 0, // enabled
+            0x84, 0, 0, 0, // input_port
+            0x80, 0, 0, 0, // output_port
+            4, 0, 0, 0, // input_port_size: 32 Bit
+            4, 0, 0, 0, // output_port_size: 32 Bit
+            6, 0, 0, 0, // input_port_type: FchHtIo
+            6, 0, 0, 0, // output_port_type: FchHtIo
+            0, 0, 0, 0, // clear_acknowledgement
+        ]));
+        assert!(roundtrip_default::<Gpio>(&[0, 0, 0]));
+        assert!(roundtrip_default::<LrMaxFreqElement>(&[
+            1, 0, // dimm_slots_per_channel
+            1, 0, 0, 0, 1, 0, 0, 0, // conditions
+            0x40, 0x06, 0x31, 0x11, 0x31,
+            0x11, // speeds: 1600, 4401, 4401
+        ]));
+        assert!(roundtrip_default::<MaxFreqElement>(&[
+            1, 0, // dimm_slots_per_channel
+            1, 0, 1, 0, 0, 0, 0, 0, // conditions
+            0x40, 0x06, 0x31, 0x11, 0x31,
+            0x11, // speeds: 1600, 4401, 4401
+        ]));
+        assert!(roundtrip_default::<BoardIdGettingMethodCustom>(&[
+            0xf, 0, 0, 0
+        ]));
+        assert!(roundtrip_default::<BoardIdGettingMethodEeprom>(&[
+            2, 0, 0, 0, 0, 0, 0, 0, 0, 0
+        ]));
+        assert!(roundtrip_default::<BoardIdGettingMethodGpio>(&[
+            3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0
+        ]));
+        assert!(roundtrip_default::<BoardIdGettingMethodSmbus>(&[
+            1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0
+        ]));
+    }
+
     #[test]
     fn test_struct_sizes() {
         const_assert!(size_of::<V2_HEADER>() == 32);
@@ -11338,4 +13565,68 @@ mod tests {
         const_assert!(size_of::<FourCC>() == 4);
         assert!(FourCC(*b"APCB").0 == [0x41, 0x50, 0x43, 0x42]);
     }
+
+    #[test]
+    fn test_entry_header_finalize() {
+        let mut header = ENTRY_HEADER::default();
+        header.finalize(48).unwrap();
+        assert_eq!(
+            usize::from(header.entry_size.get()),
+            size_of::<ENTRY_HEADER>() + 48
+        );
+        assert!(header.finalize(usize::from(u16::MAX)).is_err());
+        assert_eq!(
+            usize::from(header.entry_size.get()),
+            size_of::<ENTRY_HEADER>() + 48
+        );
+    }
+
+    #[test]
+    fn test_entry_id_u32_roundtrip() {
+        let entry_ids = [
+            EntryId::Psp(PspEntryId::BoardIdGettingMethod),
+            EntryId::Memory(MemoryEntryId::ConsoleOutControl),
+            EntryId::Df(DfEntryId::SlinkConfig),
+            EntryId::Token(TokenEntryId::Dword),
+            EntryId::Oem(OemEntryId::Unknown(0x42)),
+            EntryId::Unknown(0x1800, RawEntryId::Unknown(0x1234)),
+        ];
+        for entry_id in entry_ids {
+            let key = entry_id.as_u32();
+            assert_eq!(
+                key >> 16,
+                u32::from(entry_id.group_id().to_u16().unwrap())
+            );
+            assert_eq!(key & 0xffff, u32::from(entry_id.type_id()));
+            assert_eq!(EntryId::from_u32(key), entry_id);
+        }
+        assert_eq!(
+            EntryId::Memory(MemoryEntryId::ConsoleOutControl).as_u32(),
+            0x1704_0050
+        );
+    }
+
+    #[test]
+    fn test_baud_rate_bps() {
+        assert_eq!(BaudRate::from_bps(115200), Some(BaudRate::_115200Baud));
+        assert_eq!(BaudRate::_115200Baud.bps(), Some(115200));
+        assert_eq!(BaudRate::from_bps(3000000), Some(BaudRate::_3000000Baud));
+        assert_eq!(BaudRate::_3000000Baud.bps(), Some(3000000));
+        assert_eq!(BaudRate::from_bps(921600), Some(BaudRate::_921600Baud));
+        assert_eq!(BaudRate::_1500000Baud.bps(), Some(1500000));
+        assert_eq!(BaudRate::from_bps(115201), None);
+        for i in 0..15u8 {
+            let baud_rate = BaudRate::from_u8(i).unwrap();
+            assert_eq!(baud_rate.to_u8(), Some(i));
+            assert_eq!(
+                BaudRate::from_bps(baud_rate.bps().unwrap()),
+                Some(baud_rate)
+            );
+        }
+        assert_eq!(BaudRate::from_u32(15), Some(BaudRate::Unknown(15)));
+        assert_eq!(BaudRate::Unknown(15).to_u32(), Some(15));
+        assert_eq!(BaudRate::Unknown(15).bps(), None);
+        assert_eq!(BaudRate::Unknown(8), BaudRate::_115200Baud);
+        assert_ne!(BaudRate::Unknown(15), BaudRate::_115200Baud);
+    }
 }
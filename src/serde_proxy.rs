@@ -0,0 +1,622 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! This file contains a Deserializer wrapper that forwards everything to the
+//! wrapped Deserializer, but can (see Hooks):
+//!
+//! * record which struct fields were absent from the input--and so were
+//!   defaulted by serde (otherwise, deserialization would have failed).
+//!   Fields are recorded as "STRUCT_NAME.FIELD_NAME".
+//! * make enums which have an `Unknown(raw)` variant accept a bare raw value
+//!   (for example `0xE5`) instead of a variant name, and deserialize it as
+//!   `Unknown(raw)`.  That way, config files written by newer tools (which
+//!   know more variants) can still be read, provided those variants are
+//!   written as raw values.
+
+use core::cell::RefCell;
+use core::fmt;
+use serde::de::{
+    self, DeserializeSeed, Deserializer, EnumAccess, IntoDeserializer,
+    MapAccess, SeqAccess, VariantAccess, Visitor,
+};
+
+pub(crate) type Names = RefCell<Vec<String>>;
+
+/// Struct names whose fields are not actually struct fields but alternatives
+/// (and so are usually absent).
+const EXEMPT_STRUCTS: &[&str] = &["EntryItem"];
+
+/// What ProxyDeserializer does in addition to forwarding.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct Hooks<'a> {
+    /// If set, the names of defaulted struct fields are appended to this.
+    pub(crate) defaulted: Option<&'a Names>,
+    /// If set, enums with an `Unknown` variant also accept raw values.
+    pub(crate) unknown_passthrough: bool,
+}
+
+/// Deserializes T like Deserialize does, but accepts raw values for enums
+/// with an `Unknown` variant (deserializing them as that variant).
+pub fn deserialize_with_unknown_passthrough<'de, T, D>(
+    deserializer: D,
+) -> core::result::Result<T, D::Error>
+where
+    T: de::Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    T::deserialize(ProxyDeserializer::new(
+        deserializer,
+        Hooks { unknown_passthrough: true, ..Hooks::default() },
+    ))
+}
+
+pub(crate) struct ProxyDeserializer<'a, D> {
+    de: D,
+    hooks: Hooks<'a>,
+    /// If this is deserializing a map key, where to record the key.
+    key: Option<&'a Names>,
+}
+
+impl<'a, D> ProxyDeserializer<'a, D> {
+    pub(crate) fn new(de: D, hooks: Hooks<'a>) -> Self {
+        Self { de, hooks, key: None }
+    }
+}
+
+struct ProxyVisitor<'a, V> {
+    inner: V,
+    hooks: Hooks<'a>,
+    key: Option<&'a Names>,
+    /// (struct name, all field names) if this visits a struct whose
+    /// defaulted fields are to be recorded.
+    fields: Option<(&'static str, &'static [&'static str])>,
+}
+
+impl<'a, V> ProxyVisitor<'a, V> {
+    fn new(inner: V, hooks: Hooks<'a>, key: Option<&'a Names>) -> Self {
+        Self { inner, hooks, key, fields: None }
+    }
+    fn record_key(&self, value: &str) {
+        if let Some(key) = self.key {
+            key.borrow_mut().push(value.into());
+        }
+    }
+}
+
+struct ProxySeed<'a, S> {
+    inner: S,
+    hooks: Hooks<'a>,
+    key: Option<&'a Names>,
+}
+
+struct ProxySeqAccess<'a, A> {
+    inner: A,
+    hooks: Hooks<'a>,
+}
+
+struct ProxyMapAccess<'a, A> {
+    inner: A,
+    hooks: Hooks<'a>,
+    seen: &'a Names,
+}
+
+struct ProxyEnumAccess<'a, A> {
+    inner: A,
+    hooks: Hooks<'a>,
+}
+
+struct ProxyVariantAccess<'a, A> {
+    inner: A,
+    hooks: Hooks<'a>,
+}
+
+/// Visits an enum that has an `Unknown` variant.
+struct UnknownVisitor<V> {
+    inner: V,
+    variants: &'static [&'static str],
+}
+
+/// Enum access with the variant identifier already taken from A.
+struct KnownEnumAccess<'a, A> {
+    name: String,
+    variant: A,
+    hooks: Hooks<'a>,
+}
+
+/// Enum access for `Unknown(raw)`, with the actual identifier (which was
+/// the raw value) already taken from A.
+struct RawEnumAccess<A> {
+    raw: u64,
+    variant: A,
+}
+
+/// Variant identifier as it was in the input.
+enum Identifier {
+    Name(String),
+    Raw(u64),
+}
+
+struct IdentifierSeed;
+
+fn parse_raw(s: &str) -> Option<u64> {
+    match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => s.parse().ok(),
+    }
+}
+
+macro_rules! forward_deserialize {(
+    $($method:ident($($arg:ident: $ty:ty),*)),* $(,)?
+) => (
+    $(
+        fn $method<V: Visitor<'de>>(
+            self,
+            $($arg: $ty,)*
+            visitor: V,
+        ) -> core::result::Result<V::Value, Self::Error> {
+            self.de.$method(
+                $($arg,)*
+                ProxyVisitor::new(visitor, self.hooks, self.key),
+            )
+        }
+    )*
+)}
+
+impl<'a, 'de, D: Deserializer<'de>> Deserializer<'de>
+    for ProxyDeserializer<'a, D>
+{
+    type Error = D::Error;
+
+    forward_deserialize!(
+        deserialize_any(),
+        deserialize_bool(),
+        deserialize_i8(),
+        deserialize_i16(),
+        deserialize_i32(),
+        deserialize_i64(),
+        deserialize_i128(),
+        deserialize_u8(),
+        deserialize_u16(),
+        deserialize_u32(),
+        deserialize_u64(),
+        deserialize_u128(),
+        deserialize_f32(),
+        deserialize_f64(),
+        deserialize_char(),
+        deserialize_str(),
+        deserialize_string(),
+        deserialize_bytes(),
+        deserialize_byte_buf(),
+        deserialize_option(),
+        deserialize_unit(),
+        deserialize_unit_struct(name: &'static str),
+        deserialize_newtype_struct(name: &'static str),
+        deserialize_seq(),
+        deserialize_tuple(len: usize),
+        deserialize_tuple_struct(name: &'static str, len: usize),
+        deserialize_map(),
+        deserialize_identifier(),
+        deserialize_ignored_any(),
+    );
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> core::result::Result<V::Value, Self::Error> {
+        let mut visitor = ProxyVisitor::new(visitor, self.hooks, self.key);
+        if self.hooks.defaulted.is_some() && !EXEMPT_STRUCTS.contains(&name) {
+            visitor.fields = Some((name, fields));
+        }
+        self.de.deserialize_struct(name, fields, visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> core::result::Result<V::Value, Self::Error> {
+        let visitor = ProxyVisitor::new(visitor, self.hooks, self.key);
+        if self.hooks.unknown_passthrough && variants.contains(&"Unknown") {
+            self.de.deserialize_enum(
+                name,
+                variants,
+                UnknownVisitor { inner: visitor, variants },
+            )
+        } else {
+            self.de.deserialize_enum(name, variants, visitor)
+        }
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.de.is_human_readable()
+    }
+}
+
+macro_rules! forward_visit {(
+    $($method:ident($ty:ty)),* $(,)?
+) => (
+    $(
+        fn $method<E: de::Error>(
+            self,
+            value: $ty,
+        ) -> core::result::Result<Self::Value, E> {
+            self.inner.$method(value)
+        }
+    )*
+)}
+
+impl<'a, 'de, V: Visitor<'de>> Visitor<'de> for ProxyVisitor<'a, V> {
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.expecting(formatter)
+    }
+
+    forward_visit!(
+        visit_bool(bool),
+        visit_i8(i8),
+        visit_i16(i16),
+        visit_i32(i32),
+        visit_i64(i64),
+        visit_i128(i128),
+        visit_u8(u8),
+        visit_u16(u16),
+        visit_u32(u32),
+        visit_u64(u64),
+        visit_u128(u128),
+        visit_f32(f32),
+        visit_f64(f64),
+        visit_char(char),
+        visit_bytes(&[u8]),
+        visit_borrowed_bytes(&'de [u8]),
+        visit_byte_buf(Vec<u8>),
+    );
+
+    fn visit_str<E: de::Error>(
+        self,
+        value: &str,
+    ) -> core::result::Result<Self::Value, E> {
+        self.record_key(value);
+        self.inner.visit_str(value)
+    }
+
+    fn visit_borrowed_str<E: de::Error>(
+        self,
+        value: &'de str,
+    ) -> core::result::Result<Self::Value, E> {
+        self.record_key(value);
+        self.inner.visit_borrowed_str(value)
+    }
+
+    fn visit_string<E: de::Error>(
+        self,
+        value: String,
+    ) -> core::result::Result<Self::Value, E> {
+        self.record_key(&value);
+        self.inner.visit_string(value)
+    }
+
+    fn visit_none<E: de::Error>(self) -> core::result::Result<Self::Value, E> {
+        self.inner.visit_none()
+    }
+
+    fn visit_unit<E: de::Error>(self) -> core::result::Result<Self::Value, E> {
+        self.inner.visit_unit()
+    }
+
+    fn visit_some<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> core::result::Result<Self::Value, D::Error> {
+        self.inner.visit_some(ProxyDeserializer::new(deserializer, self.hooks))
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> core::result::Result<Self::Value, D::Error> {
+        self.inner.visit_newtype_struct(ProxyDeserializer::new(
+            deserializer,
+            self.hooks,
+        ))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(
+        self,
+        seq: A,
+    ) -> core::result::Result<Self::Value, A::Error> {
+        self.inner.visit_seq(ProxySeqAccess { inner: seq, hooks: self.hooks })
+    }
+
+    fn visit_map<A: MapAccess<'de>>(
+        self,
+        map: A,
+    ) -> core::result::Result<Self::Value, A::Error> {
+        let seen = Names::default();
+        let value = self.inner.visit_map(ProxyMapAccess {
+            inner: map,
+            hooks: self.hooks,
+            seen: &seen,
+        })?;
+        if let (Some((name, fields)), Some(defaulted)) =
+            (self.fields, self.hooks.defaulted)
+        {
+            let seen = seen.into_inner();
+            let mut defaulted = defaulted.borrow_mut();
+            for field in fields {
+                if !seen.iter().any(|key| key == field) {
+                    defaulted.push(format!("{name}.{field}"));
+                }
+            }
+        }
+        Ok(value)
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(
+        self,
+        data: A,
+    ) -> core::result::Result<Self::Value, A::Error> {
+        self.inner
+            .visit_enum(ProxyEnumAccess { inner: data, hooks: self.hooks })
+    }
+}
+
+impl<'a, 'de, V: Visitor<'de>> Visitor<'de>
+    for UnknownVisitor<ProxyVisitor<'a, V>>
+{
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.expecting(formatter)?;
+        formatter.write_str(" or a raw value")
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(
+        self,
+        data: A,
+    ) -> core::result::Result<Self::Value, A::Error> {
+        let hooks = self.inner.hooks;
+        let inner = self.inner.inner;
+        let (identifier, variant) = data.variant_seed(IdentifierSeed)?;
+        match identifier {
+            Identifier::Name(name) if self.variants.contains(&&*name) => {
+                inner.visit_enum(KnownEnumAccess { name, variant, hooks })
+            }
+            Identifier::Name(name) => match parse_raw(&name) {
+                Some(raw) => inner.visit_enum(RawEnumAccess { raw, variant }),
+                None => Err(de::Error::unknown_variant(&name, self.variants)),
+            },
+            Identifier::Raw(raw) => {
+                inner.visit_enum(RawEnumAccess { raw, variant })
+            }
+        }
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for IdentifierSeed {
+    type Value = Identifier;
+
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> core::result::Result<Self::Value, D::Error> {
+        deserializer.deserialize_identifier(self)
+    }
+}
+
+impl<'de> Visitor<'de> for IdentifierSeed {
+    type Value = Identifier;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("variant name or raw value")
+    }
+
+    fn visit_u64<E: de::Error>(
+        self,
+        value: u64,
+    ) -> core::result::Result<Self::Value, E> {
+        Ok(Identifier::Raw(value))
+    }
+
+    fn visit_str<E: de::Error>(
+        self,
+        value: &str,
+    ) -> core::result::Result<Self::Value, E> {
+        Ok(Identifier::Name(value.into()))
+    }
+}
+
+impl<'a, 'de, S: DeserializeSeed<'de>> DeserializeSeed<'de>
+    for ProxySeed<'a, S>
+{
+    type Value = S::Value;
+
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> core::result::Result<Self::Value, D::Error> {
+        self.inner.deserialize(ProxyDeserializer {
+            de: deserializer,
+            hooks: self.hooks,
+            key: self.key,
+        })
+    }
+}
+
+impl<'a, 'de, A: SeqAccess<'de>> SeqAccess<'de> for ProxySeqAccess<'a, A> {
+    type Error = A::Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> core::result::Result<Option<T::Value>, Self::Error> {
+        self.inner.next_element_seed(ProxySeed {
+            inner: seed,
+            hooks: self.hooks,
+            key: None,
+        })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, 'de, A: MapAccess<'de>> MapAccess<'de> for ProxyMapAccess<'a, A> {
+    type Error = A::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> core::result::Result<Option<K::Value>, Self::Error> {
+        self.inner.next_key_seed(ProxySeed {
+            inner: seed,
+            hooks: self.hooks,
+            key: Some(self.seen),
+        })
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> core::result::Result<V::Value, Self::Error> {
+        self.inner.next_value_seed(ProxySeed {
+            inner: seed,
+            hooks: self.hooks,
+            key: None,
+        })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, 'de, A: EnumAccess<'de>> EnumAccess<'de> for ProxyEnumAccess<'a, A> {
+    type Error = A::Error;
+    type Variant = ProxyVariantAccess<'a, A::Variant>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> core::result::Result<(V::Value, Self::Variant), Self::Error> {
+        let (value, variant) = self.inner.variant_seed(ProxySeed {
+            inner: seed,
+            hooks: self.hooks,
+            key: None,
+        })?;
+        Ok((value, ProxyVariantAccess { inner: variant, hooks: self.hooks }))
+    }
+}
+
+impl<'a, 'de, A: VariantAccess<'de>> EnumAccess<'de>
+    for KnownEnumAccess<'a, A>
+{
+    type Error = A::Error;
+    type Variant = ProxyVariantAccess<'a, A>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> core::result::Result<(V::Value, Self::Variant), Self::Error> {
+        let value = seed.deserialize(self.name.into_deserializer())?;
+        Ok((
+            value,
+            ProxyVariantAccess { inner: self.variant, hooks: self.hooks },
+        ))
+    }
+}
+
+impl<'de, A: VariantAccess<'de>> EnumAccess<'de> for RawEnumAccess<A> {
+    type Error = A::Error;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> core::result::Result<(V::Value, Self::Variant), Self::Error> {
+        let value = seed.deserialize("Unknown".into_deserializer())?;
+        Ok((value, self))
+    }
+}
+
+impl<'de, A: VariantAccess<'de>> VariantAccess<'de> for RawEnumAccess<A> {
+    type Error = A::Error;
+
+    fn unit_variant(self) -> core::result::Result<(), Self::Error> {
+        Err(de::Error::invalid_type(de::Unexpected::UnitVariant, &"raw value"))
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> core::result::Result<T::Value, Self::Error> {
+        // The raw value was the entire input for this enum.
+        self.variant.unit_variant()?;
+        seed.deserialize(self.raw.into_deserializer())
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        _visitor: V,
+    ) -> core::result::Result<V::Value, Self::Error> {
+        Err(de::Error::invalid_type(de::Unexpected::TupleVariant, &"raw value"))
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        _visitor: V,
+    ) -> core::result::Result<V::Value, Self::Error> {
+        Err(de::Error::invalid_type(
+            de::Unexpected::StructVariant,
+            &"raw value",
+        ))
+    }
+}
+
+impl<'a, 'de, A: VariantAccess<'de>> VariantAccess<'de>
+    for ProxyVariantAccess<'a, A>
+{
+    type Error = A::Error;
+
+    fn unit_variant(self) -> core::result::Result<(), Self::Error> {
+        self.inner.unit_variant()
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> core::result::Result<T::Value, Self::Error> {
+        self.inner.newtype_variant_seed(ProxySeed {
+            inner: seed,
+            hooks: self.hooks,
+            key: None,
+        })
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> core::result::Result<V::Value, Self::Error> {
+        self.inner
+            .tuple_variant(len, ProxyVisitor::new(visitor, self.hooks, None))
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> core::result::Result<V::Value, Self::Error> {
+        self.inner.struct_variant(
+            fields,
+            ProxyVisitor::new(visitor, self.hooks, None),
+        )
+    }
+}
@@ -858,6 +858,28 @@ impl_struct_serde_conversion!(
     SerdeRdimmDdr5BusElement,
     [header, payload,]
 );
+impl_struct_serde_conversion!(
+    RdimmDdr5BusElementPayloadExt1104,
+    SerdeRdimmDdr5BusElementPayloadExt1104,
+    [
+        _reserved_0,
+        _reserved_1,
+        _reserved_2,
+        _reserved_3,
+        _reserved_4,
+        _reserved_5,
+        _reserved_6,
+        _reserved_7,
+        _reserved_8,
+        _reserved_9,
+        _reserved_10,
+    ]
+);
+impl_struct_serde_conversion!(
+    RdimmDdr5BusElement1240,
+    SerdeRdimmDdr5BusElement1240,
+    [header, payload, payload_ext,]
+);
 impl_struct_serde_conversion!(
     EspiInit,
     SerdeEspiInit,
@@ -1012,6 +1034,11 @@ impl_struct_serde_conversion!(
         _reserved_2,
     ]
 );
+impl_struct_serde_conversion!(
+    XgmiPhyOverrideElement,
+    SerdeXgmiPhyOverrideElement,
+    [lane, parameter_id, _reserved_, value,]
+);
 impl_struct_serde_conversion!(
     EarlyPcieConfigElement,
     CustomSerdeEarlyPcieConfigElement,
@@ -8,15 +8,18 @@ mod tests {
         memory::ConsoleOutControl, memory::DimmInfoSmbusElement,
         memory::ExtVoltageControl, psp::BoardIdGettingMethodEeprom,
         psp::IdRevApcbMapping, psp::RevAndFeatureValue, BaudRate,
-        BoardInstances, CcxEntryId, ContextType, DfEntryId, EntryId, GroupId,
-        MemoryEntryId, PriorityLevels, PspEntryId, TokenEntryId,
+        BoardInstances, CcxEntryId, ContextFormat, ContextType, DfEntryId,
+        EntryId, GroupId, MemoryEntryId, OemEntryId, PriorityLevels,
+        PspEntryId, TokenEntryId, V2_HEADER, V3_HEADER_EXT,
     };
-    use crate::types::PriorityLevel;
+    use crate::types::{OrderedPriorityLevel, PriorityLevel};
     use crate::Apcb;
     use crate::ApcbIoOptions;
+    use crate::ApcbStats;
     use crate::EntryItemBody;
     use crate::{Error, FileSystemError};
     use core::default::Default;
+    use core::mem::size_of;
 
     #[test]
     #[should_panic]
@@ -172,6 +175,401 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn resume_group_iteration_with_cursor() -> Result<(), Error> {
+        let mut buffer: [u8; Apcb::MAX_SIZE] = [0xFF; Apcb::MAX_SIZE];
+        let mut apcb =
+            Apcb::create(&mut buffer[0..], 42, &ApcbIoOptions::default())
+                .unwrap();
+        apcb.insert_group(GroupId::Psp, *b"PSPG")?;
+        apcb.insert_group(GroupId::Df, *b"DFG ")?;
+        apcb.insert_group(GroupId::Memory, *b"MEMG")?;
+        apcb.insert_group(GroupId::Fch, *b"FCHG")?;
+        let mut cursor = apcb.group_cursor();
+        let group = cursor.next(&apcb).ok_or(Error::GroupNotFound)?;
+        assert!(group.id() == GroupId::Psp);
+        let group = cursor.next(&apcb).ok_or(Error::GroupNotFound)?;
+        assert!(group.id() == GroupId::Df);
+
+        // Pause; the cursor does not borrow the Apcb.
+        let saved_cursor = cursor;
+        assert!(saved_cursor.offset() > 0);
+        apcb.header_mut()?;
+
+        let mut cursor = saved_cursor;
+        let group = cursor.next(&apcb).ok_or(Error::GroupNotFound)?;
+        assert!(group.id() == GroupId::Memory);
+        let group = cursor.next(&apcb).ok_or(Error::GroupNotFound)?;
+        assert!(group.id() == GroupId::Fch);
+        assert!(cursor.next(&apcb).is_none());
+        assert!(cursor.next(&apcb).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn load_entry_with_foreign_group_id() -> Result<(), Error> {
+        let mut buffer: [u8; Apcb::MAX_SIZE] = [0xFF; Apcb::MAX_SIZE];
+        let mut apcb =
+            Apcb::create(&mut buffer[0..], 42, &ApcbIoOptions::default())
+                .unwrap();
+        apcb.insert_group(GroupId::Psp, *b"PSPG")?;
+        apcb.insert_entry(
+            EntryId::Psp(PspEntryId::BoardIdGettingMethod),
+            0,
+            BoardInstances::all(),
+            ContextType::Struct,
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &[1u8; 48],
+        )?;
+        apcb.save().unwrap();
+        // Make the entry claim to be in the Memory group (0x1704).
+        let entry_offset = 128 + 16;
+        buffer[entry_offset..entry_offset + 2]
+            .copy_from_slice(&0x1704u16.to_le_bytes());
+
+        match Apcb::load(
+            &mut buffer[0..],
+            &ApcbIoOptions::default().with_check_checksum(false).build(),
+        ) {
+            Err(Error::EntryGroupIdMismatch { entry, group }) => {
+                assert!(entry == 0x1704);
+                assert!(group == 0x1701);
+            }
+            _ => panic!("unexpected result"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn load_unsupported_version() -> Result<(), Error> {
+        let mut buffer: [u8; Apcb::MAX_SIZE] = [0xFF; Apcb::MAX_SIZE];
+        let apcb =
+            Apcb::create(&mut buffer[0..], 42, &ApcbIoOptions::default())
+                .unwrap();
+        apcb.save().unwrap();
+        // V2_HEADER::version
+        buffer[6..8].copy_from_slice(&0x40u16.to_le_bytes());
+
+        match Apcb::load(
+            &mut buffer[0..],
+            &ApcbIoOptions::default().with_check_checksum(false).build(),
+        ) {
+            Err(Error::UnsupportedVersion { found }) => {
+                assert!(found == 0x40);
+            }
+            _ => panic!("unexpected result"),
+        }
+
+        let apcb = Apcb::load(
+            &mut buffer[0..],
+            &ApcbIoOptions::default()
+                .with_check_checksum(false)
+                .with_allow_any_version(true)
+                .build(),
+        )?;
+        assert!(apcb.header()?.version.get() == 0x40);
+        Ok(())
+    }
+
+    #[test]
+    fn load_unexpected_group_version() -> Result<(), Error> {
+        let mut buffer: [u8; Apcb::MAX_SIZE] = [0xFF; Apcb::MAX_SIZE];
+        let mut apcb =
+            Apcb::create(&mut buffer[0..], 42, &ApcbIoOptions::default())
+                .unwrap();
+        apcb.insert_group(GroupId::Psp, *b"PSPG")?;
+        apcb.save().unwrap();
+        let apcb = Apcb::load(&mut buffer[0..], &ApcbIoOptions::default())?;
+        let group = apcb.group(GroupId::Psp)?.ok_or(Error::GroupNotFound)?;
+        assert!(group.version() == 1);
+        // GROUP_HEADER::version
+        let version_offset = 128 + 8;
+        buffer[version_offset..version_offset + 2]
+            .copy_from_slice(&0x02u16.to_le_bytes());
+
+        match Apcb::load(
+            &mut buffer[0..],
+            &ApcbIoOptions::default().with_check_checksum(false).build(),
+        ) {
+            Err(Error::GroupVersionMismatch { group, version }) => {
+                assert!(group == 0x1701);
+                assert!(version == 0x02);
+            }
+            _ => panic!("unexpected result"),
+        }
+
+        let apcb = Apcb::load(
+            &mut buffer[0..],
+            &ApcbIoOptions::default()
+                .with_check_checksum(false)
+                .with_allow_any_version(true)
+                .build(),
+        )?;
+        let group = apcb.group(GroupId::Psp)?.ok_or(Error::GroupNotFound)?;
+        assert!(group.version() == 0x02);
+        apcb.validate(None)?;
+        Ok(())
+    }
+
+    #[test]
+    fn load_entry_extending_past_group() -> Result<(), Error> {
+        let mut buffer: [u8; Apcb::MAX_SIZE] = [0xFF; Apcb::MAX_SIZE];
+        let mut apcb =
+            Apcb::create(&mut buffer[0..], 42, &ApcbIoOptions::default())
+                .unwrap();
+        apcb.insert_group(GroupId::Psp, *b"PSPG")?;
+        apcb.insert_entry(
+            EntryId::Psp(PspEntryId::BoardIdGettingMethod),
+            0,
+            BoardInstances::all(),
+            ContextType::Struct,
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &[1u8; 48],
+        )?;
+        apcb.insert_group(GroupId::Memory, *b"MEMG")?;
+        apcb.insert_entry(
+            EntryId::Memory(MemoryEntryId::PsUdimmDdr4OdtPat),
+            0,
+            BoardInstances::all(),
+            ContextType::Struct,
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &[2u8; 48],
+        )?;
+        apcb.save().unwrap();
+        // Make the Psp entry claim to extend into the Memory group.
+        let entry_size_offset = 128 + 16 + 4;
+        buffer[entry_size_offset..entry_size_offset + 2]
+            .copy_from_slice(&(16u16 + 48 + 32).to_le_bytes());
+
+        match Apcb::load(
+            &mut buffer[0..],
+            &ApcbIoOptions::default().with_check_checksum(false).build(),
+        ) {
+            Err(Error::EntryRange) => {}
+            _ => panic!("unexpected result"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn live_bytes() -> Result<(), Error> {
+        let mut buffer: [u8; Apcb::MAX_SIZE] = [0xFF; Apcb::MAX_SIZE];
+        let mut apcb =
+            Apcb::create(&mut buffer[0..], 42, &ApcbIoOptions::default())
+                .unwrap();
+        apcb.insert_group(GroupId::Psp, *b"PSPG")?;
+        let apcb_size = apcb.header()?.apcb_size.get() as usize;
+        let bytes = apcb.live_bytes()?;
+        assert!(bytes.len() == apcb_size);
+        assert!(bytes.len() < Apcb::MAX_SIZE);
+        assert!(bytes.starts_with(b"APCB"));
+        Ok(())
+    }
+
+    #[test]
+    fn insert_group_default_signature() -> Result<(), Error> {
+        assert!(GroupId::Memory.canonical_signature() == Some(*b"MEMG"));
+        assert!(GroupId::Df.canonical_signature() == Some(*b"DFG "));
+        assert!(GroupId::Unknown(0x1800).canonical_signature().is_none());
+        let mut buffer: [u8; Apcb::MAX_SIZE] = [0xFF; Apcb::MAX_SIZE];
+        let mut apcb =
+            Apcb::create(&mut buffer[0..], 42, &ApcbIoOptions::default())
+                .unwrap();
+        apcb.insert_group_default_signature(GroupId::Memory)?;
+        apcb.insert_group_default_signature(GroupId::Token)?;
+        assert!(matches!(
+            apcb.insert_group_default_signature(GroupId::Unknown(0x1800)),
+            Err(Error::GroupTypeMismatch)
+        ));
+        assert!(matches!(
+            apcb.insert_group(GroupId::Psp, *b"MEMG"),
+            Err(Error::GroupTypeMismatch)
+        ));
+        apcb.save().unwrap();
+        let apcb =
+            Apcb::load(&mut buffer[0..], &ApcbIoOptions::default()).unwrap();
+        let group = apcb.group(GroupId::Memory)?.ok_or(Error::GroupNotFound)?;
+        assert!(group.signature() == *b"MEMG");
+        let group = apcb.group(GroupId::Token)?.ok_or(Error::GroupNotFound)?;
+        assert!(group.signature() == *b"TOKN");
+        Ok(())
+    }
+
+    #[test]
+    fn insert_group_raw() -> Result<(), Error> {
+        let mut source_buffer: [u8; Apcb::MAX_SIZE] = [0xFF; Apcb::MAX_SIZE];
+        let mut source = Apcb::create(
+            &mut source_buffer[0..],
+            42,
+            &ApcbIoOptions::default(),
+        )
+        .unwrap();
+        source.insert_group(GroupId::Psp, *b"PSPG")?;
+        source.insert_entry(
+            EntryId::Psp(PspEntryId::BoardIdGettingMethod),
+            0,
+            BoardInstances::all(),
+            ContextType::Struct,
+            PriorityLevels::from_level(PriorityLevel::Low),
+            &[1u8; 48],
+        )?;
+        source.insert_entry(
+            EntryId::Psp(PspEntryId::Unknown(0x99)),
+            0,
+            BoardInstances::all(),
+            ContextType::Struct,
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &[2u8; 8],
+        )?;
+        let source_group =
+            source.group(GroupId::Psp)?.ok_or(Error::GroupNotFound)?;
+        let body = source_group.body_as_buf();
+        assert!(body.len() == (16 + 48) + (16 + 8));
+
+        let mut buffer: [u8; Apcb::MAX_SIZE] = [0xFF; Apcb::MAX_SIZE];
+        let mut apcb =
+            Apcb::create(&mut buffer[0..], 42, &ApcbIoOptions::default())
+                .unwrap();
+        apcb.insert_group(GroupId::Memory, *b"MEMG")?;
+        assert!(matches!(
+            apcb.insert_group_raw(*b"PSPG", 0x1704, body),
+            Err(Error::FileSystem(
+                FileSystemError::InconsistentHeader,
+                "ENTRY_HEADER::group_id"
+            ))
+        ));
+        assert!(apcb.insert_group_raw(*b"PSPG", 0x1701, &body[..20]).is_err());
+        apcb.insert_group_raw(*b"PSPG", 0x1701, body)?;
+        assert!(matches!(
+            apcb.insert_group_raw(*b"PSPG", 0x1701, body),
+            Err(Error::GroupUniqueKeyViolation)
+        ));
+        apcb.save().unwrap();
+        let apcb =
+            Apcb::load(&mut buffer[0..], &ApcbIoOptions::default()).unwrap();
+        let group = apcb.group(GroupId::Psp)?.ok_or(Error::GroupNotFound)?;
+        assert!(group.signature() == *b"PSPG");
+        assert!(group.body_as_buf() == body);
+        let mut entries = group.entries();
+        let entry = entries.next().unwrap();
+        assert!(entry.id() == EntryId::Psp(PspEntryId::BoardIdGettingMethod));
+        let entry = entries.next().unwrap();
+        assert!(entry.id() == EntryId::Psp(PspEntryId::Unknown(0x99)));
+        assert!(entries.next().is_none());
+        assert!(apcb.group(GroupId::Memory)?.is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn set_group_signature() -> Result<(), Error> {
+        let mut buffer: [u8; Apcb::MAX_SIZE] = [0xFF; Apcb::MAX_SIZE];
+        let mut apcb =
+            Apcb::create(&mut buffer[0..], 42, &ApcbIoOptions::default())
+                .unwrap();
+        apcb.insert_group(GroupId::Df, *b"DFG ")?;
+        apcb.insert_group(GroupId::Memory, *b"MEMG")?;
+        apcb.insert_entry(
+            EntryId::Df(DfEntryId::SlinkConfig),
+            0,
+            BoardInstances::all(),
+            ContextType::Struct,
+            PriorityLevels::from_level(PriorityLevel::Low),
+            &[1u8; 48],
+        )?;
+        let mut old_body = [0u8; 16 + 48];
+        old_body.copy_from_slice(
+            apcb.group(GroupId::Df)?.ok_or(Error::GroupNotFound)?.body_as_buf(),
+        );
+        assert!(matches!(
+            apcb.set_group_signature(GroupId::Psp, *b"PSPG"),
+            Err(Error::GroupNotFound)
+        ));
+        // Like a vendor blob with a misspelled signature
+        apcb.set_group_signature(GroupId::Df, *b"DGF ")?;
+        apcb.save().unwrap();
+        let mut apcb =
+            Apcb::load(&mut buffer[0..], &ApcbIoOptions::default()).unwrap();
+        let group = apcb.group(GroupId::Df)?.ok_or(Error::GroupNotFound)?;
+        assert!(group.signature() == *b"DGF ");
+        assert!(group.body_as_buf() == old_body);
+        apcb.set_group_signature(GroupId::Df, *b"DFG ")?;
+        apcb.save().unwrap();
+        let apcb =
+            Apcb::load(&mut buffer[0..], &ApcbIoOptions::default()).unwrap();
+        let group = apcb.group(GroupId::Df)?.ok_or(Error::GroupNotFound)?;
+        assert!(group.signature() == *b"DFG ");
+        assert!(group.body_as_buf() == old_body);
+        let group = apcb.group(GroupId::Memory)?.ok_or(Error::GroupNotFound)?;
+        assert!(group.signature() == *b"MEMG");
+        Ok(())
+    }
+
+    #[test]
+    fn iterate_mut_entry_extending_past_group() -> Result<(), Error> {
+        let mut buffer: [u8; Apcb::MAX_SIZE] = [0xFF; Apcb::MAX_SIZE];
+        let mut apcb =
+            Apcb::create(&mut buffer[0..], 42, &ApcbIoOptions::default())
+                .unwrap();
+        apcb.insert_group(GroupId::Psp, *b"PSPG")?;
+        apcb.insert_entry(
+            EntryId::Psp(PspEntryId::BoardIdGettingMethod),
+            0,
+            BoardInstances::all(),
+            ContextType::Struct,
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &[1u8; 48],
+        )?;
+        let mut group = apcb.group_mut(GroupId::Psp)?.unwrap();
+        let entry = group.entries_mut().next1()?;
+        entry.header.entry_size.set(16 + 48 + 32);
+        match group.entries_mut().next1() {
+            Err(Error::EntryRange) => {}
+            _ => panic!("unexpected result"),
+        }
+        assert!(group.entries_mut().next().is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn delete_second_entry() -> Result<(), Error> {
+        let mut buffer: [u8; Apcb::MAX_SIZE] = [0xFF; Apcb::MAX_SIZE];
+        let mut apcb =
+            Apcb::create(&mut buffer[0..], 42, &ApcbIoOptions::default())
+                .unwrap();
+        apcb.insert_group(GroupId::Psp, *b"PSPG")?;
+        for (i, type_id) in [97u16, 98, 99].into_iter().enumerate() {
+            apcb.insert_entry(
+                EntryId::Psp(PspEntryId::Unknown(type_id)),
+                0,
+                BoardInstances::all(),
+                ContextType::Struct,
+                PriorityLevels::from_level(PriorityLevel::Normal),
+                &[i as u8; 48],
+            )?;
+        }
+        apcb.delete_entry(
+            EntryId::Psp(PspEntryId::Unknown(98)),
+            0,
+            BoardInstances::all(),
+        )?;
+        apcb.save().unwrap();
+        let apcb =
+            Apcb::load(&mut buffer[0..], &ApcbIoOptions::default()).unwrap();
+        let group = apcb.group(GroupId::Psp)?.ok_or(Error::GroupNotFound)?;
+        let mut entries = group.entries();
+
+        let entry = entries.next().unwrap();
+        assert!(entry.id() == EntryId::Psp(PspEntryId::Unknown(97)));
+        assert!(entry.body_as_buf() == Some(&[0u8; 48][..]));
+
+        let entry = entries.next().unwrap();
+        assert!(entry.id() == EntryId::Psp(PspEntryId::Unknown(99)));
+        assert!(entry.body_as_buf() == Some(&[2u8; 48][..]));
+
+        assert!(entries.next().is_none());
+        Ok(())
+    }
+
     #[test]
     fn delete_entries() -> Result<(), Error> {
         let mut buffer: [u8; Apcb::MAX_SIZE] = [0xFF; Apcb::MAX_SIZE];
@@ -217,7 +615,7 @@ mod tests {
 
         let mut entries = group.entries();
 
-        let entry = entries.next().ok_or(Error::EntryNotFound)?;
+        let entry = entries.next().unwrap();
         assert!(entry.id() == EntryId::Psp(PspEntryId::Unknown(97)));
 
         assert!(matches!(entries.next(), None));
@@ -231,6 +629,53 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn preserve_oem_entries() -> Result<(), Error> {
+        let mut buffer: [u8; Apcb::MAX_SIZE] = [0xFF; Apcb::MAX_SIZE];
+        let mut apcb =
+            Apcb::create(&mut buffer[0..], 42, &ApcbIoOptions::default())
+                .unwrap();
+        apcb.insert_group(GroupId::Oem, *b"OEMG")?;
+        apcb.insert_group(GroupId::Memory, *b"MEMG")?;
+        apcb.insert_entry(
+            EntryId::Oem(OemEntryId::Unknown(0x42)),
+            0,
+            BoardInstances::all(),
+            ContextType::Struct,
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &[1, 2, 3, 4, 5, 6, 7, 8],
+        )?;
+        apcb.insert_entry(
+            EntryId::Oem(OemEntryId::Unknown(0x43)),
+            0,
+            BoardInstances::all(),
+            ContextType::Struct,
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &[9u8; 12],
+        )?;
+        apcb.save().unwrap();
+        let original = buffer;
+
+        let apcb =
+            Apcb::load(&mut buffer[0..], &ApcbIoOptions::default()).unwrap();
+        {
+            let mut entries = apcb.oem_entries_raw()?;
+            assert_eq!(
+                entries.next(),
+                Some((0x42, &[1, 2, 3, 4, 5, 6, 7, 8][..]))
+            );
+            assert_eq!(entries.next(), Some((0x43, &[9u8; 12][..])));
+            assert_eq!(entries.next(), None);
+        }
+        apcb.save().unwrap();
+
+        // The header changes on save (unique_apcb_instance, checksum), but
+        // the groups must not.
+        let groups_offset = size_of::<V2_HEADER>() + size_of::<V3_HEADER_EXT>();
+        assert_eq!(buffer[groups_offset..], original[groups_offset..]);
+        Ok(())
+    }
+
     #[test]
     fn insert_entries() -> Result<(), Error> {
         let mut buffer: [u8; Apcb::MAX_SIZE] = [0xFF; Apcb::MAX_SIZE];
@@ -270,12 +715,12 @@ mod tests {
 
         let mut entries = group.entries();
 
-        let entry = entries.next().ok_or(Error::EntryNotFound)?;
+        let entry = entries.next().unwrap();
         assert!(entry.id() == EntryId::Psp(PspEntryId::BoardIdGettingMethod));
         assert!(entry.instance_id() == 0);
         assert!(entry.board_instance_mask() == BoardInstances::all());
 
-        let entry = entries.next().ok_or(Error::EntryNotFound)?;
+        let entry = entries.next().unwrap();
         assert!(entry.id() == EntryId::Psp(PspEntryId::Unknown(97)));
         assert!(entry.instance_id() == 0);
         assert!(entry.board_instance_mask() == BoardInstances::all());
@@ -329,7 +774,7 @@ mod tests {
 
         let mut entries = group.entries();
 
-        let entry = entries.next().ok_or(Error::EntryNotFound)?;
+        let entry = entries.next().unwrap();
         assert!(entry.id() == EntryId::Psp(PspEntryId::BoardIdGettingMethod));
         assert!(entry.instance_id() == 0);
         assert!(entry.board_instance_mask() == BoardInstances::all());
@@ -342,7 +787,7 @@ mod tests {
 
         let mut entries = group.entries_mut();
 
-        let mut entry = entries.next().ok_or(Error::EntryNotFound)?;
+        let mut entry = entries.next().unwrap();
         assert!(
             entry.id() == EntryId::Memory(MemoryEntryId::ConsoleOutControl)
         );
@@ -368,20 +813,108 @@ mod tests {
     }
 
     #[test]
-    fn insert_incompatible_struct_entries() -> Result<(), Error> {
+    fn entry_body_prefix() -> Result<(), Error> {
+        use crate::ondisk::EntryCompatible;
+        use zerocopy::AsBytes;
         let mut buffer: [u8; Apcb::MAX_SIZE] = [0xFF; Apcb::MAX_SIZE];
         let mut apcb =
             Apcb::create(&mut buffer[0..], 42, &ApcbIoOptions::default())
                 .unwrap();
-        apcb.insert_group(GroupId::Psp, *b"PSPG")?;
         apcb.insert_group(GroupId::Memory, *b"MEMG")?;
-        apcb.insert_entry(
-            EntryId::Psp(PspEntryId::BoardIdGettingMethod),
+        let console_out_control = ConsoleOutControl::default();
+        apcb.insert_struct_entry(
+            EntryId::Memory(MemoryEntryId::ConsoleOutControl),
             0,
             BoardInstances::all(),
-            ContextType::Struct,
-            PriorityLevels::from_level(PriorityLevel::Low),
-            &[1u8; 48],
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &console_out_control,
+            &[],
+        )?;
+        let group = apcb.group(GroupId::Memory)?.ok_or(Error::GroupNotFound)?;
+        let entry = group
+            .entry_exact(
+                EntryId::Memory(MemoryEntryId::ConsoleOutControl),
+                0,
+                BoardInstances::all(),
+            )
+            .unwrap();
+        let prefix = entry.body_prefix(20);
+        assert!(prefix == &console_out_control.as_bytes()[..20]);
+        assert!(ConsoleOutControl::is_entry_compatible(entry.id(), prefix));
+        assert!(entry.body_prefix(1000) == console_out_control.as_bytes());
+        Ok(())
+    }
+
+    #[test]
+    fn modify_struct_entry() -> Result<(), Error> {
+        let mut buffer: [u8; Apcb::MAX_SIZE] = [0xFF; Apcb::MAX_SIZE];
+        let mut apcb =
+            Apcb::create(&mut buffer[0..], 42, &ApcbIoOptions::default())
+                .unwrap();
+        apcb.insert_group(GroupId::Memory, *b"MEMG")?;
+        apcb.insert_struct_entry(
+            EntryId::Memory(MemoryEntryId::ConsoleOutControl),
+            0,
+            BoardInstances::all(),
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &ConsoleOutControl::default(),
+            &[],
+        )?;
+        apcb.modify_struct(
+            EntryId::Memory(MemoryEntryId::ConsoleOutControl),
+            0,
+            BoardInstances::all(),
+            |console_out_control: &mut ConsoleOutControl| {
+                console_out_control
+                    .abl_console_out_control
+                    .set_enable_mem_pmu_logging(true);
+            },
+        )?;
+        assert!(matches!(
+            apcb.modify_struct(
+                EntryId::Memory(MemoryEntryId::ConsoleOutControl),
+                0,
+                BoardInstances::new(),
+                |_: &mut ConsoleOutControl| {},
+            ),
+            Err(Error::EntryNotFound { .. })
+        ));
+
+        apcb.save().unwrap();
+        let apcb =
+            Apcb::load(&mut buffer[0..], &ApcbIoOptions::default()).unwrap();
+        let group = apcb.group(GroupId::Memory)?.ok_or(Error::GroupNotFound)?;
+        let entry = group
+            .entry_exact(
+                EntryId::Memory(MemoryEntryId::ConsoleOutControl),
+                0,
+                BoardInstances::all(),
+            )
+            .unwrap();
+        let (console_out_control, _) =
+            entry.body_as_struct::<ConsoleOutControl>().unwrap();
+        let control = &console_out_control.abl_console_out_control;
+        assert!(control.enable_mem_pmu_logging()?);
+        assert!(control.enable_console_logging()?);
+        assert!(!control.enable_mem_status_logging()?);
+        Ok(())
+    }
+
+    #[test]
+    fn insert_incompatible_struct_entries() -> Result<(), Error> {
+        let mut buffer: [u8; Apcb::MAX_SIZE] = [0xFF; Apcb::MAX_SIZE];
+        let mut apcb =
+            Apcb::create(&mut buffer[0..], 42, &ApcbIoOptions::default())
+                .unwrap();
+        apcb.insert_group(GroupId::Psp, *b"PSPG")?;
+        apcb.insert_group(GroupId::Memory, *b"MEMG")?;
+        apcb.insert_entry(
+            EntryId::Psp(PspEntryId::BoardIdGettingMethod),
+            0,
+            BoardInstances::all(),
+            ContextType::Struct,
+            PriorityLevels::from_level(PriorityLevel::Low),
+            &[1u8; 48],
         )?;
         match apcb.insert_struct_entry(
             EntryId::Memory(MemoryEntryId::ConsoleOutControl),
@@ -442,7 +975,7 @@ mod tests {
 
         let mut entries = group.entries();
 
-        let entry = entries.next().ok_or(Error::EntryNotFound)?;
+        let entry = entries.next().unwrap();
         assert!(entry.id() == EntryId::Psp(PspEntryId::BoardIdGettingMethod));
         assert!(entry.instance_id() == 0);
         assert!(entry.board_instance_mask() == BoardInstances::all());
@@ -479,7 +1012,7 @@ mod tests {
 
         let mut entries = group.entries();
 
-        let entry = entries.next().ok_or(Error::EntryNotFound)?;
+        let entry = entries.next().unwrap();
         let (control, _) = entry
             .body_as_struct::<ExtVoltageControl>()
             .ok_or(Error::EntryTypeMismatch)?;
@@ -547,7 +1080,7 @@ mod tests {
 
         let mut entries = group.entries();
 
-        let entry = entries.next().ok_or(Error::EntryNotFound)?;
+        let entry = entries.next().unwrap();
         assert!(entry.id() == EntryId::Psp(PspEntryId::BoardIdGettingMethod));
         assert!(entry.instance_id() == 0);
         assert!(entry.board_instance_mask() == BoardInstances::all());
@@ -560,7 +1093,7 @@ mod tests {
 
         let mut entries = group.entries();
 
-        let entry = entries.next().ok_or(Error::EntryNotFound)?;
+        let entry = entries.next().unwrap();
         assert!(entry.id() == EntryId::Memory(MemoryEntryId::DimmInfoSmbus));
         assert!(entry.instance_id() == 0);
         assert!(entry.board_instance_mask() == BoardInstances::all());
@@ -726,12 +1259,12 @@ mod tests {
 
         let mut entries = group.entries_mut();
 
-        let entry = entries.next().ok_or(Error::EntryNotFound)?;
+        let entry = entries.next().unwrap();
         assert!(entry.id() == EntryId::Df(DfEntryId::SlinkConfig));
         assert!(entry.instance_id() == 0);
         assert!(entry.board_instance_mask() == BoardInstances::all());
 
-        let entry = entries.next().ok_or(Error::EntryNotFound)?;
+        let entry = entries.next().unwrap();
         assert!(entry.id() == EntryId::Df(DfEntryId::XgmiPhyOverride));
         assert!(entry.instance_id() == 0);
         assert!(entry.board_instance_mask() == BoardInstances::all());
@@ -751,14 +1284,14 @@ mod tests {
 
         let mut entries = group.entries_mut();
 
-        let entry = entries.next().ok_or(Error::EntryNotFound)?;
+        let entry = entries.next().unwrap();
         assert!(entry.id() == EntryId::Token(TokenEntryId::Byte));
 
         match entry.body {
             EntryItemBody::Tokens(ref tokens) => {
                 let mut tokens = tokens.iter().unwrap();
 
-                let token = tokens.next().ok_or(Error::TokenNotFound)?;
+                let token = tokens.next().unwrap();
                 assert!(token.id() == 0xae46_cea4);
                 assert!(token.value() == 2);
 
@@ -842,7 +1375,7 @@ mod tests {
             0xae46_cea4,
             2,
         ) {
-            Err(Error::EntryNotFound) => Ok(()),
+            Err(Error::EntryNotFound { .. }) => Ok(()),
             Err(e) => Err(e),
             _ => {
                 panic!("should not succeed");
@@ -850,6 +1383,156 @@ mod tests {
         }
     }
 
+    #[test]
+    fn insert_tokens_duplicate() -> Result<(), Error> {
+        let mut buffer: [u8; Apcb::MAX_SIZE] = [0xFF; Apcb::MAX_SIZE];
+        let mut apcb =
+            Apcb::create(&mut buffer[0..], 42, &ApcbIoOptions::default())
+                .unwrap();
+        apcb.insert_group(GroupId::Token, *b"TOKN")?;
+        let entry_id = EntryId::Token(TokenEntryId::Byte);
+        let board_instance_mask = BoardInstances::from_instance(0).unwrap();
+        match apcb.insert_token(
+            entry_id,
+            0,
+            board_instance_mask,
+            0xae46_cea4,
+            2,
+        ) {
+            Err(Error::EntryNotFound {
+                entry_id: e,
+                instance_id: 0,
+                board_instance_mask: b,
+            }) => {
+                assert!(e == entry_id);
+                assert!(b == board_instance_mask);
+            }
+            _ => {
+                panic!("should fail with EntryNotFound");
+            }
+        }
+        apcb.insert_entry(
+            entry_id,
+            0,
+            board_instance_mask,
+            ContextType::Tokens,
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &[],
+        )?;
+        apcb.insert_token(entry_id, 0, board_instance_mask, 0xae46_cea4, 2)?;
+        match apcb.insert_token(
+            entry_id,
+            0,
+            board_instance_mask,
+            0xae46_cea4,
+            3,
+        ) {
+            Err(Error::TokenUniqueKeyViolation {
+                entry_id: TokenEntryId::Byte,
+                token_id: 0xae46_cea4,
+            }) => {}
+            _ => {
+                panic!("should fail with TokenUniqueKeyViolation");
+            }
+        }
+        match apcb.set_token_value(entry_id, 0, board_instance_mask, 0x42, 3) {
+            Err(Error::TokenNotFound {
+                entry_id: Some(TokenEntryId::Byte),
+                token_id: 0x42,
+            }) => {}
+            _ => {
+                panic!("should fail with TokenNotFound");
+            }
+        }
+        assert!(
+            apcb.token_value(entry_id, 0, board_instance_mask, 0xae46_cea4)?
+                == 2
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn insert_tokens_value_range() -> Result<(), Error> {
+        let mut buffer: [u8; Apcb::MAX_SIZE] = [0xFF; Apcb::MAX_SIZE];
+        let mut apcb =
+            Apcb::create(&mut buffer[0..], 42, &ApcbIoOptions::default())
+                .unwrap();
+        apcb.insert_group(GroupId::Token, *b"TOKN")?;
+        for (token_entry_id, max_value) in [
+            (TokenEntryId::Bool, 1u32),
+            (TokenEntryId::Byte, 0xFF),
+            (TokenEntryId::Word, 0xFFFF),
+            (TokenEntryId::Dword, 0xFFFF_FFFF),
+        ] {
+            let entry_id = EntryId::Token(token_entry_id);
+            apcb.insert_entry(
+                entry_id,
+                0,
+                BoardInstances::all(),
+                ContextType::Tokens,
+                PriorityLevels::from_level(PriorityLevel::Normal),
+                &[],
+            )?;
+            apcb.insert_token(
+                entry_id,
+                0,
+                BoardInstances::all(),
+                0x1000,
+                max_value,
+            )?;
+            if let Some(value) = max_value.checked_add(1) {
+                match apcb.insert_token(
+                    entry_id,
+                    0,
+                    BoardInstances::all(),
+                    0x2000,
+                    value,
+                ) {
+                    Err(Error::TokenValueRange {
+                        entry_id: e,
+                        token_id: 0x2000,
+                        value: v,
+                    }) => {
+                        assert!(e == token_entry_id);
+                        assert!(v == value);
+                    }
+                    _ => {
+                        panic!("should fail with TokenValueRange");
+                    }
+                }
+                // Modifying an existing token is checked the same way.
+                match apcb.set_token_value(
+                    entry_id,
+                    0,
+                    BoardInstances::all(),
+                    0x1000,
+                    value,
+                ) {
+                    Err(Error::TokenValueRange {
+                        entry_id: e,
+                        token_id: 0x1000,
+                        value: v,
+                    }) => {
+                        assert!(e == token_entry_id);
+                        assert!(v == value);
+                    }
+                    _ => {
+                        panic!("should fail with TokenValueRange");
+                    }
+                }
+            }
+        }
+        assert!(
+            apcb.token_value(
+                EntryId::Token(TokenEntryId::Word),
+                0,
+                BoardInstances::all(),
+                0x1000
+            )? == 0xFFFF
+        );
+        Ok(())
+    }
+
     #[test]
     fn insert_tokens_easy() -> Result<(), Error> {
         let mut buffer: [u8; Apcb::MAX_SIZE] = [0xFF; Apcb::MAX_SIZE];
@@ -920,12 +1603,12 @@ mod tests {
 
         let mut entries = group.entries();
 
-        let entry = entries.next().ok_or(Error::EntryNotFound)?;
+        let entry = entries.next().unwrap();
         assert!(entry.id() == EntryId::Psp(PspEntryId::BoardIdGettingMethod));
         assert!(entry.instance_id() == 0);
         assert!(entry.board_instance_mask() == BoardInstances::all());
 
-        let entry = entries.next().ok_or(Error::EntryNotFound)?;
+        let entry = entries.next().unwrap();
         assert!(entry.id() == EntryId::Psp(PspEntryId::Unknown(97)));
         assert!(entry.instance_id() == 0);
         assert!(entry.board_instance_mask() == BoardInstances::all());
@@ -944,11 +1627,11 @@ mod tests {
         assert!(group.signature() == *b"TOKN");
         let mut entries = group.entries();
 
-        let entry = entries.next().ok_or(Error::EntryNotFound)?;
+        let entry = entries.next().unwrap();
         match entry.body {
             EntryItemBody::<_>::Tokens(tokens) => {
                 let mut tokens = tokens.iter().unwrap();
-                let token = tokens.next().ok_or(Error::TokenNotFound)?;
+                let token = tokens.next().unwrap();
                 assert!(token.id() == 0x014FBF20);
                 assert!(token.value() == 1);
                 assert!(matches!(tokens.next(), None));
@@ -1071,12 +1754,12 @@ mod tests {
 
         let mut entries = group.entries();
 
-        let entry = entries.next().ok_or(Error::EntryNotFound)?;
+        let entry = entries.next().unwrap();
         assert!(entry.id() == EntryId::Psp(PspEntryId::BoardIdGettingMethod));
         assert!(entry.instance_id() == 0);
         assert!(entry.board_instance_mask() == BoardInstances::all());
 
-        let entry = entries.next().ok_or(Error::EntryNotFound)?;
+        let entry = entries.next().unwrap();
         assert!(entry.id() == EntryId::Psp(PspEntryId::Unknown(97)));
         assert!(entry.instance_id() == 0);
         assert!(entry.board_instance_mask() == BoardInstances::all());
@@ -1095,18 +1778,18 @@ mod tests {
         assert!(group.signature() == *b"TOKN");
         let mut entries = group.entries();
 
-        let entry = entries.next().ok_or(Error::EntryNotFound)?;
+        let entry = entries.next().unwrap();
         match entry.body {
             EntryItemBody::<_>::Tokens(tokens) => {
                 let mut tokens = tokens.iter().unwrap();
 
                 // Note: Tokens were reordered by insert_token.
 
-                let token = tokens.next().ok_or(Error::TokenNotFound)?;
+                let token = tokens.next().unwrap();
                 assert!(token.id() == 0x640d_d003);
                 assert!(token.value() == 2);
 
-                let token = tokens.next().ok_or(Error::TokenNotFound)?;
+                let token = tokens.next().unwrap();
                 assert!(token.id() == 0xae46_cea4);
                 assert!(token.value() == 1);
 
@@ -1391,12 +2074,12 @@ mod tests {
 
         let mut entries = group.entries();
 
-        let entry = entries.next().ok_or(Error::EntryNotFound)?;
+        let entry = entries.next().unwrap();
         assert!(entry.id() == EntryId::Psp(PspEntryId::BoardIdGettingMethod));
         assert!(entry.instance_id() == 0);
         assert!(entry.board_instance_mask() == BoardInstances::all());
 
-        let entry = entries.next().ok_or(Error::EntryNotFound)?;
+        let entry = entries.next().unwrap();
         assert!(entry.id() == EntryId::Psp(PspEntryId::Unknown(97)));
         assert!(entry.instance_id() == 0);
         assert!(entry.board_instance_mask() == BoardInstances::all());
@@ -1415,12 +2098,12 @@ mod tests {
         assert!(group.signature() == *b"TOKN");
         let mut entries = group.entries();
 
-        let entry = entries.next().ok_or(Error::EntryNotFound)?;
+        let entry = entries.next().unwrap();
         match entry.body {
             EntryItemBody::<_>::Tokens(tokens) => {
                 let mut tokens = tokens.iter().unwrap();
 
-                let token = tokens.next().ok_or(Error::TokenNotFound)?;
+                let token = tokens.next().unwrap();
                 assert!(token.id() == 0x014FBF20);
                 assert!(token.value() == 1);
 
@@ -1488,7 +2171,7 @@ mod tests {
 
         let mut entries = group.entries();
 
-        let entry = entries.next().ok_or(Error::EntryNotFound)?;
+        let entry = entries.next().unwrap();
         assert!(entry.id() == EntryId::Psp(PspEntryId::BoardIdGettingMethod));
         assert!(entry.instance_id() == 0);
         assert!(entry.board_instance_mask() == BoardInstances::all());
@@ -1501,7 +2184,7 @@ mod tests {
 
         let mut entries = group.entries_mut();
 
-        let mut entry = entries.next().ok_or(Error::EntryNotFound)?;
+        let mut entry = entries.next().unwrap();
         assert!(
             entry.id()
                 == EntryId::Memory(MemoryEntryId::PlatformSpecificOverride)
@@ -1560,10 +2243,7 @@ mod tests {
             Ok(_) => {
                 panic!("should not be reached");
             }
-            Err(Error::FileSystem(
-                FileSystemError::InconsistentHeader,
-                "V2_HEADER::checksum_byte",
-            )) => Ok(()),
+            Err(Error::Checksum { .. }) => Ok(()),
             _ => {
                 panic!("should not be reached");
             }
@@ -1604,7 +2284,7 @@ mod tests {
 
         let mut entries = group.entries_mut();
 
-        let mut entry = entries.next().ok_or(Error::EntryNotFound)?;
+        let mut entry = entries.next().unwrap();
         assert!(
             entry.id() == EntryId::Memory(MemoryEntryId::PsRdimmDdr4CadBus)
         );
@@ -1614,7 +2294,7 @@ mod tests {
         let mut items =
             entry.body_as_struct_array_mut::<RdimmDdr4CadBusElement>().unwrap();
         let mut items = items.iter_mut();
-        let item = items.next().ok_or(Error::EntryNotFound)?;
+        let item = items.next().unwrap();
 
         assert!(item.dimm_slots_per_channel().unwrap() == 2);
         assert!(
@@ -1684,7 +2364,7 @@ mod tests {
 
         let mut entries = group.entries_mut();
 
-        let mut entry = entries.next().ok_or(Error::EntryNotFound)?;
+        let mut entry = entries.next().unwrap();
         assert!(
             entry.id() == EntryId::Memory(MemoryEntryId::PsRdimmDdr4DataBus)
         );
@@ -1694,7 +2374,7 @@ mod tests {
         let mut items =
             entry.body_as_struct_array_mut::<Ddr4DataBusElement>().unwrap();
         let mut items = items.iter_mut();
-        let item = items.next().ok_or(Error::EntryNotFound)?;
+        let item = items.next().unwrap();
 
         assert!(item.dimm_slots_per_channel().unwrap() == 2);
         assert!(
@@ -1725,4 +2405,849 @@ mod tests {
         assert!(matches!(groups.next(), None));
         Ok(())
     }
+
+    #[test]
+    fn insert_lrdimm_ddr4_max_freq_element() -> Result<(), Error> {
+        let mut buffer: [u8; Apcb::MAX_SIZE] = [0xFF; Apcb::MAX_SIZE];
+        let mut apcb =
+            Apcb::create(&mut buffer[0..], 42, &ApcbIoOptions::default())
+                .unwrap();
+        apcb.insert_group(GroupId::Memory, *b"MEMG")?;
+        use crate::memory::{
+            DdrSpeed, DimmsPerChannel, DimmsPerChannelSelector,
+            LrMaxFreqElement, LrStretchFreqElement,
+        };
+        let element = LrMaxFreqElement::new(
+            DdrSpeed::UnsupportedMilan,
+            DimmsPerChannel::Specific(
+                DimmsPerChannelSelector::new().with_two_dimms(true),
+            ),
+            2,
+            0,
+            2,
+            0,
+            DdrSpeed::Ddr2400,
+        );
+        apcb.insert_struct_array_as_entry(
+            EntryId::Memory(MemoryEntryId::PsLrdimmDdr4MaxFreq),
+            0,
+            BoardInstances::all(),
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &[element],
+        )?;
+        apcb.insert_struct_array_as_entry(
+            EntryId::Memory(MemoryEntryId::PsLrdimmDdr4StretchFreq),
+            0,
+            BoardInstances::all(),
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &[LrStretchFreqElement::default()],
+        )?;
+        apcb.save().unwrap();
+        let apcb =
+            Apcb::load(&mut buffer[0..], &ApcbIoOptions::default()).unwrap();
+        let group = apcb.group(GroupId::Memory)?.ok_or(Error::GroupNotFound)?;
+
+        let entry = group
+            .entry_exact(
+                EntryId::Memory(MemoryEntryId::PsLrdimmDdr4MaxFreq),
+                0,
+                BoardInstances::all(),
+            )
+            .unwrap();
+        let items = entry
+            .body_as_struct_array::<LrMaxFreqElement>()
+            .ok_or(Error::EntryTypeMismatch)?;
+        let mut items = items.iter();
+        let item = items.next().unwrap();
+        assert!(item == &element);
+        assert!(item.dimm_slots_per_channel()? == 2);
+        assert!(items.next().is_none());
+
+        let entry = group
+            .entry_exact(
+                EntryId::Memory(MemoryEntryId::PsLrdimmDdr4StretchFreq),
+                0,
+                BoardInstances::all(),
+            )
+            .unwrap();
+        let items = entry
+            .body_as_struct_array::<LrStretchFreqElement>()
+            .ok_or(Error::EntryTypeMismatch)?;
+        let mut items = items.iter();
+        assert!(items.next() == Some(&LrStretchFreqElement::default()));
+        assert!(items.next().is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn entry_tail_bytes() -> Result<(), Error> {
+        use zerocopy::AsBytes;
+        let mut buffer: [u8; Apcb::MAX_SIZE] = [0xFF; Apcb::MAX_SIZE];
+        let mut apcb =
+            Apcb::create(&mut buffer[0..], 42, &ApcbIoOptions::default())
+                .unwrap();
+        apcb.insert_group(GroupId::Memory, *b"MEMG")?;
+        let header = ExtVoltageControl::default();
+        let header_size = header.as_bytes().len();
+        let mut payload = [0xABu8; 64];
+        payload[..header_size].copy_from_slice(header.as_bytes());
+        let payload = &payload[..header_size + 8];
+        apcb.insert_entry(
+            EntryId::Memory(MemoryEntryId::ExtVoltageControl),
+            0,
+            BoardInstances::all(),
+            ContextType::Struct,
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            payload,
+        )?;
+        let group = apcb.group(GroupId::Memory)?.ok_or(Error::GroupNotFound)?;
+        let entry = group
+            .entry_exact(
+                EntryId::Memory(MemoryEntryId::ExtVoltageControl),
+                0,
+                BoardInstances::all(),
+            )
+            .unwrap();
+        let tail = entry.body.tail_bytes::<ExtVoltageControl>()?;
+        assert!(tail == [0xAB; 8]);
+        Ok(())
+    }
+
+    #[test]
+    fn looks_canonical() -> Result<(), Error> {
+        use core::mem::size_of;
+        use zerocopy::AsBytes;
+        let mut buffer: [u8; Apcb::MAX_SIZE] = [0xFF; Apcb::MAX_SIZE];
+        let mut apcb =
+            Apcb::create(&mut buffer[0..], 42, &ApcbIoOptions::default())
+                .unwrap();
+        apcb.insert_group(GroupId::Psp, *b"PSPG")?;
+        apcb.insert_entry(
+            EntryId::Psp(PspEntryId::BoardIdGettingMethod),
+            0,
+            BoardInstances::all(),
+            ContextType::Struct,
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &[1u8; 48],
+        )?;
+        apcb.save().unwrap();
+        let apcb = Apcb::load(&mut buffer[0..], &ApcbIoOptions::default())?;
+        assert!(apcb.looks_canonical());
+
+        // Change the entry body without updating the checksum.
+        let body_offset = 128 + 16 + 16;
+        buffer[body_offset] = 2;
+        let apcb = Apcb::load(
+            &mut buffer[0..],
+            &ApcbIoOptions::default().with_check_checksum(false).build(),
+        )?;
+        assert!(!apcb.looks_canonical());
+
+        // A modeled struct entry whose padding is not zero.
+        let mut buffer: [u8; Apcb::MAX_SIZE] = [0xFF; Apcb::MAX_SIZE];
+        let mut apcb =
+            Apcb::create(&mut buffer[0..], 42, &ApcbIoOptions::default())
+                .unwrap();
+        apcb.insert_group(GroupId::Memory, *b"MEMG")?;
+        let control = ExtVoltageControl::new_disabled();
+        let mut payload = [0u8; size_of::<ExtVoltageControl>() + 2];
+        payload[..size_of::<ExtVoltageControl>()]
+            .copy_from_slice(control.as_bytes());
+        apcb.insert_entry(
+            EntryId::Memory(MemoryEntryId::ExtVoltageControl),
+            0,
+            BoardInstances::all(),
+            ContextType::Struct,
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &payload,
+        )?;
+        apcb.save().unwrap();
+        let apcb = Apcb::load(&mut buffer[0..], &ApcbIoOptions::default())?;
+        assert!(apcb.looks_canonical());
+
+        let padding_offset = 128 + 16 + 16 + size_of::<ExtVoltageControl>();
+        buffer[padding_offset + 3] = 1;
+        let apcb = Apcb::load(
+            &mut buffer[0..],
+            &ApcbIoOptions::default().with_check_checksum(false).build(),
+        )?;
+        apcb.save().unwrap();
+        let apcb = Apcb::load(&mut buffer[0..], &ApcbIoOptions::default())?;
+        assert!(!apcb.looks_canonical());
+        Ok(())
+    }
+
+    #[test]
+    fn insert_entry_duplicate() -> Result<(), Error> {
+        let mut buffer: [u8; Apcb::MAX_SIZE] = [0xFF; Apcb::MAX_SIZE];
+        let mut apcb =
+            Apcb::create(&mut buffer[0..], 42, &ApcbIoOptions::default())
+                .unwrap();
+        apcb.insert_group(GroupId::Psp, *b"PSPG")?;
+        let entry_id = EntryId::Psp(PspEntryId::BoardIdGettingMethod);
+        apcb.insert_entry(
+            entry_id,
+            0,
+            BoardInstances::all(),
+            ContextType::Struct,
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &[1u8; 48],
+        )?;
+        let size = apcb.stats()?.total_size;
+        match apcb.insert_entry(
+            entry_id,
+            0,
+            BoardInstances::all(),
+            ContextType::Struct,
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &[2u8; 48],
+        ) {
+            Err(Error::EntryUniqueKeyViolation {
+                entry_id: existing_entry_id,
+                instance_id: 0,
+                board_instance_mask,
+            }) => {
+                assert!(existing_entry_id == entry_id);
+                assert!(board_instance_mask == BoardInstances::all());
+            }
+            _ => {
+                panic!("duplicate entry was accepted");
+            }
+        }
+        assert!(apcb.stats()?.total_size == size);
+        // Different board instance mask is a different entry.
+        apcb.insert_entry(
+            entry_id,
+            0,
+            BoardInstances::from_instance(1)?,
+            ContextType::Struct,
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &[3u8; 48],
+        )?;
+        assert!(apcb.stats()?.entry_count == 2);
+        Ok(())
+    }
+
+    #[test]
+    fn set_entry_board_mask() -> Result<(), Error> {
+        let mut buffer: [u8; Apcb::MAX_SIZE] = [0xFF; Apcb::MAX_SIZE];
+        let mut apcb =
+            Apcb::create(&mut buffer[0..], 42, &ApcbIoOptions::default())
+                .unwrap();
+        apcb.insert_group(GroupId::Psp, *b"PSPG")?;
+        let entry_id = EntryId::Psp(PspEntryId::BoardIdGettingMethod);
+        for (board_instance, value) in [(0, 1u8), (2, 2u8)] {
+            apcb.insert_entry(
+                entry_id,
+                0,
+                BoardInstances::from_instance(board_instance)?,
+                ContextType::Struct,
+                PriorityLevels::from_level(PriorityLevel::Normal),
+                &[value; 8],
+            )?;
+        }
+        assert!(matches!(
+            apcb.set_entry_board_mask(
+                entry_id,
+                0,
+                BoardInstances::from_instance(0)?,
+                BoardInstances::from_instance(2)?,
+            ),
+            Err(Error::EntryUniqueKeyViolation { .. })
+        ));
+        apcb.set_entry_board_mask(
+            entry_id,
+            0,
+            BoardInstances::from_instance(0)?,
+            BoardInstances::from(0xFFFF),
+        )?;
+        assert!(
+            apcb.entry_board_mask(
+                entry_id,
+                0,
+                BoardInstances::from_instance(2)?
+            )? == BoardInstances::from_instance(2)?
+        );
+        assert!(
+            apcb.entry_board_mask(
+                entry_id,
+                0,
+                BoardInstances::from_instance(0)?
+            )? == BoardInstances::from(0xFFFF)
+        );
+        // Entries are still in the default order.
+        let group = apcb.group(GroupId::Psp)?.ok_or(Error::GroupNotFound)?;
+        let mut entries = group.entries();
+        assert!(
+            entries.next().unwrap().board_instance_mask()
+                == BoardInstances::from_instance(2)?
+        );
+        assert!(
+            entries.next().unwrap().board_instance_mask()
+                == BoardInstances::from(0xFFFF)
+        );
+        assert!(entries.next().is_none());
+
+        apcb.save().unwrap();
+        let apcb = Apcb::load(&mut buffer[0..], &ApcbIoOptions::default())?;
+        let group = apcb.group(GroupId::Psp)?.ok_or(Error::GroupNotFound)?;
+        let entry = group
+            .entry_exact(entry_id, 0, BoardInstances::from(0xFFFF))
+            .unwrap();
+        match entry.body {
+            EntryItemBody::Struct(buf) => {
+                assert!(buf == [1u8; 8]);
+            }
+            _ => {
+                panic!("unexpected entry body");
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn upsert_entry() -> Result<(), Error> {
+        let mut buffer: [u8; Apcb::MAX_SIZE] = [0xFF; Apcb::MAX_SIZE];
+        let mut apcb =
+            Apcb::create(&mut buffer[0..], 42, &ApcbIoOptions::default())
+                .unwrap();
+        apcb.insert_group(GroupId::Psp, *b"PSPG")?;
+        let entry_id = EntryId::Psp(PspEntryId::BoardIdGettingMethod);
+        // Inserts
+        apcb.upsert_entry(
+            entry_id,
+            0,
+            BoardInstances::all(),
+            ContextType::Struct,
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &[1u8; 48],
+        )?;
+        // Replaces
+        apcb.upsert_entry(
+            entry_id,
+            0,
+            BoardInstances::all(),
+            ContextType::Struct,
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &[2u8; 16],
+        )?;
+        assert!(apcb.stats()?.entry_count == 1);
+        let group = apcb.group(GroupId::Psp)?.ok_or(Error::GroupNotFound)?;
+        let entry =
+            group.entry_exact(entry_id, 0, BoardInstances::all()).unwrap();
+        match entry.body {
+            EntryItemBody::Struct(buf) => {
+                assert!(buf == [2u8; 16]);
+            }
+            _ => {
+                panic!("unexpected entry body");
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn capabilities() -> Result<(), Error> {
+        assert!(crate::supported_groups().contains(&GroupId::Memory));
+        assert!(crate::supported_groups().contains(&GroupId::Token));
+        assert!(!crate::supported_groups()
+            .iter()
+            .any(|group_id| matches!(group_id, GroupId::Unknown(_))));
+        assert!(crate::supports_serde() == cfg!(feature = "serde"));
+        let mut buffer: [u8; Apcb::MAX_SIZE] = [0xFF; Apcb::MAX_SIZE];
+        let apcb =
+            Apcb::create(&mut buffer[0..], 42, &ApcbIoOptions::default())?;
+        assert!(apcb.header()?.version.get() == crate::crate_format_version());
+        Ok(())
+    }
+
+    #[test]
+    fn apcb_stats() -> Result<(), Error> {
+        let mut buffer: [u8; Apcb::MAX_SIZE] = [0xFF; Apcb::MAX_SIZE];
+        let mut apcb =
+            Apcb::create(&mut buffer[0..], 42, &ApcbIoOptions::default())
+                .unwrap();
+        assert!(
+            apcb.stats()?
+                == ApcbStats {
+                    group_count: 0,
+                    entry_count: 0,
+                    token_count: 0,
+                    total_size: 128,
+                }
+        );
+        apcb.insert_group(GroupId::Psp, *b"PSPG")?;
+        apcb.insert_group(GroupId::Token, *b"TOKN")?;
+        apcb.insert_entry(
+            EntryId::Psp(PspEntryId::BoardIdGettingMethod),
+            0,
+            BoardInstances::all(),
+            ContextType::Struct,
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &[1u8; 48],
+        )?;
+        apcb.insert_entry(
+            EntryId::Token(TokenEntryId::Byte),
+            0,
+            BoardInstances::all(),
+            ContextType::Tokens,
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &[],
+        )?;
+        apcb.insert_token(
+            EntryId::Token(TokenEntryId::Byte),
+            0,
+            BoardInstances::all(),
+            0xae46_cea4,
+            2,
+        )?;
+        apcb.insert_token(
+            EntryId::Token(TokenEntryId::Byte),
+            0,
+            BoardInstances::all(),
+            0x1333_32df,
+            3,
+        )?;
+        apcb.save().unwrap();
+        let apcb = Apcb::load(&mut buffer[0..], &ApcbIoOptions::default())?;
+        assert!(
+            apcb.stats()?
+                == ApcbStats {
+                    group_count: 2,
+                    entry_count: 2,
+                    token_count: 2,
+                    total_size: 128 + (16 + 16 + 48) + (16 + 16 + 2 * 8),
+                }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn priority_levels_highest() {
+        assert!(PriorityLevels::new().highest().is_none());
+        assert!(
+            PriorityLevels::new()
+                .with_hard_force(true)
+                .with_medium(true)
+                .with_normal(true)
+                .highest()
+                == Some(PriorityLevel::HardForce)
+        );
+        assert!(
+            PriorityLevels::new()
+                .with_event_logging(true)
+                .with_low(true)
+                .highest()
+                == Some(PriorityLevel::EventLogging)
+        );
+        assert!(
+            PriorityLevels::default().highest() == Some(PriorityLevel::Normal)
+        );
+
+        let mut levels = [
+            PriorityLevels::from_level(PriorityLevel::Low),
+            PriorityLevels::new(),
+            PriorityLevels::from_level(PriorityLevel::HardForce),
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            PriorityLevels::from_level(PriorityLevel::High),
+        ]
+        .map(|levels| levels.highest().map(OrderedPriorityLevel));
+        levels.sort_unstable();
+        assert!(
+            levels
+                == [
+                    None,
+                    Some(OrderedPriorityLevel(PriorityLevel::Normal)),
+                    Some(OrderedPriorityLevel(PriorityLevel::Low)),
+                    Some(OrderedPriorityLevel(PriorityLevel::High)),
+                    Some(OrderedPriorityLevel(PriorityLevel::HardForce)),
+                ]
+        );
+        assert!(
+            OrderedPriorityLevel(PriorityLevel::Medium)
+                > OrderedPriorityLevel(PriorityLevel::EventLogging)
+        );
+    }
+
+    #[test]
+    fn groups_exact_size() -> Result<(), Error> {
+        let mut buffer: [u8; Apcb::MAX_SIZE] = [0xFF; Apcb::MAX_SIZE];
+        let mut apcb =
+            Apcb::create(&mut buffer[0..], 42, &ApcbIoOptions::default())
+                .unwrap();
+        assert!(apcb.groups()?.len() == 0);
+        apcb.insert_group(GroupId::Psp, *b"PSPG")?;
+        apcb.insert_group(GroupId::Memory, *b"MEMG")?;
+        apcb.insert_group(GroupId::Token, *b"TOKN")?;
+        apcb.save().unwrap();
+        let apcb = Apcb::load(&mut buffer[0..], &ApcbIoOptions::default())?;
+        let mut groups = apcb.groups()?;
+        assert!(groups.len() == 3);
+        assert!(groups.size_hint() == (3, Some(3)));
+        let mut count = 0;
+        while groups.next().is_some() {
+            count += 1;
+            assert!(groups.len() == 3 - count);
+        }
+        assert!(count == 3);
+        assert!(apcb.groups()?.count() == apcb.groups()?.len());
+        Ok(())
+    }
+
+    #[test]
+    fn group_entry_token_counts() -> Result<(), Error> {
+        let mut buffer: [u8; Apcb::MAX_SIZE] = [0xFF; Apcb::MAX_SIZE];
+        let mut apcb =
+            Apcb::create(&mut buffer[0..], 42, &ApcbIoOptions::default())
+                .unwrap();
+        assert!(apcb.group_count()? == 0);
+        assert!(apcb.entry_count()? == 0);
+        assert!(apcb.token_count(EntryId::Token(TokenEntryId::Byte))? == 0);
+        apcb.insert_group(GroupId::Psp, *b"PSPG")?;
+        apcb.insert_group(GroupId::Token, *b"TOKN")?;
+        apcb.insert_entry(
+            EntryId::Psp(PspEntryId::BoardIdGettingMethod),
+            0,
+            BoardInstances::all(),
+            ContextType::Struct,
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &[1u8; 48],
+        )?;
+        for board_instance in [0, 1] {
+            let board_instance_mask =
+                BoardInstances::from_instance(board_instance).unwrap();
+            apcb.insert_entry(
+                EntryId::Token(TokenEntryId::Byte),
+                0,
+                board_instance_mask,
+                ContextType::Tokens,
+                PriorityLevels::from_level(PriorityLevel::Normal),
+                &[],
+            )?;
+            for token_id in [1, 2, 3] {
+                apcb.insert_token(
+                    EntryId::Token(TokenEntryId::Byte),
+                    0,
+                    board_instance_mask,
+                    token_id,
+                    board_instance.into(),
+                )?;
+            }
+        }
+        apcb.insert_entry(
+            EntryId::Token(TokenEntryId::Bool),
+            0,
+            BoardInstances::all(),
+            ContextType::Tokens,
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &[],
+        )?;
+        apcb.insert_token(
+            EntryId::Token(TokenEntryId::Bool),
+            0,
+            BoardInstances::all(),
+            4,
+            1,
+        )?;
+        apcb.save().unwrap();
+        let apcb = Apcb::load(&mut buffer[0..], &ApcbIoOptions::default())?;
+        assert!(apcb.group_count()? == 2);
+        assert!(apcb.entry_count()? == 4);
+        assert!(apcb.token_count(EntryId::Token(TokenEntryId::Byte))? == 6);
+        assert!(apcb.token_count(EntryId::Token(TokenEntryId::Bool))? == 1);
+        assert!(apcb.token_count(EntryId::Token(TokenEntryId::Dword))? == 0);
+        assert!(matches!(
+            apcb.token_count(EntryId::Psp(PspEntryId::BoardIdGettingMethod)),
+            Err(Error::EntryTypeMismatch)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn insert_entry_size_delta() -> Result<(), Error> {
+        let mut buffer: [u8; Apcb::MAX_SIZE] = [0xFF; Apcb::MAX_SIZE];
+        let mut apcb =
+            Apcb::create(&mut buffer[0..], 42, &ApcbIoOptions::default())
+                .unwrap();
+        let entry_id = EntryId::Psp(PspEntryId::BoardIdGettingMethod);
+        let size = apcb.live_bytes()?.len();
+        let delta = apcb.insert_entry_size_delta(entry_id, 45);
+        assert!(delta == 16 + 16 + 48);
+        apcb.insert_group(GroupId::Psp, *b"PSPG")?;
+        assert!(apcb.insert_entry_size_delta(entry_id, 45) == 16 + 48);
+        apcb.insert_entry(
+            entry_id,
+            0,
+            BoardInstances::all(),
+            ContextType::Struct,
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &[1u8; 45],
+        )?;
+        assert!(apcb.live_bytes()?.len() == size + delta);
+        Ok(())
+    }
+
+    #[test]
+    fn all_entries() -> Result<(), Error> {
+        let mut buffer: [u8; Apcb::MAX_SIZE] = [0xFF; Apcb::MAX_SIZE];
+        let mut apcb =
+            Apcb::create(&mut buffer[0..], 42, &ApcbIoOptions::default())
+                .unwrap();
+        assert!(apcb.all_entries()?.count() == 0);
+        apcb.insert_group(GroupId::Psp, *b"PSPG")?;
+        apcb.insert_group(GroupId::Df, *b"DFG ")?;
+        apcb.insert_group(GroupId::Token, *b"TOKN")?;
+        apcb.insert_entry(
+            EntryId::Psp(PspEntryId::BoardIdGettingMethod),
+            0,
+            BoardInstances::all(),
+            ContextType::Struct,
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &[1u8; 48],
+        )?;
+        apcb.insert_entry(
+            EntryId::Df(DfEntryId::SlinkConfig),
+            0,
+            BoardInstances::all(),
+            ContextType::Struct,
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &[1u8; 48],
+        )?;
+        apcb.insert_entry(
+            EntryId::Df(DfEntryId::XgmiPhyOverride),
+            0,
+            BoardInstances::all(),
+            ContextType::Struct,
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &[2u8; 1],
+        )?;
+        apcb.insert_entry(
+            EntryId::Token(TokenEntryId::Byte),
+            0,
+            BoardInstances::all(),
+            ContextType::Tokens,
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &[],
+        )?;
+        let count = apcb.all_entries()?.count();
+        assert!(count == 4);
+        assert!(
+            count
+                == apcb
+                    .groups()?
+                    .map(|group| group.entries().count())
+                    .sum::<usize>()
+        );
+        {
+            let mut entries = apcb.all_entries()?;
+            let (group_id, entry) = entries.nth(2).unwrap();
+            assert!(group_id == GroupId::Df);
+            assert!(entry.id() == EntryId::Df(DfEntryId::XgmiPhyOverride));
+        }
+
+        for (_, mut entry) in apcb.all_entries_mut()? {
+            entry.set_priority_mask(PriorityLevels::from_level(
+                PriorityLevel::Low,
+            ));
+        }
+        assert!(apcb.all_entries()?.all(|(_, entry)| entry.priority_mask()
+            == u8::from(PriorityLevels::from_level(PriorityLevel::Low))));
+        Ok(())
+    }
+
+    #[test]
+    fn set_entry_context_format() -> Result<(), Error> {
+        let mut buffer: [u8; Apcb::MAX_SIZE] = [0xFF; Apcb::MAX_SIZE];
+        let mut apcb =
+            Apcb::create(&mut buffer[0..], 42, &ApcbIoOptions::default())?;
+        let entry_id = EntryId::Token(TokenEntryId::Byte);
+        apcb.insert_group(GroupId::Token, *b"TOKN")?;
+        apcb.insert_entry(
+            entry_id,
+            0,
+            BoardInstances::all(),
+            ContextType::Tokens,
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &[],
+        )?;
+        for (token_id, token_value) in [(1, 10), (2, 20), (3, 30)] {
+            apcb.insert_token(
+                entry_id,
+                0,
+                BoardInstances::all(),
+                token_id,
+                token_value,
+            )?;
+        }
+        apcb.set_entry_context_format(
+            entry_id,
+            0,
+            BoardInstances::all(),
+            ContextFormat::Raw,
+        )?;
+        {
+            // Raw token entries are allowed to be out of order.
+            let mut group = apcb.group_mut(GroupId::Token)?.unwrap();
+            let mut entry = group
+                .entry_exact_mut(entry_id, 0, BoardInstances::all())
+                .unwrap();
+            assert!(entry.context_format() == ContextFormat::Raw);
+            match &mut entry.body {
+                EntryItemBody::<_>::Tokens(tokens) => {
+                    for (token, key) in tokens.iter_mut()?.zip([3, 1, 2]) {
+                        token.token.key.set(key);
+                    }
+                }
+                _ => panic!("expected tokens"),
+            }
+        }
+        apcb.save().unwrap();
+        let mut apcb = Apcb::load(&mut buffer[0..], &ApcbIoOptions::default())?;
+        apcb.set_entry_context_format(
+            entry_id,
+            0,
+            BoardInstances::all(),
+            ContextFormat::SortAscending,
+        )?;
+        let group = apcb.group(GroupId::Token)?.unwrap();
+        let entry =
+            group.entry_exact(entry_id, 0, BoardInstances::all()).unwrap();
+        assert!(entry.context_format() == ContextFormat::SortAscending);
+        assert!(entry.key_size() == 4);
+        assert!(entry.key_pos() == 0);
+        match &entry.body {
+            EntryItemBody::<_>::Tokens(tokens) => {
+                let mut tokens = tokens.iter()?;
+                for (key, value) in [(1, 20), (2, 30), (3, 10)] {
+                    let token = tokens.next().unwrap();
+                    assert!(token.id() == key);
+                    assert!(token.value() == value);
+                }
+                assert!(tokens.next().is_none());
+            }
+            _ => panic!("expected tokens"),
+        }
+
+        // Sorting fails (and leaves the entry alone) on duplicate keys.
+        apcb.set_entry_context_format(
+            entry_id,
+            0,
+            BoardInstances::all(),
+            ContextFormat::Raw,
+        )?;
+        {
+            let mut group = apcb.group_mut(GroupId::Token)?.unwrap();
+            let mut entry = group
+                .entry_exact_mut(entry_id, 0, BoardInstances::all())
+                .unwrap();
+            match &mut entry.body {
+                EntryItemBody::<_>::Tokens(tokens) => {
+                    for (token, key) in tokens.iter_mut()?.zip([3, 1, 3]) {
+                        token.token.key.set(key);
+                    }
+                }
+                _ => panic!("expected tokens"),
+            }
+        }
+        match apcb.set_entry_context_format(
+            entry_id,
+            0,
+            BoardInstances::all(),
+            ContextFormat::SortAscending,
+        ) {
+            Err(Error::TokenUniqueKeyViolation {
+                entry_id: TokenEntryId::Byte,
+                token_id: 3,
+            }) => {}
+            _ => {
+                panic!("should fail with TokenUniqueKeyViolation");
+            }
+        }
+        let group = apcb.group(GroupId::Token)?.unwrap();
+        let entry =
+            group.entry_exact(entry_id, 0, BoardInstances::all()).unwrap();
+        assert!(entry.context_format() == ContextFormat::Raw);
+        match &entry.body {
+            EntryItemBody::<_>::Tokens(tokens) => {
+                let keys = tokens.iter()?.map(|token| token.id());
+                assert!(keys.eq([3, 1, 3]));
+            }
+            _ => panic!("expected tokens"),
+        }
+        assert!(matches!(
+            apcb.set_entry_context_format(
+                EntryId::Token(TokenEntryId::Word),
+                0,
+                BoardInstances::all(),
+                ContextFormat::Raw,
+            ),
+            Err(Error::EntryNotFound { .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn validate_corrupted_group_size() -> Result<(), Error> {
+        let mut buffer: [u8; Apcb::MAX_SIZE] = [0xFF; Apcb::MAX_SIZE];
+        let mut apcb =
+            Apcb::create(&mut buffer[0..], 42, &ApcbIoOptions::default())?;
+        apcb.insert_group(GroupId::Psp, *b"PSPG")?;
+        apcb.insert_group(GroupId::Memory, *b"MEMG")?;
+        apcb.insert_entry(
+            EntryId::Psp(PspEntryId::BoardIdGettingMethod),
+            0,
+            BoardInstances::all(),
+            ContextType::Struct,
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &[1u8; 48],
+        )?;
+        apcb.insert_entry(
+            EntryId::Memory(MemoryEntryId::SpdInfo),
+            0,
+            BoardInstances::all(),
+            ContextType::Struct,
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &[2u8; 12],
+        )?;
+        apcb.validate(None)?;
+        {
+            let group = apcb.group_mut(GroupId::Memory)?.unwrap();
+            let group_size = group.header.group_size.get();
+            group.header.group_size.set(group_size - 4);
+        }
+        // The entry now sticks out of the (last) group.
+        let psp_group_size =
+            apcb.group(GroupId::Psp)?.unwrap().header.group_size.get() as usize;
+        match apcb.validate(None) {
+            Err(Error::FileSystemAt {
+                error: FileSystemError::InconsistentHeader,
+                field: "ENTRY_HEADER::entry_size",
+                group_id: Some(0x1704),
+                entry_id: Some(0x30),
+                offset,
+            }) => {
+                assert!(offset == 128 + psp_group_size + 16);
+            }
+            e => panic!("expected the Memory group to be reported: {e:?}"),
+        }
+        {
+            let group = apcb.group_mut(GroupId::Memory)?.unwrap();
+            let group_size = group.header.group_size.get();
+            group.header.group_size.set(group_size + 8);
+        }
+        // Now the group sticks out of the APCB.
+        match apcb.validate(None) {
+            Err(Error::FileSystemAt {
+                error: FileSystemError::InconsistentHeader,
+                field: "GROUP_HEADER::group_size",
+                group_id: Some(0x1704),
+                entry_id: None,
+                offset,
+            }) => {
+                assert!(offset == 128 + psp_group_size);
+            }
+            e => panic!("expected the Memory group to be reported: {e:?}"),
+        }
+        Ok(())
+    }
 }
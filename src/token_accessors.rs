@@ -13,6 +13,7 @@ use crate::ondisk::{
     BoolToken, ByteToken, ContextType, DwordToken, EntryId, TokenEntryId,
     WordToken,
 };
+use crate::types::ApcbEdit;
 use crate::types::Error;
 use crate::types::Result;
 
@@ -67,10 +68,17 @@ impl<'a, 'b> TokensMut<'a, 'b> {
                 self.instance_id,
                 self.board_instance_mask,
             )
-            .ok_or(Error::EntryNotFound)?;
+            .ok_or(Error::EntryNotFound {
+                entry_id: EntryId::Token(token_entry_id),
+                instance_id: self.instance_id,
+                board_instance_mask: self.board_instance_mask,
+            })?;
         match &entry.body {
             EntryItemBody::<_>::Tokens(ref a) => {
-                let token = a.token(field_key).ok_or(Error::TokenNotFound)?;
+                let token = a.token(field_key).ok_or(Error::TokenNotFound {
+                    entry_id: Some(token_entry_id),
+                    token_id: field_key,
+                })?;
                 assert!(token.id() == field_key);
                 let token_value = token.value();
                 Ok(token_value)
@@ -96,7 +104,7 @@ impl<'a, 'b> TokensMut<'a, 'b> {
             token_id,
             token_value,
         ) {
-            Err(Error::EntryNotFound) => {
+            Err(Error::EntryNotFound { .. }) => {
                 match self.apcb.insert_entry(
                     entry_id,
                     self.instance_id,
@@ -105,7 +113,7 @@ impl<'a, 'b> TokensMut<'a, 'b> {
                     self.priority_mask,
                     &[],
                 ) {
-                    Err(Error::EntryUniqueKeyViolation) => {}
+                    Err(Error::EntryUniqueKeyViolation { .. }) => {}
                     Err(x) => {
                         return Err(x);
                     }
@@ -147,7 +155,7 @@ impl<'a, 'b> TokensMut<'a, 'b> {
                     token_value,
                 )?;
             }
-            Err(Error::TokenUniqueKeyViolation) => {
+            Err(Error::TokenUniqueKeyViolation { .. }) => {
                 let mut group = self.apcb.group_mut(GroupId::Token)?.unwrap();
                 let mut entry = group
                     .entry_exact_mut(
@@ -165,6 +173,13 @@ impl<'a, 'b> TokensMut<'a, 'b> {
                         return Err(Error::EntryTypeMismatch);
                     }
                 }
+                self.apcb.record_edit(ApcbEdit::SetToken {
+                    entry_id,
+                    instance_id: self.instance_id,
+                    board_instance_mask: self.board_instance_mask,
+                    token_id,
+                    token_value,
+                });
             }
             Err(x) => {
                 return Err(x);
@@ -198,10 +213,17 @@ impl<'a, 'b> Tokens<'a, 'b> {
                 self.instance_id,
                 self.board_instance_mask,
             )
-            .ok_or(Error::EntryNotFound)?;
+            .ok_or(Error::EntryNotFound {
+                entry_id: EntryId::Token(token_entry_id),
+                instance_id: self.instance_id,
+                board_instance_mask: self.board_instance_mask,
+            })?;
         match &entry.body {
             EntryItemBody::<_>::Tokens(ref a) => {
-                let token = a.token(field_key).ok_or(Error::TokenNotFound)?;
+                let token = a.token(field_key).ok_or(Error::TokenNotFound {
+                    entry_id: Some(token_entry_id),
+                    token_id: field_key,
+                })?;
                 assert!(token.id() == field_key);
                 let token_value = token.value();
                 Ok(token_value)
@@ -257,7 +279,10 @@ macro_rules! make_token_accessors {(
                 Ok(Self::$field_name(value))
             } else
           )*{
-                Err(Error::TokenNotFound)
+                Err(Error::TokenNotFound {
+                    entry_id: Some($field_entry_id),
+                    token_id: tag,
+                })
             }
         }
     }
@@ -272,7 +297,7 @@ macro_rules! make_token_accessors {(
                 })
             } else
           )*{
-                Err(Error::TokenNotFound)
+                unreachable!("every variant has a token id")
             }
         }
     }
@@ -337,6 +362,21 @@ macro_rules! make_token_accessors {(
            true
        }
       }
+      /// If FIELD_KEY is a known token and VALUE cannot be decoded as its
+      /// type (for bitfield types: has reserved bits set), returns the
+      /// token's name.
+      #[cfg_attr(not(feature = "std"), allow(dead_code))]
+      pub(crate) fn undecodable_token_name(field_key: u32, value: u32) -> Option<&'static str> {
+       $(
+           if (field_key == $field_key) {
+               return match <$field_user_ty>::from_u32(value) {
+                   Some(_) => None,
+                   None => Some(stringify!($field_name)),
+               };
+           }
+       )*
+       None
+      }
       pub fn valid_for_abl0(&self, abl0_version: u32) -> core::result::Result<bool, Error> {
           let token_entry = TOKEN_ENTRY::try_from(self)?;
           Ok(Self::valid_for_abl0_raw(abl0_version, token_entry.key.get()))
@@ -13,6 +13,7 @@ use core::mem::size_of;
 use num_traits::FromPrimitive;
 use pre::pre;
 use zerocopy::ByteSlice;
+use zerocopy::LayoutVerified;
 
 #[cfg(feature = "serde")]
 use serde::ser::{Serialize, Serializer};
@@ -94,33 +95,21 @@ impl<'a> TokensEntryItem<&'a mut TOKEN_ENTRY> {
         self.token.key.get()
     }
     pub fn value(&self) -> u32 {
-        self.token.value.get()
-            & match self.entry_id {
-                TokenEntryId::Bool => 0x1,
-                TokenEntryId::Byte => 0xFF,
-                TokenEntryId::Word => 0xFFFF,
-                TokenEntryId::Dword => 0xFFFF_FFFF,
-                TokenEntryId::Unknown(_) => 0xFFFF_FFFF,
-            }
+        self.token.value.get() & self.entry_id.value_mask()
     }
 
     // Since the id is a sort key, it cannot be mutated.
 
     pub fn set_value(&mut self, value: u32) -> Result<()> {
-        if value
-            == (value
-                & match self.entry_id {
-                    TokenEntryId::Bool => 0x1,
-                    TokenEntryId::Byte => 0xFF,
-                    TokenEntryId::Word => 0xFFFF,
-                    TokenEntryId::Dword => 0xFFFF_FFFF,
-                    TokenEntryId::Unknown(_) => 0xFFFF_FFFF,
-                })
-        {
+        if value & !self.entry_id.value_mask() == 0 {
             self.token.value.set(value);
             Ok(())
         } else {
-            Err(Error::TokenRange)
+            Err(Error::TokenValueRange {
+                entry_id: self.entry_id,
+                token_id: self.id(),
+                value,
+            })
         }
     }
 }
@@ -182,7 +171,10 @@ impl<'a> TokensEntryIter<&'a mut [u8]> {
         loop {
             let mut buf = &mut self.buf[..self.remaining_used_size];
             if buf.is_empty() {
-                return Err(Error::TokenNotFound);
+                return Err(Error::TokenNotFound {
+                    entry_id: Some(self.entry_id),
+                    token_id,
+                });
             }
             match Self::next_item(self.entry_id, &mut buf) {
                 Ok(e) => {
@@ -468,14 +460,7 @@ impl<'a> TokensEntryItem<&'a TOKEN_ENTRY> {
         self.token.key.get()
     }
     pub fn value(&self) -> u32 {
-        self.token.value.get()
-            & match self.entry_id {
-                TokenEntryId::Bool => 0x1,
-                TokenEntryId::Byte => 0xFF,
-                TokenEntryId::Word => 0xFFFF,
-                TokenEntryId::Dword => 0xFFFF_FFFF,
-                TokenEntryId::Unknown(_) => 0xFFFF_FFFF,
-            }
+        self.token.value.get() & self.entry_id.value_mask()
     }
 }
 
@@ -531,18 +516,14 @@ impl<'a> TokensEntryIter<&'a [u8]> {
                 FileSystemError::InconsistentHeader,
                 "ENTRY_HEADER::context_format",
             ))?;
-        if context_format != ContextFormat::SortAscending {
-            return Err(Error::FileSystem(
-                FileSystemError::InconsistentHeader,
-                "ENTRY_HEADER::context_format",
-            ));
-        }
         let mut previous_id = 0_u32;
         while self.remaining_used_size > 0 {
             match self.next1() {
                 Ok(item) => {
                     let id = item.id();
-                    if id < previous_id {
+                    if context_format == ContextFormat::SortAscending
+                        && id < previous_id
+                    {
                         return Err(Error::TokenOrderingViolation);
                     }
                     previous_id = id
@@ -591,6 +572,18 @@ impl<BufferType: ByteSlice> TokensEntryBodyItem<BufferType> {
     }
 }
 
+impl<'a> TokensEntryBodyItem<&'a [u8]> {
+    /// Returns all the tokens as one slice, without decoding them.
+    pub(crate) fn raw_entries(&self) -> Result<&'a [TOKEN_ENTRY]> {
+        self.prepare_iter()?;
+        let buf: &'a [u8] =
+            self.buf.get(..self.used_size).ok_or(Error::EntryRange)?;
+        LayoutVerified::<&'a [u8], [TOKEN_ENTRY]>::new_slice_unaligned(buf)
+            .map(|tokens| tokens.into_slice())
+            .ok_or(Error::EntryTypeMismatch)
+    }
+}
+
 impl<'a> TokensEntryBodyItem<&'a mut [u8]> {
     pub fn iter_mut(&mut self) -> Result<TokensEntryIter<&'_ mut [u8]>> {
         let entry_id = self.prepare_iter()?;
@@ -638,6 +631,45 @@ impl<'a> TokensEntryBodyItem<&'a mut [u8]> {
     pub(crate) fn delete_token(&mut self, token_id: u32) -> Result<()> {
         self.iter_mut()?.delete_token(token_id)
     }
+
+    /// Sets the context_format of the tokens.  Switching to
+    /// ContextFormat::SortAscending sorts the tokens by key--and fails with
+    /// TokenUniqueKeyViolation (without changing anything) if two tokens
+    /// have the same key.
+    /// Note: The caller has to update the ENTRY_HEADER accordingly.
+    pub(crate) fn set_context_format(
+        &mut self,
+        context_format: ContextFormat,
+    ) -> Result<()> {
+        if context_format == ContextFormat::SortAscending {
+            let entry_id = TokenEntryId::from_u16(self.entry_id).ok_or(
+                Error::FileSystem(
+                    FileSystemError::InconsistentHeader,
+                    "ENTRY_HEADER::entry_id",
+                ),
+            )?;
+            let buf =
+                self.buf.get_mut(..self.used_size).ok_or(Error::EntryRange)?;
+            let mut tokens =
+                LayoutVerified::<_, [TOKEN_ENTRY]>::new_slice_unaligned(buf)
+                    .ok_or(Error::EntryTypeMismatch)?;
+            for (i, token) in tokens.iter().enumerate() {
+                let token_id = token.key.get();
+                if tokens[..i].iter().any(|other| other.key.get() == token_id) {
+                    return Err(Error::TokenUniqueKeyViolation {
+                        entry_id,
+                        token_id,
+                    });
+                }
+            }
+            tokens.sort_unstable_by_key(|token| token.key.get());
+            self.unit_size = 8;
+            self.key_size = 4;
+            self.key_pos = 0;
+        }
+        self.context_format = context_format as u8;
+        Ok(())
+    }
 }
 
 impl TokenEntryId {
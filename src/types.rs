@@ -2,7 +2,9 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use crate::ondisk::TokenEntryId;
+use crate::ondisk::{
+    BoardInstances, ContextFormat, EntryId, GroupId, TokenEntryId,
+};
 
 #[derive(Debug)]
 #[cfg_attr(feature = "std", derive(thiserror::Error))]
@@ -22,6 +24,20 @@ pub enum Error {
     ArithmeticOverflow,
     #[cfg_attr(feature = "std", error("file system error {0}: {1}"))]
     FileSystem(FileSystemError, &'static str), // message, field name
+    /// Like FileSystem, but also says where in the APCB the problem is.
+    /// OFFSET is relative to the beginning of the APCB.
+    #[cfg_attr(feature = "std", error("file system error {error}: {field} (group {group_id:#x?}, entry {entry_id:#x?}, offset {offset:#x})"))]
+    FileSystemAt {
+        error: FileSystemError,
+        field: &'static str,
+        group_id: Option<u16>,
+        entry_id: Option<u16>,
+        offset: usize,
+    },
+    #[cfg_attr(feature = "std", error("checksum mismatch: stored {stored:#04x}, calculated {calculated:#04x}"))]
+    Checksum { stored: u8, calculated: u8 },
+    #[cfg_attr(feature = "std", error("V3_HEADER_EXT checksum mismatch: stored {stored:#04x}, calculated {calculated:#04x}"))]
+    HeaderChecksum { stored: u8, calculated: u8 },
     #[cfg_attr(feature = "std", error("out of space"))]
     OutOfSpace,
     #[cfg_attr(feature = "std", error("group not found"))]
@@ -30,32 +46,69 @@ pub enum Error {
     GroupUniqueKeyViolation,
     #[cfg_attr(feature = "std", error("group type mismatch"))]
     GroupTypeMismatch,
-    #[cfg_attr(feature = "std", error("entry not found"))]
-    EntryNotFound,
-    #[cfg_attr(feature = "std", error("entry unique key violation"))]
-    EntryUniqueKeyViolation,
+    #[cfg_attr(feature = "std", error("entry not found: entry {entry_id:?} instance {instance_id} board instance mask {board_instance_mask:?}"))]
+    EntryNotFound {
+        entry_id: EntryId,
+        instance_id: u16,
+        board_instance_mask: BoardInstances,
+    },
+    #[cfg_attr(feature = "std", error("entry unique key violation: entry {entry_id:?} instance {instance_id} board instance mask {board_instance_mask:?} already exists"))]
+    EntryUniqueKeyViolation {
+        entry_id: EntryId,
+        instance_id: u16,
+        board_instance_mask: BoardInstances,
+    },
     #[cfg_attr(feature = "std", error("entry type mismatch"))]
     EntryTypeMismatch,
     #[cfg_attr(feature = "std", error("entry range"))]
     EntryRange,
-    #[cfg_attr(feature = "std", error("token not found"))]
-    TokenNotFound,
+    #[cfg_attr(feature = "std", error("entry group id {entry:#06x} does not match its group's id {group:#06x}"))]
+    EntryGroupIdMismatch { entry: u16, group: u16 },
+    #[cfg_attr(
+        feature = "std",
+        error("group {group:#06x} has unexpected version {version:#x}")
+    )]
+    GroupVersionMismatch { group: u16, version: u16 },
+    #[cfg_attr(feature = "std", error("unsupported APCB version {found:#x}"))]
+    UnsupportedVersion { found: u16 },
+    /// ENTRY_ID is None if the token was looked for in all the token
+    /// entries.
+    #[cfg_attr(
+        feature = "std",
+        error("token not found: token entry {entry_id:?} token {token_id:#x}")
+    )]
+    TokenNotFound { entry_id: Option<TokenEntryId>, token_id: u32 },
     #[cfg_attr(feature = "std", error("token ordering violation"))]
     TokenOrderingViolation,
-    #[cfg_attr(feature = "std", error("token unique key violation"))]
-    TokenUniqueKeyViolation,
+    #[cfg_attr(feature = "std", error("token unique key violation: token entry {entry_id:?} token {token_id:#x} already exists"))]
+    TokenUniqueKeyViolation { entry_id: TokenEntryId, token_id: u32 },
     #[cfg_attr(feature = "std", error("token range"))]
     TokenRange,
+    #[cfg_attr(feature = "std", error("token entry {entry_id:?} token {token_id:#x} cannot hold value {value:#x}"))]
+    TokenValueRange { entry_id: TokenEntryId, token_id: u32, value: u32 },
     #[cfg_attr(feature = "std", error("token entry {entry_id:?} token {token_id} is incompatible with ABL version {abl0_version}"))]
     TokenVersionMismatch {
         entry_id: TokenEntryId,
         token_id: u32,
         abl0_version: u32,
     },
+    #[cfg_attr(feature = "std", error("token {key:#x} has value {actual:#x} but {expected:#x} was expected"))]
+    TokenValueMismatch { key: u32, expected: u32, actual: u32 },
     #[cfg_attr(feature = "std", error("parameter not found"))]
     ParameterNotFound,
-    #[cfg_attr(feature = "std", error("parameter range"))]
-    ParameterRange,
+    /// OFFSET is the offset of the offending parameter attributes (or,
+    /// if the Limit terminator is missing, of the end of the parameters).
+    #[cfg_attr(
+        feature = "std",
+        error("parameter range at offset {offset:#x}")
+    )]
+    ParameterRange { offset: usize },
+    #[cfg(feature = "std")]
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[cfg(feature = "serde-yaml")]
+    #[error("YAML error: {0}")]
+    Yaml(#[from] serde_yaml::Error),
     // Errors used only for Serde
     #[cfg_attr(feature = "std", error("entry not extractable"))]
     EntryNotExtractable,
@@ -65,6 +118,7 @@ pub enum Error {
 
 pub type Result<Q> = core::result::Result<Q, Error>;
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum PriorityLevel {
     HardForce,
     High,
@@ -74,6 +128,37 @@ pub enum PriorityLevel {
     Normal, // the default
 }
 
+/// Orders PriorityLevel by strength, so HardForce is the greatest and Normal
+/// is the least.  This is useful for sorting entries by
+/// PriorityLevels::highest.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct OrderedPriorityLevel(pub PriorityLevel);
+
+impl OrderedPriorityLevel {
+    fn strength(&self) -> u8 {
+        match self.0 {
+            PriorityLevel::HardForce => 5,
+            PriorityLevel::High => 4,
+            PriorityLevel::Medium => 3,
+            PriorityLevel::EventLogging => 2,
+            PriorityLevel::Low => 1,
+            PriorityLevel::Normal => 0,
+        }
+    }
+}
+
+impl PartialOrd for OrderedPriorityLevel {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OrderedPriorityLevel {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.strength().cmp(&other.strength())
+    }
+}
+
 #[cfg(feature = "std")]
 extern crate std;
 
@@ -105,6 +190,201 @@ pub enum MemDfeSearchVersion {
     Turin1 = 0x0c0c0c,
 }
 
+/// Processor generation that a blob is meant to be used on.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SiliconGeneration {
+    Naples,
+    Rome,
+    Milan,
+    Genoa,
+    Turin,
+}
+
+impl SiliconGeneration {
+    pub fn supports_ddr4(&self) -> bool {
+        matches!(self, Self::Naples | Self::Rome | Self::Milan)
+    }
+    pub fn supports_ddr5(&self) -> bool {
+        matches!(self, Self::Genoa | Self::Turin)
+    }
+}
+
+/// Something in a blob that the target SiliconGeneration would not
+/// understand.  See Apcb::check_for_silicon.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SiliconIncompatibility {
+    /// The entry is for a DRAM technology that the generation doesn't
+    /// support (for example a DDR4 entry on a DDR5-only platform).
+    UnsupportedMemoryTechnology {
+        entry_id: EntryId,
+        instance_id: u16,
+        board_instance_mask: BoardInstances,
+    },
+    /// The entry id is unknown.
+    UnknownEntry {
+        entry_id: EntryId,
+        instance_id: u16,
+        board_instance_mask: BoardInstances,
+    },
+    /// The token id is unknown.
+    UnknownToken { entry_id: TokenEntryId, token_id: u32 },
+}
+
+/// A bitfield in a blob that has reserved bits set--for example because a
+/// vendor extension uses them.  See Apcb::report_unknown_bits.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct UnknownBitsReport {
+    pub entry_id: EntryId,
+    pub instance_id: u16,
+    pub board_instance_mask: BoardInstances,
+    /// Index of the element in the entry's struct array, struct sequence,
+    /// beep code table or token list (0 for the entry header).
+    pub element_index: usize,
+    /// "STRUCT_NAME.FIELD_NAME", or the token name for tokens.
+    pub field: &'static str,
+    /// Raw value of the field (including the reserved bits).
+    pub value: u64,
+}
+
+/// Something Apcb::load_best_effort had to work around.
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum ApcbWarning {
+    /// The stored checksum did not match the contents.
+    ChecksumMismatch { stored: u8, calculated: u8 },
+    /// V2_HEADER::apcb_size extends beyond the end of the buffer.
+    Truncated { apcb_size: usize, buffer_size: usize },
+    /// The groups starting at OFFSET (relative to the beginning of the
+    /// APCB), SIZE bytes in total, could not be parsed and were skipped.
+    GroupsSkipped { offset: usize, size: usize, error: Error },
+    /// Nothing could be recovered.
+    LoadFailed(Error),
+}
+
+/// A difference between two Apcbs, as returned by Apcb::diff.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ApcbDiffItem {
+    /// The V2_HEADER or the V3_HEADER_EXT differs (or only one of them has
+    /// a V3_HEADER_EXT).
+    HeaderChanged,
+    GroupAdded {
+        group_id: GroupId,
+    },
+    GroupRemoved {
+        group_id: GroupId,
+    },
+    /// The group header (for example its signature or version) differs.
+    GroupChanged {
+        group_id: GroupId,
+    },
+    EntryAdded {
+        entry_id: EntryId,
+        instance_id: u16,
+        board_instance_mask: BoardInstances,
+    },
+    EntryRemoved {
+        entry_id: EntryId,
+        instance_id: u16,
+        board_instance_mask: BoardInstances,
+    },
+    /// The entry header (except for its size) or the body differs.
+    /// Note: For token entries, the tokens are reported as TokenChanged
+    /// instead.
+    EntryChanged {
+        entry_id: EntryId,
+        instance_id: u16,
+        board_instance_mask: BoardInstances,
+    },
+    /// OLD is None if the token was added, NEW is None if it was removed.
+    TokenChanged {
+        entry_id: EntryId,
+        instance_id: u16,
+        board_instance_mask: BoardInstances,
+        token_id: u32,
+        old: Option<u32>,
+        new: Option<u32>,
+    },
+}
+
+/// A mutating operation that was performed on an Apcb, as recorded by
+/// Apcb::edit_log (see ApcbIoOptions::record_edits).
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ApcbEdit {
+    InsertGroup {
+        group_id: GroupId,
+        signature: [u8; 4],
+    },
+    DeleteGroup {
+        group_id: GroupId,
+    },
+    /// The signature of an existing group was changed.
+    SetGroupSignature {
+        group_id: GroupId,
+        signature: [u8; 4],
+    },
+    InsertEntry {
+        entry_id: EntryId,
+        instance_id: u16,
+        board_instance_mask: BoardInstances,
+    },
+    DeleteEntry {
+        entry_id: EntryId,
+        instance_id: u16,
+        board_instance_mask: BoardInstances,
+    },
+    SetEntryBoardMask {
+        entry_id: EntryId,
+        instance_id: u16,
+        old_board_instance_mask: BoardInstances,
+        new_board_instance_mask: BoardInstances,
+    },
+    SetEntryContextFormat {
+        entry_id: EntryId,
+        instance_id: u16,
+        board_instance_mask: BoardInstances,
+        context_format: ContextFormat,
+    },
+    /// The body of an existing entry was modified in place (see
+    /// Apcb::modify_struct).
+    ModifyEntryBody {
+        entry_id: EntryId,
+        instance_id: u16,
+        board_instance_mask: BoardInstances,
+    },
+    /// The entries of a group were reordered (see Apcb::sort_entries).
+    SortEntries {
+        group_id: GroupId,
+    },
+    InsertToken {
+        entry_id: EntryId,
+        instance_id: u16,
+        board_instance_mask: BoardInstances,
+        token_id: u32,
+        token_value: u32,
+    },
+    DeleteToken {
+        entry_id: EntryId,
+        instance_id: u16,
+        board_instance_mask: BoardInstances,
+        token_id: u32,
+    },
+    /// The value of an existing token was changed.
+    SetToken {
+        entry_id: EntryId,
+        instance_id: u16,
+        board_instance_mask: BoardInstances,
+        token_id: u32,
+        token_value: u32,
+    },
+}
+
 #[derive(Copy, Clone, Debug, Default)] // TODO: Remove Copy?
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
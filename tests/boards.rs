@@ -0,0 +1,157 @@
+#[cfg(feature = "std")]
+#[test]
+fn test_split_by_board() {
+    use amd_apcb::memory::{ConsoleOutControl, ExtVoltageControl};
+    use amd_apcb::{
+        Apcb, ApcbIoOptions, BoardInstances, EntryId, GroupId, MemoryEntryId,
+        PriorityLevel, PriorityLevels,
+    };
+    use std::borrow::Cow;
+
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    apcb.insert_group(GroupId::Memory, *b"MEMG").unwrap();
+    apcb.insert_struct_entry(
+        EntryId::Memory(MemoryEntryId::ExtVoltageControl),
+        0,
+        BoardInstances::all(),
+        PriorityLevels::from_level(PriorityLevel::Normal),
+        &ExtVoltageControl::new_disabled(),
+        &[],
+    )
+    .unwrap();
+    for board_instance in [0, 1] {
+        let mut console_out_control = ConsoleOutControl::default();
+        console_out_control
+            .abl_console_out_control
+            .set_enable_mem_pmu_logging(board_instance == 1);
+        apcb.insert_struct_entry(
+            EntryId::Memory(MemoryEntryId::ConsoleOutControl),
+            0,
+            BoardInstances::from_instance(board_instance).unwrap(),
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &console_out_control,
+            &[],
+        )
+        .unwrap();
+    }
+    let blobs = apcb.split_by_board().unwrap();
+    assert_eq!(blobs.keys().copied().collect::<Vec<_>>(), [0, 1]);
+    for (board_instance, blob) in blobs {
+        let apcb =
+            Apcb::load(Cow::from(blob), &ApcbIoOptions::default()).unwrap();
+        apcb.validate(None).unwrap();
+        let memory_group = apcb.group(GroupId::Memory).unwrap().unwrap();
+        let entries = memory_group.entries().collect::<Vec<_>>();
+        assert_eq!(entries.len(), 2);
+        for entry in &entries {
+            let expected_mask = match entry.id() {
+                EntryId::Memory(MemoryEntryId::ConsoleOutControl) => {
+                    BoardInstances::from_instance(board_instance).unwrap()
+                }
+                _ => BoardInstances::all(),
+            };
+            assert_eq!(entry.board_instance_mask(), expected_mask);
+        }
+        let (console_out_control, _) = entries
+            .iter()
+            .find_map(|entry| entry.body_as_struct::<ConsoleOutControl>())
+            .unwrap();
+        assert_eq!(
+            console_out_control
+                .abl_console_out_control
+                .enable_mem_pmu_logging()
+                .unwrap(),
+            board_instance == 1
+        );
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_redundant_entries() {
+    use amd_apcb::memory::{ConsoleOutControl, ExtVoltageControl};
+    use amd_apcb::{
+        Apcb, ApcbIoOptions, BoardInstances, EntryId, GroupId, MemoryEntryId,
+        PriorityLevel, PriorityLevels,
+    };
+    use std::borrow::Cow;
+
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    apcb.insert_group(GroupId::Memory, *b"MEMG").unwrap();
+    for board_instance in [0, 1, 2] {
+        let board_instance_mask =
+            BoardInstances::from_instance(board_instance).unwrap();
+        apcb.insert_struct_entry(
+            EntryId::Memory(MemoryEntryId::ExtVoltageControl),
+            0,
+            board_instance_mask,
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &ExtVoltageControl::new_disabled(),
+            &[],
+        )
+        .unwrap();
+        let mut console_out_control = ConsoleOutControl::default();
+        console_out_control
+            .abl_console_out_control
+            .set_enable_mem_pmu_logging(board_instance == 1);
+        apcb.insert_struct_entry(
+            EntryId::Memory(MemoryEntryId::ConsoleOutControl),
+            0,
+            board_instance_mask,
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &console_out_control,
+            &[],
+        )
+        .unwrap();
+    }
+    assert_eq!(
+        apcb.redundant_entries().unwrap(),
+        [
+            (
+                EntryId::Memory(MemoryEntryId::ConsoleOutControl),
+                vec![0b001, 0b100]
+            ),
+            (
+                EntryId::Memory(MemoryEntryId::ExtVoltageControl),
+                vec![0b001, 0b010, 0b100]
+            ),
+        ]
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_board_instances_from_instances() {
+    use amd_apcb::{BoardInstances, Error};
+
+    let mask = BoardInstances::from_instances(&[5, 0, 2, 2]).unwrap();
+    assert_eq!(u16::from(mask), 0b10_0101);
+    assert_eq!(mask.instances().collect::<Vec<_>>(), [0, 2, 5]);
+    assert_eq!(
+        BoardInstances::from_instances(&mask.instances().collect::<Vec<_>>())
+            .unwrap(),
+        mask
+    );
+    assert_eq!(
+        BoardInstances::all().instances().collect::<Vec<_>>(),
+        (0..16).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        BoardInstances::from_instances(&[]).unwrap().instances().count(),
+        0
+    );
+    assert!(matches!(
+        BoardInstances::from_instances(&[1, 16]),
+        Err(Error::EntryTypeMismatch)
+    ));
+}
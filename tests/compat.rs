@@ -58,3 +58,288 @@ fn test_invalid_FchConsoleOutMode_5() {
         Err(_) => {}
     };
 }
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_slink_region_interleaving_size() {
+    use amd_apcb::df::SlinkRegionInterleavingSize;
+    let size: SlinkRegionInterleavingSize =
+        serde_yaml::from_str("\"Auto\"").expect("configuration be valid JSON");
+    assert_eq!(size, SlinkRegionInterleavingSize::Auto);
+    let size: SlinkRegionInterleavingSize =
+        serde_yaml::from_str("\"256 B\"").expect("configuration be valid JSON");
+    assert_eq!(size, SlinkRegionInterleavingSize::_256B);
+    let size: SlinkRegionInterleavingSize =
+        serde_yaml::from_str("5").expect("configuration be valid JSON");
+    assert_eq!(size, SlinkRegionInterleavingSize::Reserved(5));
+    let s = serde_yaml::to_string(&size).unwrap();
+    let size: SlinkRegionInterleavingSize =
+        serde_yaml::from_str(&s).expect("configuration be valid JSON");
+    assert_eq!(size, SlinkRegionInterleavingSize::Reserved(5));
+    // Values of named variants are not Reserved.
+    assert!(serde_yaml::from_str::<SlinkRegionInterleavingSize>("0").is_err());
+    assert!(serde_yaml::from_str::<SlinkRegionInterleavingSize>("7").is_err());
+    assert_eq!(
+        SlinkRegionInterleavingSize::Reserved(7),
+        SlinkRegionInterleavingSize::Auto
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_baud_rate_unknown() {
+    use amd_apcb::BaudRate;
+    let baud_rate: BaudRate = serde_yaml::from_str("\"115200 Baud\"")
+        .expect("configuration be valid JSON");
+    assert_eq!(baud_rate, BaudRate::_115200Baud);
+    let baud_rate: BaudRate = serde_yaml::from_str("\"921600 Baud\"")
+        .expect("configuration be valid JSON");
+    assert_eq!(baud_rate, BaudRate::_921600Baud);
+    let baud_rate: BaudRate = serde_yaml::from_str("Unknown: 15")
+        .expect("configuration be valid JSON");
+    assert_eq!(baud_rate, BaudRate::Unknown(15));
+    assert!(serde_yaml::from_str::<BaudRate>("Unknown: 8").is_err());
+    assert!(serde_yaml::from_str::<BaudRate>("Unknown: 10").is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_unknown_passthrough() {
+    use amd_apcb::DfEntryId;
+    const CONFIG_STR: &str = "[SlinkConfig, 0xE5, 230]";
+    assert!(serde_yaml::from_str::<Vec<DfEntryId>>(CONFIG_STR).is_err());
+    let ids: Vec<DfEntryId> = amd_apcb::deserialize_with_unknown_passthrough(
+        serde_yaml::Deserializer::from_str(CONFIG_STR),
+    )
+    .expect("configuration be valid JSON");
+    assert_eq!(
+        ids,
+        [
+            DfEntryId::SlinkConfig,
+            DfEntryId::Unknown(0xE5),
+            DfEntryId::Unknown(230),
+        ]
+    );
+    assert!(
+        amd_apcb::deserialize_with_unknown_passthrough::<Vec<DfEntryId>, _>(
+            serde_yaml::Deserializer::from_str("[XgmiFoo]")
+        )
+        .is_err()
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_unknown_passthrough_in_struct() {
+    use amd_apcb::{DfEntryId, TokenEntryId};
+
+    #[derive(serde::Deserialize, Debug, PartialEq)]
+    #[serde(deny_unknown_fields)]
+    struct Config {
+        token_entry: TokenEntryId,
+        df_entries: Vec<DfEntryId>,
+        #[serde(default)]
+        note: Option<String>,
+    }
+
+    const CONFIG_STR: &str = "
+token_entry: 0xE5
+df_entries: [SlinkConfig, 0xE6]
+";
+    assert!(serde_yaml::from_str::<Config>(CONFIG_STR).is_err());
+    let config: Config = amd_apcb::deserialize_with_unknown_passthrough(
+        serde_yaml::Deserializer::from_str(CONFIG_STR),
+    )
+    .expect("configuration be valid YAML");
+    assert_eq!(
+        config,
+        Config {
+            token_entry: TokenEntryId::Unknown(0xE5),
+            df_entries: vec![DfEntryId::SlinkConfig, DfEntryId::Unknown(0xE6)],
+            note: None,
+        }
+    );
+    // Other fields are still checked.
+    assert!(amd_apcb::deserialize_with_unknown_passthrough::<Config, _>(
+        serde_yaml::Deserializer::from_str(
+            "token_entry: 0xE5\ndf_entries: []\nfoo: 1\n"
+        )
+    )
+    .is_err());
+}
+
+#[cfg(feature = "serde")]
+const COMMENTED_CONFIG_STR: &str = r#"
+{
+        version: "0.1.0",
+        header: {
+                signature: "APCB",
+                header_size: 0x0000,
+                version: 48,
+                unique_apcb_instance: 0x00000002,
+        },
+        v3_header_ext: {
+                signature: "ECB2",
+                _reserved_2: 0x0010,
+                struct_version: 18,
+                data_version: 256,
+                ext_header_size: 0x00000060,
+                _reserved_4: 0xffff,
+                _reserved_5: 0x0040,
+                data_offset: 0x0058,
+                header_checksum: 0x00,
+                signature_ending: "BCPA"
+        },
+        groups: [
+                {
+                        header: {
+                                signature: "MEMG",
+                                group_id: 0x1704,
+                                header_size: 16,
+                                version: 1,
+                                _reserved_: 0,
+                                group_size: 64,
+                        }
+                }
+        ],
+        entries: [
+                {
+                        header: {
+                                group_id: 0x1704,
+                                entry_id: 0x0053,
+                                entry_size: 48,
+                                instance_id: 0,
+                                context_type: "Struct",
+                                context_format: "Raw",
+                                unit_size: 0,
+                                priority_mask: { normal: true },
+                                key_size: 0,
+                                key_pos: 0,
+                                board_instance_mask: 0xffff,
+                        },
+                        ExtVoltageControl: {
+                                enabled: false,
+                                input_port: 0x84,
+                                output_port: 0x80,
+                                input_port_size: "32 Bit",
+                                output_port_size: "32 Bit",
+                                input_port_type: "FchHtIo",
+                                output_port_type: "FchHtIo",
+                                clear_acknowledgement: false,
+                        },
+                        _comment: "tuned for board rev B",
+                }
+        ]
+}
+"#;
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_entry_comment_roundtrip() {
+    use amd_apcb::{Apcb, BoardInstances, EntryId, MemoryEntryId};
+
+    let entry_id = EntryId::Memory(MemoryEntryId::ExtVoltageControl);
+    let apcb: Apcb<'_> = serde_yaml::from_str(COMMENTED_CONFIG_STR)
+        .expect("configuration be valid");
+    assert_eq!(
+        apcb.entry_comment(entry_id, 0, BoardInstances::all()),
+        Some("tuned for board rev B")
+    );
+    let dumped: serde_yaml::Value = serde_yaml::to_value(&apcb).unwrap();
+    assert_eq!(
+        dumped["entries"][0]["_comment"].as_str(),
+        Some("tuned for board rev B")
+    );
+
+    // The comment does not end up in the blob.
+    let uncommented: Apcb<'_> = serde_yaml::from_str(
+        &COMMENTED_CONFIG_STR
+            .replace("_comment: \"tuned for board rev B\",", ""),
+    )
+    .expect("configuration be valid");
+    assert_eq!(
+        uncommented.entry_comment(entry_id, 0, BoardInstances::all()),
+        None
+    );
+    assert_eq!(apcb.live_bytes().unwrap(), uncommented.live_bytes().unwrap());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_entry_comment_follows_entry() {
+    use amd_apcb::{
+        Apcb, BoardInstances, ContextType, EntryId, GroupId, MemoryEntryId,
+        PriorityLevel, PriorityLevels,
+    };
+
+    let entry_id = EntryId::Memory(MemoryEntryId::ExtVoltageControl);
+    let board_instance_mask = BoardInstances::from_instance(1).unwrap();
+    let mut apcb: Apcb<'_> = serde_yaml::from_str(COMMENTED_CONFIG_STR)
+        .expect("configuration be valid");
+    apcb.set_entry_board_mask(
+        entry_id,
+        0,
+        BoardInstances::all(),
+        board_instance_mask,
+    )
+    .unwrap();
+    assert_eq!(apcb.entry_comment(entry_id, 0, BoardInstances::all()), None);
+    assert_eq!(
+        apcb.entry_comment(entry_id, 0, board_instance_mask),
+        Some("tuned for board rev B")
+    );
+
+    apcb.delete_entry(entry_id, 0, board_instance_mask).unwrap();
+    assert_eq!(apcb.entry_comment(entry_id, 0, board_instance_mask), None);
+
+    let mut apcb: Apcb<'_> = serde_yaml::from_str(COMMENTED_CONFIG_STR)
+        .expect("configuration be valid");
+    let body = {
+        let group = apcb.group(GroupId::Memory).unwrap().unwrap();
+        let entry =
+            group.entry_exact(entry_id, 0, BoardInstances::all()).unwrap();
+        entry.body_as_buf().unwrap().to_vec()
+    };
+    apcb.upsert_entry(
+        entry_id,
+        0,
+        BoardInstances::all(),
+        ContextType::Struct,
+        PriorityLevels::from_level(PriorityLevel::Normal),
+        &body,
+    )
+    .unwrap();
+    assert_eq!(
+        apcb.entry_comment(entry_id, 0, BoardInstances::all()),
+        Some("tuned for board rev B")
+    );
+    apcb.delete_group(GroupId::Memory).unwrap();
+    assert_eq!(apcb.entry_comment(entry_id, 0, BoardInstances::all()), None);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_naples_abl_console_port_default() {
+    use amd_apcb::memory::NaplesAblConsoleOutControl;
+    const CONFIG: &str = r#"{
+        enable_console_logging: true,
+        enable_mem_flow_logging: true,
+        enable_mem_setreg_logging: true,
+        enable_mem_getreg_logging: false,
+        enable_mem_status_logging: false,
+        enable_mem_pmu_logging: false,
+        enable_mem_pmu_sram_read_logging: false,
+        enable_mem_pmu_sram_write_logging: false,
+        enable_mem_test_verbose_logging: false,
+    }"#;
+    // abl_console_port is optional (and defaults to port 0x80).
+    let control: NaplesAblConsoleOutControl =
+        serde_yaml::from_str(CONFIG).expect("configuration be valid");
+    assert_eq!(control, NaplesAblConsoleOutControl::default());
+    assert_eq!(control.abl_console_port().unwrap(), 0x80);
+    let control: NaplesAblConsoleOutControl = serde_yaml::from_str(
+        &CONFIG.replace("{", "{ abl_console_port: 0x3f8,"),
+    )
+    .expect("configuration be valid");
+    assert_eq!(control.abl_console_port().unwrap(), 0x3f8);
+}
@@ -0,0 +1,994 @@
+#[cfg(feature = "std")]
+#[test]
+fn test_sort_entries() {
+    use amd_apcb::memory::RdimmDdr4CadBusElement;
+    use amd_apcb::{
+        Apcb, ApcbIoOptions, BoardInstances, EntryId, GroupId, MemoryEntryId,
+        PriorityLevel, PriorityLevels,
+    };
+    use std::borrow::Cow;
+
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    apcb.insert_group(GroupId::Memory, *b"MEMG").unwrap();
+    for instance_id in 0..3 {
+        apcb.insert_struct_array_as_entry(
+            EntryId::Memory(MemoryEntryId::PsRdimmDdr4CadBus),
+            instance_id,
+            BoardInstances::all(),
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &[RdimmDdr4CadBusElement::default()],
+        )
+        .unwrap();
+    }
+    apcb.sort_entries(GroupId::Memory, |a, b| {
+        b.instance_id().cmp(&a.instance_id())
+    })
+    .unwrap();
+
+    let buf = apcb.save().unwrap().into_owned();
+    let apcb = Apcb::load(Cow::from(buf), &ApcbIoOptions::default()).unwrap();
+    let group = apcb.group(GroupId::Memory).unwrap().unwrap();
+    let instance_ids =
+        group.entries().map(|entry| entry.instance_id()).collect::<Vec<_>>();
+    assert_eq!(instance_ids, [2, 1, 0]);
+    for entry in group.entries() {
+        assert_eq!(
+            entry
+                .body_as_struct_array::<RdimmDdr4CadBusElement>()
+                .unwrap()
+                .iter()
+                .collect::<Vec<_>>(),
+            [&RdimmDdr4CadBusElement::default()]
+        );
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_espi_sio_init_elements() {
+    use amd_apcb::fch::{EspiSioAccessWidth, EspiSioInitElement};
+    use amd_apcb::{
+        Apcb, ApcbIoOptions, BoardInstances, EntryId, FchEntryId, GroupId,
+        PriorityLevel, PriorityLevels,
+    };
+    use std::borrow::Cow;
+
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    apcb.insert_group(GroupId::Fch, *b"FCHG").unwrap();
+    let devices = [
+        EspiSioInitElement::new(0x2e, EspiSioAccessWidth::_8Bit, 0xff, 0x55)
+            .unwrap(),
+        EspiSioInitElement::new(
+            0x4e,
+            EspiSioAccessWidth::_16Bit,
+            0xffff_0000,
+            0x1234,
+        )
+        .unwrap(),
+    ];
+    apcb.insert_struct_array_as_entry(
+        EntryId::Fch(FchEntryId::EspiSioInit),
+        0,
+        BoardInstances::all(),
+        PriorityLevels::from_level(PriorityLevel::Normal),
+        &devices,
+    )
+    .unwrap();
+
+    let buf = apcb.save().unwrap().into_owned();
+    let apcb = Apcb::load(Cow::from(buf), &ApcbIoOptions::default()).unwrap();
+    let group = apcb.group(GroupId::Fch).unwrap().unwrap();
+    let entry = group
+        .entry_exact(
+            EntryId::Fch(FchEntryId::EspiSioInit),
+            0,
+            BoardInstances::all(),
+        )
+        .unwrap();
+    let array = entry.body_as_struct_array::<EspiSioInitElement>().unwrap();
+    let elements = array.iter().collect::<Vec<_>>();
+    assert_eq!(elements, [&devices[0], &devices[1]]);
+    assert_eq!(elements[1].io_port().unwrap(), 0x4e);
+    assert_eq!(elements[1].access_width().unwrap(), EspiSioAccessWidth::_16Bit);
+    assert_eq!(elements[1].data_mask().unwrap(), 0xffff_0000);
+    assert_eq!(elements[1].data_or().unwrap(), 0x1234);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_espi_sio_init_element_edit() {
+    use amd_apcb::fch::{EspiSioAccessWidth, EspiSioInitElement};
+    use amd_apcb::{
+        Apcb, ApcbIoOptions, BoardInstances, EntryId, FchEntryId, GroupId,
+        PriorityLevel, PriorityLevels,
+    };
+    use std::borrow::Cow;
+
+    let entry_id = EntryId::Fch(FchEntryId::EspiSioInit);
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    apcb.insert_group(GroupId::Fch, *b"FCHG").unwrap();
+    let devices = [
+        EspiSioInitElement::new(0x2e, EspiSioAccessWidth::_8Bit, 0xff, 0x55)
+            .unwrap(),
+        EspiSioInitElement::new(0x4e, EspiSioAccessWidth::_32Bit, 0xff, 0)
+            .unwrap(),
+    ];
+    apcb.insert_struct_array_as_entry(
+        entry_id,
+        0,
+        BoardInstances::all(),
+        PriorityLevels::from_level(PriorityLevel::Normal),
+        &devices,
+    )
+    .unwrap();
+    let before = apcb.save_no_inc().unwrap().into_owned();
+
+    let mut apcb =
+        Apcb::load(Cow::from(before.clone()), &ApcbIoOptions::default())
+            .unwrap();
+    let mut group = apcb.group_mut(GroupId::Fch).unwrap().unwrap();
+    let mut entry =
+        group.entry_exact_mut(entry_id, 0, BoardInstances::all()).unwrap();
+    let mut array =
+        entry.body_as_struct_array_mut::<EspiSioInitElement>().unwrap();
+    let element = array.iter_mut().nth(1).unwrap();
+    element.set_data_or(0xAA55_1234);
+    let after = apcb.save_no_inc().unwrap().into_owned();
+
+    // Only the checksum and the four bytes of data_or of the second element
+    // may differ.
+    let data_or_offset = after
+        .windows(4)
+        .position(|w| w == 0xAA55_1234u32.to_le_bytes())
+        .unwrap();
+    assert_eq!(before.len(), after.len());
+    for (i, (a, b)) in before.iter().zip(after.iter()).enumerate() {
+        if i != 16 && !(data_or_offset..data_or_offset + 4).contains(&i) {
+            assert_eq!(a, b, "byte {i} changed");
+        }
+    }
+    assert_eq!(&before[data_or_offset..data_or_offset + 4], &[0; 4]);
+
+    let apcb = Apcb::load(Cow::from(after), &ApcbIoOptions::default()).unwrap();
+    let group = apcb.group(GroupId::Fch).unwrap().unwrap();
+    let entry = group.entry_exact(entry_id, 0, BoardInstances::all()).unwrap();
+    let array = entry.body_as_struct_array::<EspiSioInitElement>().unwrap();
+    let elements = array.iter().collect::<Vec<_>>();
+    assert_eq!(elements[0], &devices[0]);
+    assert_eq!(elements[1].io_port().unwrap(), 0x4e);
+    assert_eq!(elements[1].data_or().unwrap(), 0xAA55_1234);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_espi_init_struct_entry() {
+    use amd_apcb::fch::{
+        EspiInit, EspiInitClockFrequency, EspiInitIoMode, EspiInitIoRange,
+    };
+    use amd_apcb::{
+        Apcb, ApcbIoOptions, BoardInstances, EntryId, FchEntryId, GroupId,
+        PriorityLevel, PriorityLevels,
+    };
+    use std::borrow::Cow;
+
+    let entry_id = EntryId::Fch(FchEntryId::EspiInit);
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    apcb.insert_group(GroupId::Fch, *b"FCHG").unwrap();
+    let mut espi_init = EspiInit::default();
+    espi_init.set_espi_enabled(true);
+    espi_init.set_clock_frequency(EspiInitClockFrequency::_33_33MHz);
+    espi_init.set_io_mode(EspiInitIoMode::Quad);
+    espi_init.set_io80_decoding_enabled(true);
+    espi_init.set_io_range(0, Some(EspiInitIoRange { base: 0xca2, size: 2 }));
+    espi_init.set_irq_mask(0x0000_0010);
+    apcb.insert_struct_entry(
+        entry_id,
+        0,
+        BoardInstances::all(),
+        PriorityLevels::from_level(PriorityLevel::Normal),
+        &espi_init,
+        &[],
+    )
+    .unwrap();
+
+    let buf = apcb.save().unwrap().into_owned();
+    let apcb = Apcb::load(Cow::from(buf), &ApcbIoOptions::default()).unwrap();
+    let group = apcb.group(GroupId::Fch).unwrap().unwrap();
+    let entry = group.entry_exact(entry_id, 0, BoardInstances::all()).unwrap();
+    let (header, _) = entry.body_as_struct::<EspiInit>().unwrap();
+    assert_eq!(header, &espi_init);
+    assert!(header.espi_enabled().unwrap());
+    assert_eq!(
+        header.clock_frequency().unwrap(),
+        EspiInitClockFrequency::_33_33MHz
+    );
+    assert_eq!(header.io_mode().unwrap(), EspiInitIoMode::Quad);
+    assert!(header.io80_decoding_enabled().unwrap());
+    assert!(!header.io6064_decoding_enabled().unwrap());
+    let io_range = header.io_range(0).unwrap().unwrap();
+    assert_eq!((io_range.base, io_range.size), (0xca2, 2));
+    assert!(header.io_range(1).unwrap().is_none());
+    assert_eq!(header.irq_mask().unwrap(), 0x0000_0010);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_xgmi_phy_override_elements() {
+    use amd_apcb::df::XgmiPhyOverrideElement;
+    use amd_apcb::{
+        Apcb, ApcbIoOptions, BoardInstances, DfEntryId, EntryId, GroupId,
+        PriorityLevel, PriorityLevels,
+    };
+    use std::borrow::Cow;
+
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    apcb.insert_group(GroupId::Df, *b"DFG ").unwrap();
+    let overrides = [
+        XgmiPhyOverrideElement::new(0, 3, 0x7a),
+        XgmiPhyOverrideElement::new(5, 1, 0x1234_5678),
+    ];
+    apcb.insert_struct_array_as_entry(
+        EntryId::Df(DfEntryId::XgmiPhyOverride),
+        0,
+        BoardInstances::all(),
+        PriorityLevels::from_level(PriorityLevel::Normal),
+        &overrides,
+    )
+    .unwrap();
+
+    let buf = apcb.save().unwrap().into_owned();
+    let apcb = Apcb::load(Cow::from(buf), &ApcbIoOptions::default()).unwrap();
+    let group = apcb.group(GroupId::Df).unwrap().unwrap();
+    let entry = group
+        .entry_exact(
+            EntryId::Df(DfEntryId::XgmiPhyOverride),
+            0,
+            BoardInstances::all(),
+        )
+        .unwrap();
+    let array = entry.body_as_struct_array::<XgmiPhyOverrideElement>().unwrap();
+    let elements = array.iter().collect::<Vec<_>>();
+    assert_eq!(elements, [&overrides[0], &overrides[1]]);
+    assert_eq!(elements[1].lane().unwrap(), 5);
+    assert_eq!(elements[1].parameter_id().unwrap(), 1);
+    assert_eq!(elements[1].value().unwrap(), 0x1234_5678);
+
+    // The layout is provisional, so serde keeps the body opaque.
+    #[cfg(feature = "serde")]
+    {
+        let dumped: serde_yaml::Value = serde_yaml::to_value(&apcb).unwrap();
+        let entry = &dumped["entries"][0];
+        assert!(entry.get("XgmiPhyOverrideElement").is_none());
+        assert!(entry.get("struct_body").is_some());
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_diagnostic_dump() {
+    use amd_apcb::memory::{ExtVoltageControl, MemDfeSearchElement32};
+    use amd_apcb::{
+        Apcb, ApcbContext, ApcbIoOptions, BoardInstances, ContextType, EntryId,
+        GroupId, MemDfeSearchVersion, MemoryEntryId, PriorityLevel,
+        PriorityLevels, PspEntryId,
+    };
+    use std::borrow::Cow;
+
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    apcb.insert_group(GroupId::Psp, *b"PSPG").unwrap();
+    apcb.insert_group(GroupId::Memory, *b"MEMG").unwrap();
+    apcb.insert_struct_entry(
+        EntryId::Memory(MemoryEntryId::ExtVoltageControl),
+        0,
+        BoardInstances::all(),
+        PriorityLevels::from_level(PriorityLevel::Normal),
+        &ExtVoltageControl::new_disabled(),
+        &[],
+    )
+    .unwrap();
+    apcb.upsert_entry(
+        EntryId::Psp(PspEntryId::BoardIdGettingMethod),
+        0,
+        BoardInstances::all(),
+        ContextType::Struct,
+        PriorityLevels::from_level(PriorityLevel::Normal),
+        &[0xa5; 4],
+    )
+    .unwrap();
+
+    let dump = apcb.diagnostic_dump().unwrap();
+    assert!(dump.contains("group Psp"));
+    assert!(dump.contains("entry Psp(BoardIdGettingMethod)"));
+    assert!(dump.contains("(not modeled)"));
+    assert!(dump.contains(" a5 a5 a5 a5"));
+    assert!(dump.contains("entry Memory(ExtVoltageControl)"));
+    assert!(dump.contains("decoded: ExtVoltageControl {"));
+
+    // MemDfeSearch is only modeled once the context says which layout.
+    let options = ApcbIoOptions::builder()
+        .with_context(
+            ApcbContext::builder()
+                .with_mem_dfe_search_version(Some(MemDfeSearchVersion::Genoa2))
+                .build(),
+        )
+        .build();
+    let mut apcb =
+        Apcb::create(Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]), 42, &options)
+            .unwrap();
+    apcb.insert_group(GroupId::Memory, *b"MEMG").unwrap();
+    apcb.insert_struct_array_as_entry(
+        EntryId::Memory(MemoryEntryId::MemDfeSearch),
+        0,
+        BoardInstances::all(),
+        PriorityLevels::from_level(PriorityLevel::Normal),
+        &[MemDfeSearchElement32::default()],
+    )
+    .unwrap();
+    let dump = apcb.diagnostic_dump().unwrap();
+    assert!(dump.contains("decoded: [MemDfeSearchElement32 {"));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_rdimm_ddr5_bus_element_1240_round_trip() {
+    use amd_apcb::memory::{RdimmDdr5BusElement, RdimmDdr5BusElement1240};
+    use amd_apcb::{
+        Apcb, ApcbIoOptions, BoardInstances, EntryId, GroupId, MemoryEntryId,
+        PriorityLevel, PriorityLevels,
+    };
+    use std::borrow::Cow;
+
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    apcb.insert_group(GroupId::Memory, *b"MEMG").unwrap();
+    // 17 * 1240 Byte is also a multiple of the older 136 Byte layout.
+    let elements = (0..17)
+        .map(|i| {
+            RdimmDdr5BusElement1240::new(2400 + 100 * i, 1 + (i % 2) as u8)
+        })
+        .collect::<Vec<_>>();
+    apcb.insert_struct_array_as_entry(
+        EntryId::Memory(MemoryEntryId::PsRdimmDdr5Bus),
+        0,
+        BoardInstances::all(),
+        PriorityLevels::from_level(PriorityLevel::Normal),
+        &elements,
+    )
+    .unwrap();
+
+    let buf = apcb.save().unwrap().into_owned();
+    let apcb =
+        Apcb::load(Cow::from(buf.clone()), &ApcbIoOptions::default()).unwrap();
+    let group = apcb.group(GroupId::Memory).unwrap().unwrap();
+    let entry = group
+        .entry_exact(
+            EntryId::Memory(MemoryEntryId::PsRdimmDdr5Bus),
+            0,
+            BoardInstances::all(),
+        )
+        .unwrap();
+    assert!(entry.body_as_struct_array::<RdimmDdr5BusElement>().is_none());
+    let items =
+        entry.body_as_struct_array::<RdimmDdr5BusElement1240>().unwrap();
+    assert_eq!(items.iter().copied().collect::<Vec<_>>(), elements);
+
+    assert_eq!(apcb.save_no_inc().unwrap().into_owned(), buf);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_dimm_info_smbus_struct_array_round_trip() {
+    use amd_apcb::memory::DimmInfoSmbusElement;
+    use amd_apcb::{
+        Apcb, ApcbIoOptions, BoardInstances, EntryId, Error, GroupId,
+        MemoryEntryId, PriorityLevel, PriorityLevels,
+    };
+    use std::borrow::Cow;
+
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    apcb.insert_group(GroupId::Memory, *b"MEMG").unwrap();
+    let slots = [
+        DimmInfoSmbusElement::new_slot(0, 0, 0, 0xa0, Some(0xe0), None, None)
+            .unwrap(),
+        DimmInfoSmbusElement::new_slot(0, 1, 0, 0xa2, Some(0xe0), None, None)
+            .unwrap(),
+        DimmInfoSmbusElement::new_slot(1, 0, 1, 0xa4, None, None, None)
+            .unwrap(),
+    ];
+    assert!(matches!(
+        apcb.insert_struct_array_as_entry(
+            EntryId::Memory(MemoryEntryId::PsRdimmDdr4CadBus),
+            0,
+            BoardInstances::all(),
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &slots,
+        ),
+        Err(Error::EntryTypeMismatch)
+    ));
+    apcb.insert_struct_array_as_entry(
+        EntryId::Memory(MemoryEntryId::DimmInfoSmbus),
+        0,
+        BoardInstances::all(),
+        PriorityLevels::from_level(PriorityLevel::Normal),
+        &slots,
+    )
+    .unwrap();
+
+    let buf = apcb.save().unwrap().into_owned();
+    let apcb = Apcb::load(Cow::from(buf), &ApcbIoOptions::default()).unwrap();
+    let group = apcb.group(GroupId::Memory).unwrap().unwrap();
+    assert_eq!(group.entries().count(), 1);
+    let entry = group
+        .entry_exact(
+            EntryId::Memory(MemoryEntryId::DimmInfoSmbus),
+            0,
+            BoardInstances::all(),
+        )
+        .unwrap();
+    assert_eq!(
+        entry.body_as_buf().unwrap().len(),
+        3 * core::mem::size_of::<DimmInfoSmbusElement>()
+    );
+    let items = entry.body_as_struct_array::<DimmInfoSmbusElement>().unwrap();
+    assert_eq!(
+        items.iter().collect::<Vec<_>>(),
+        [&slots[0], &slots[1], &slots[2]]
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_insert_early_pcie_config() {
+    use amd_apcb::gnb::{
+        EarlyPcieConfigBody, EarlyPcieConfigElement, EarlyPcieLinkSpeed,
+    };
+    use amd_apcb::{
+        Apcb, ApcbIoOptions, BoardInstances, EntryId, Error, GnbEntryId,
+        GroupId,
+    };
+    use std::borrow::Cow;
+
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    let bodies = [
+        EarlyPcieConfigBody::default().with_start_lane(0).with_end_lane(15),
+        EarlyPcieConfigBody::default()
+            .with_start_lane(16)
+            .with_end_lane(23)
+            .with_link_speed(EarlyPcieLinkSpeed::Gen4),
+    ];
+    assert!(matches!(
+        apcb.insert_early_pcie_config(BoardInstances::all(), &bodies),
+        Err(Error::GroupNotFound)
+    ));
+    apcb.insert_group(GroupId::Gnb, *b"GNBG").unwrap();
+    apcb.insert_early_pcie_config(BoardInstances::all(), &bodies).unwrap();
+    apcb.insert_early_pcie_config(
+        BoardInstances::from_instance(1).unwrap(),
+        &[],
+    )
+    .unwrap();
+
+    let buf = apcb.save().unwrap().into_owned();
+    let apcb = Apcb::load(Cow::from(buf), &ApcbIoOptions::default()).unwrap();
+    let group = apcb.group(GroupId::Gnb).unwrap().unwrap();
+    let entry = group
+        .entry_exact(
+            EntryId::Gnb(GnbEntryId::EarlyPcieConfig),
+            0,
+            BoardInstances::all(),
+        )
+        .unwrap();
+    let elements =
+        entry.body_as_struct_array::<EarlyPcieConfigElement>().unwrap();
+    let elements = elements
+        .iter()
+        .map(|element| element.body().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(elements, bodies);
+
+    let entry = group
+        .entry_exact(
+            EntryId::Gnb(GnbEntryId::EarlyPcieConfig),
+            0,
+            BoardInstances::from_instance(1).unwrap(),
+        )
+        .unwrap();
+    assert_eq!(entry.body_as_buf().unwrap().len(), 0);
+    let elements =
+        entry.body_as_struct_array::<EarlyPcieConfigElement>().unwrap();
+    assert_eq!(elements.iter().count(), 0);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_id_display() {
+    use amd_apcb::{
+        EntryId, GroupId, MemoryEntryId, PspEntryId, RawEntryId, TokenEntryId,
+    };
+
+    assert_eq!(GroupId::Memory.to_string(), "Memory (0x1704)");
+    assert_eq!(GroupId::Unknown(0x1800).to_string(), "Unknown (0x1800)");
+    assert_eq!(
+        EntryId::Memory(MemoryEntryId::PsRdimmDdr5MaxFreq).to_string(),
+        "Memory/PsRdimmDdr5MaxFreq (0x1704/0x8E)"
+    );
+    assert_eq!(
+        EntryId::Psp(PspEntryId::Unknown(0x99)).to_string(),
+        "Psp/Unknown (0x1701/0x99)"
+    );
+    assert_eq!(
+        EntryId::Unknown(0x1800, RawEntryId::Unknown(0x123)).to_string(),
+        "Unknown/Unknown (0x1800/0x123)"
+    );
+    assert_eq!(
+        EntryId::Token(TokenEntryId::Dword).to_string(),
+        "Token/Dword (0x3000/0x04)"
+    );
+    assert_eq!(TokenEntryId::Byte.to_string(), "Byte (0x01)");
+    assert_eq!(TokenEntryId::Unknown(0x8).to_string(), "Unknown (0x08)");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_ddr_post_package_repair() {
+    use amd_apcb::memory::{
+        DdrPostPackageRepairBody, DdrPostPackageRepairElement,
+    };
+    use amd_apcb::{
+        Apcb, ApcbIoOptions, BoardInstances, EntryId, GroupId, MemoryEntryId,
+        PriorityLevel, PriorityLevels,
+    };
+    use std::borrow::Cow;
+
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    apcb.insert_group(GroupId::Memory, *b"MEMG").unwrap();
+    let board_instance_mask = BoardInstances::all();
+    apcb.set_ddr_post_package_repair(
+        board_instance_mask,
+        &[DdrPostPackageRepairBody::new().with_row(1)],
+    )
+    .unwrap();
+    let repairs = [
+        DdrPostPackageRepairBody::new()
+            .with_bank(3)
+            .with_row(0x1_2345)
+            .with_column(0x12)
+            .with_socket(1)
+            .with_channel(5)
+            .with_valid(true),
+        DdrPostPackageRepairBody::new()
+            .with_bank(7)
+            .with_chip_select(2)
+            .with_row(0x42)
+            .with_hard_repair(true),
+    ];
+    apcb.set_ddr_post_package_repair(board_instance_mask, &repairs).unwrap();
+
+    let buf = apcb.save().unwrap().into_owned();
+    let apcb = Apcb::load(Cow::from(buf), &ApcbIoOptions::default()).unwrap();
+    let group = apcb.group(GroupId::Memory).unwrap().unwrap();
+    assert_eq!(group.entries().count(), 1);
+    let entry = group
+        .entry_exact(
+            EntryId::Memory(MemoryEntryId::DdrPostPackageRepair),
+            0,
+            board_instance_mask,
+        )
+        .unwrap();
+    assert_eq!(
+        entry.priority_mask(),
+        u8::from(PriorityLevels::from_level(PriorityLevel::Normal))
+    );
+    let elements =
+        entry.body_as_struct_array::<DdrPostPackageRepairElement>().unwrap();
+    let elements = elements.iter().collect::<Vec<_>>();
+    assert_eq!(elements.len(), 3);
+    assert!(elements[2].body().is_none());
+
+    let result = apcb.ddr_post_package_repair(board_instance_mask).unwrap();
+    assert_eq!(
+        result.iter().map(|repair| u64::from(*repair)).collect::<Vec<_>>(),
+        [u64::from(repairs[0]), u64::from(repairs[1].with_valid(true))]
+    );
+    assert_eq!(result[1].bank(), 7);
+    assert_eq!(result[1].row(), 0x42);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_sort_entries_by_key() {
+    use amd_apcb::memory::DimmInfoSmbusElement;
+    use amd_apcb::{
+        Apcb, ApcbIoOptions, BoardInstances, EntryId, GroupId, MemoryEntryId,
+        PriorityLevel, PriorityLevels,
+    };
+    use std::borrow::Cow;
+
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    apcb.insert_group(GroupId::Psp, *b"PSPG").unwrap();
+    apcb.insert_group(GroupId::Memory, *b"MEMG").unwrap();
+    let slot = |instance_id: u16| {
+        DimmInfoSmbusElement::new_slot(
+            0,
+            instance_id as u8,
+            0,
+            0xa0,
+            None,
+            None,
+            None,
+        )
+        .unwrap()
+    };
+    for instance_id in 0..3 {
+        apcb.insert_struct_array_as_entry(
+            EntryId::Memory(MemoryEntryId::DimmInfoSmbus),
+            instance_id,
+            BoardInstances::all(),
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &[slot(instance_id)],
+        )
+        .unwrap();
+    }
+    // Scramble.
+    apcb.sort_entries(GroupId::Memory, |a, b| {
+        b.instance_id().cmp(&a.instance_id())
+    })
+    .unwrap();
+    let instance_ids = |apcb: &Apcb| {
+        apcb.group(GroupId::Memory)
+            .unwrap()
+            .unwrap()
+            .entries()
+            .map(|entry| entry.instance_id())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(instance_ids(&apcb), [2, 1, 0]);
+
+    for mut group in apcb.groups_mut().unwrap() {
+        group
+            .sort_entries_by_key(|entry| {
+                (
+                    entry.id().type_id(),
+                    entry.instance_id(),
+                    u16::from(entry.board_instance_mask()),
+                )
+            })
+            .unwrap();
+    }
+
+    let buf = apcb.save().unwrap().into_owned();
+    let apcb = Apcb::load(Cow::from(buf), &ApcbIoOptions::default()).unwrap();
+    assert_eq!(instance_ids(&apcb), [0, 1, 2]);
+    for entry in apcb.group(GroupId::Memory).unwrap().unwrap().entries() {
+        assert_eq!(
+            entry
+                .body_as_struct_array::<DimmInfoSmbusElement>()
+                .unwrap()
+                .iter()
+                .collect::<Vec<_>>(),
+            [&slot(entry.instance_id())]
+        );
+    }
+    assert_eq!(apcb.group(GroupId::Psp).unwrap().unwrap().entries().count(), 0);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_mem_dfe_search_versions() {
+    use amd_apcb::memory::{MemDfeSearchElement32, MemDfeSearchElement36};
+    use amd_apcb::{
+        Apcb, ApcbIoOptions, BoardInstances, EntryId, Error, GroupId,
+        MemDfeSearchElements, MemDfeSearchVersion, MemoryEntryId,
+        PriorityLevel, PriorityLevels,
+    };
+    use std::borrow::Cow;
+
+    let entry_id = EntryId::Memory(MemoryEntryId::MemDfeSearch);
+    let mask32 = BoardInstances::from_instance(0).unwrap();
+    let mask36 = BoardInstances::from_instance(1).unwrap();
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    apcb.insert_group(GroupId::Memory, *b"MEMG").unwrap();
+    apcb.insert_struct_array_as_entry(
+        entry_id,
+        0,
+        mask32,
+        PriorityLevels::from_level(PriorityLevel::Normal),
+        &[MemDfeSearchElement32::default(); 2],
+    )
+    .unwrap();
+    apcb.insert_struct_array_as_entry(
+        entry_id,
+        0,
+        mask36,
+        PriorityLevels::from_level(PriorityLevel::Normal),
+        &[MemDfeSearchElement36::default(); 3],
+    )
+    .unwrap();
+
+    let buf = apcb.save().unwrap().into_owned();
+    let apcb = Apcb::load(Cow::from(buf), &ApcbIoOptions::default()).unwrap();
+    match apcb.mem_dfe_search(mask32, MemDfeSearchVersion::Genoa2).unwrap() {
+        MemDfeSearchElements::Element32(elements) => {
+            let elements = elements.iter().collect::<Vec<_>>();
+            assert_eq!(elements, [&MemDfeSearchElement32::default(); 2]);
+        }
+        MemDfeSearchElements::Element36(_) => panic!("wrong layout"),
+    }
+    match apcb.mem_dfe_search(mask36, MemDfeSearchVersion::Turin1).unwrap() {
+        MemDfeSearchElements::Element36(elements) => {
+            let elements = elements.iter().collect::<Vec<_>>();
+            assert_eq!(elements, [&MemDfeSearchElement36::default(); 3]);
+        }
+        MemDfeSearchElements::Element32(_) => panic!("wrong layout"),
+    }
+    assert!(matches!(
+        apcb.mem_dfe_search(mask32, MemDfeSearchVersion::Turin1),
+        Err(Error::EntryTypeMismatch)
+    ));
+    assert!(matches!(
+        apcb.mem_dfe_search(mask36, MemDfeSearchVersion::Genoa2),
+        Err(Error::EntryTypeMismatch)
+    ));
+    assert!(matches!(
+        apcb.mem_dfe_search(mask32, MemDfeSearchVersion::Genoa1),
+        Err(Error::EntryTypeMismatch)
+    ));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_delete_entry_exact() {
+    use amd_apcb::memory::RdimmDdr4CadBusElement;
+    use amd_apcb::{
+        Apcb, ApcbIoOptions, BoardInstances, EntryId, Error, GroupId,
+        MemoryEntryId, PriorityLevel, PriorityLevels,
+    };
+    use std::borrow::Cow;
+
+    let entry_id = EntryId::Memory(MemoryEntryId::PsRdimmDdr4CadBus);
+    let board0 = BoardInstances::from_instance(0).unwrap();
+    let boards01 = BoardInstances::from_instances(&[0, 1]).unwrap();
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    apcb.insert_group(GroupId::Memory, *b"MEMG").unwrap();
+    for (mask, dimm_slots_per_channel) in [(board0, 1), (boards01, 2)] {
+        let mut element = RdimmDdr4CadBusElement::default();
+        element.set_dimm_slots_per_channel(dimm_slots_per_channel);
+        apcb.insert_struct_array_as_entry(
+            entry_id,
+            0,
+            mask,
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &[element],
+        )
+        .unwrap();
+    }
+
+    // Overlapping, but not equal, masks do not match.
+    let board1 = BoardInstances::from_instance(1).unwrap();
+    assert!(matches!(
+        apcb.delete_entry_exact(entry_id, 0, board1),
+        Err(Error::EntryNotFound { .. })
+    ));
+    assert!(matches!(
+        apcb.delete_entry(entry_id, 0, BoardInstances::all()),
+        Err(Error::EntryNotFound { .. })
+    ));
+
+    apcb.delete_entry_exact(entry_id, 0, boards01).unwrap();
+    let buf = apcb.save().unwrap().into_owned();
+    let mut apcb =
+        Apcb::load(Cow::from(buf), &ApcbIoOptions::default()).unwrap();
+    let group = apcb.group(GroupId::Memory).unwrap().unwrap();
+    assert_eq!(group.entries().count(), 1);
+    assert!(group.entry_exact(entry_id, 0, boards01).is_none());
+    let entry = group.entry_exact(entry_id, 0, board0).unwrap();
+    let elements =
+        entry.body_as_struct_array::<RdimmDdr4CadBusElement>().unwrap();
+    let element = elements.iter().next().unwrap();
+    assert_eq!(element.dimm_slots_per_channel().unwrap(), 1);
+
+    apcb.delete_entry_exact(entry_id, 0, board0).unwrap();
+    let group = apcb.group(GroupId::Memory).unwrap().unwrap();
+    assert_eq!(group.entries().count(), 0);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_compact_into() {
+    use amd_apcb::memory::RdimmDdr4CadBusElement;
+    use amd_apcb::{
+        Apcb, ApcbIoOptions, BoardInstances, EntryId, Error, GroupId,
+        MemoryEntryId, PriorityLevel, PriorityLevels,
+    };
+    use std::borrow::Cow;
+
+    let entry_id = EntryId::Memory(MemoryEntryId::PsRdimmDdr4CadBus);
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    apcb.insert_group(GroupId::Memory, *b"MEMG").unwrap();
+    let empty_size = apcb.used_size().unwrap();
+    for instance_id in 0..4 {
+        apcb.insert_struct_array_as_entry(
+            entry_id,
+            instance_id,
+            BoardInstances::all(),
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &[RdimmDdr4CadBusElement::default(); 4],
+        )
+        .unwrap();
+    }
+    let full_size = apcb.used_size().unwrap();
+    assert!(full_size > empty_size);
+    for instance_id in 1..4 {
+        apcb.delete_entry(entry_id, instance_id, BoardInstances::all())
+            .unwrap();
+    }
+    let used_size = apcb.used_size().unwrap();
+    assert!(used_size < full_size);
+    assert_eq!(used_size - empty_size, (full_size - empty_size) / 4);
+
+    let mut out = vec![0u8; used_size - 1];
+    assert!(matches!(apcb.compact_into(&mut out), Err(Error::OutOfSpace)));
+    let mut out = vec![0u8; Apcb::MAX_SIZE];
+    assert_eq!(apcb.compact_into(&mut out).unwrap(), used_size);
+    out.truncate(used_size);
+    let mut written = Vec::new();
+    apcb.to_writer(&mut written).unwrap();
+    assert_eq!(written, out);
+    let compacted =
+        Apcb::load(Cow::from(out), &ApcbIoOptions::default()).unwrap();
+    assert_eq!(compacted.used_size().unwrap(), used_size);
+    assert_eq!(compacted.unique_apcb_instance().unwrap(), 42);
+    let group = compacted.group(GroupId::Memory).unwrap().unwrap();
+    let instance_ids =
+        group.entries().map(|entry| entry.instance_id()).collect::<Vec<_>>();
+    assert_eq!(instance_ids, [0]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_replace_entry_out_of_space() {
+    use amd_apcb::memory::DdrPostPackageRepairBody;
+    use amd_apcb::{
+        Apcb, ApcbIoOptions, BoardInstances, ContextType, EntryId, Error,
+        GroupId, PriorityLevel, PriorityLevels, PspEntryId,
+    };
+    use std::borrow::Cow;
+
+    let entry_id = EntryId::Psp(PspEntryId::BoardIdGettingMethod);
+    // Headers, two groups and an entry with a body of 16 bytes--plus 64
+    // Bytes of room.
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; 128 + 2 * 16 + 32 + 64]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    apcb.insert_group(GroupId::Psp, *b"PSPG").unwrap();
+    apcb.insert_group(GroupId::Memory, *b"MEMG").unwrap();
+    apcb.upsert_entry(
+        entry_id,
+        0,
+        BoardInstances::all(),
+        ContextType::Struct,
+        PriorityLevels::from_level(PriorityLevel::Normal),
+        &[1; 16],
+    )
+    .unwrap();
+    assert!(matches!(
+        apcb.upsert_entry(
+            entry_id,
+            0,
+            BoardInstances::all(),
+            ContextType::Struct,
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &[2; 96],
+        ),
+        Err(Error::OutOfSpace)
+    ));
+    let group = apcb.group(GroupId::Psp).unwrap().unwrap();
+    let entry = group.entry_exact(entry_id, 0, BoardInstances::all()).unwrap();
+    assert_eq!(entry.body_as_buf().unwrap(), [1; 16]);
+
+    // Growing into the free space works.
+    apcb.upsert_entry(
+        entry_id,
+        0,
+        BoardInstances::all(),
+        ContextType::Struct,
+        PriorityLevels::from_level(PriorityLevel::Normal),
+        &[3; 80],
+    )
+    .unwrap();
+    let group = apcb.group(GroupId::Psp).unwrap().unwrap();
+    let entry = group.entry_exact(entry_id, 0, BoardInstances::all()).unwrap();
+    assert_eq!(entry.body_as_buf().unwrap(), [3; 80]);
+
+    let mut repair = DdrPostPackageRepairBody::default();
+    repair.set_bank(3);
+    repair.set_valid(true);
+    assert!(matches!(
+        apcb.set_ddr_post_package_repair(BoardInstances::all(), &[repair]),
+        Err(Error::OutOfSpace)
+    ));
+    apcb.delete_entry(entry_id, 0, BoardInstances::all()).unwrap();
+    apcb.set_ddr_post_package_repair(BoardInstances::all(), &[repair]).unwrap();
+    assert!(matches!(
+        apcb.set_ddr_post_package_repair(
+            BoardInstances::all(),
+            &[DdrPostPackageRepairBody::default(); 16]
+        ),
+        Err(Error::OutOfSpace)
+    ));
+    let repairs = apcb.ddr_post_package_repair(BoardInstances::all()).unwrap();
+    assert_eq!(repairs.len(), 1);
+    assert_eq!(repairs[0].bank(), 3);
+}
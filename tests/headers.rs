@@ -182,3 +182,320 @@ fn test_unknown_field() {
         }
     };
 }
+
+#[cfg(feature = "serde")]
+const CONSOLE_CONFIG_STR: &str = r#"
+{
+        version: "0.1.0",
+        header: {
+                signature: "APCB",
+                header_size: 0x0000,
+                version: 48,
+                unique_apcb_instance: 0x00000002,
+        },
+        groups: [
+                {
+                        header: {
+                                signature: "MEMG",
+                                group_id: 0x1704,
+                                header_size: 16,
+                                version: 1,
+                                group_size: 0,
+                        },
+                },
+        ],
+        entries: [
+                {
+                        header: {
+                                group_id: 0x1704,
+                                entry_id: 0x0050,
+                                entry_size: 36,
+                                instance_id: 0,
+                                context_type: "Struct",
+                                context_format: "Raw",
+                                unit_size: 0,
+                                priority_mask: {
+                                        normal: true,
+                                },
+                                key_size: 0,
+                                key_pos: 0,
+                                board_instance_mask: 0xffff,
+                        },
+                        ConsoleOutControl: {
+                                abl_console_out_control: {
+                                        enable_console_logging: true,
+                                        enable_mem_flow_logging: true,
+                                        enable_mem_setreg_logging: true,
+                                        enable_mem_getreg_logging: false,
+                                        enable_mem_status_logging: false,
+                                        enable_mem_pmu_logging: false,
+                                        enable_mem_pmu_sram_read_logging: false,
+                                        enable_mem_pmu_sram_write_logging: false,
+                                        enable_mem_test_verbose_logging: false,
+                                        enable_mem_basic_output_logging: false,
+                                },
+                                abl_breakpoint_control: {
+                                        enable_breakpoint: true,
+                                        break_on_all_dies: true,
+                                },
+                        },
+                },
+        ]
+}
+"#;
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_defaulted_fields() {
+    let (configuration, defaulted) =
+        amd_apcb::Apcb::deserialize_reporting_defaults(
+            serde_yaml::Deserializer::from_str(CONSOLE_CONFIG_STR),
+        )
+        .expect("configuration be valid JSON");
+    assert!(defaulted
+        .iter()
+        .any(|f| f == "AblConsoleOutControl.abl_console_port"));
+    assert!(!defaulted
+        .iter()
+        .any(|f| f == "AblConsoleOutControl.enable_console_logging"));
+    let groups = configuration.groups().unwrap().collect::<Vec<_>>();
+    let group = groups
+        .iter()
+        .find(|group| group.id() == amd_apcb::GroupId::Memory)
+        .unwrap();
+    let mut found = false;
+    for entry in group.entries() {
+        if let Some((s, _)) =
+            entry.body_as_struct::<amd_apcb::memory::ConsoleOutControl>()
+        {
+            assert_eq!(
+                s.abl_console_out_control.abl_console_port().unwrap(),
+                0x80
+            );
+            found = true;
+        }
+    }
+    assert!(found);
+}
+
+#[cfg(feature = "schemars")]
+#[test]
+fn test_json_schema_document() {
+    use amd_apcb::Apcb;
+
+    let schema = Apcb::json_schema_document();
+    assert_eq!(
+        schema.schema.metadata.as_ref().unwrap().title.as_deref(),
+        Some("Apcb")
+    );
+    for name in [
+        "GroupId",
+        "GROUP_HEADER",
+        "SerdeGroupItem",
+        "TokenEntryId",
+        "V2_HEADER",
+    ] {
+        assert!(schema.definitions.contains_key(name), "{name} is missing");
+    }
+    let group_id =
+        serde_yaml::to_string(&schema.definitions["GroupId"]).unwrap();
+    assert!(group_id.contains("Memory (0x1704)"));
+    let group_header =
+        serde_yaml::to_string(&schema.definitions["GROUP_HEADER"]).unwrap();
+    assert!(group_header.contains("#/definitions/GroupId"));
+    let yaml = serde_yaml::to_string(&schema).unwrap();
+    assert!(yaml.contains("$schema"));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_create_v2_only() {
+    use amd_apcb::{Apcb, ApcbIoOptions, GroupId};
+    use std::borrow::Cow;
+
+    let has_ecb2 = |buf: &[u8]| buf.windows(4).any(|w| w == b"ECB2");
+
+    let apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    let buf = apcb.save().unwrap().into_owned();
+    assert!(has_ecb2(&buf));
+    let apcb = Apcb::load(Cow::from(buf), &ApcbIoOptions::default()).unwrap();
+    assert!(apcb.v3_header_ext().unwrap().is_some());
+    assert_eq!(apcb.header().unwrap().version.get(), 0x30);
+
+    let mut apcb = Apcb::create_v2_only(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    assert!(apcb.v3_header_ext().unwrap().is_none());
+    apcb.insert_group(GroupId::Psp, *b"PSPG").unwrap();
+    let buf = apcb.save().unwrap().into_owned();
+    assert!(!has_ecb2(&buf));
+    let apcb = Apcb::load(Cow::from(buf), &ApcbIoOptions::default()).unwrap();
+    assert!(apcb.v3_header_ext().unwrap().is_none());
+    let header = apcb.header().unwrap();
+    assert_eq!(header.version.get(), 0x20);
+    assert_eq!(header.header_size.get(), 32);
+    assert_eq!(header.apcb_size.get(), 32 + 16);
+    assert_eq!(apcb.group_count().unwrap(), 1);
+    assert!(apcb.group(GroupId::Psp).unwrap().is_some());
+}
+
+#[cfg(feature = "serde-yaml")]
+#[test]
+fn test_yaml_round_trip() {
+    use amd_apcb::fch::EspiInit;
+    use amd_apcb::{
+        Apcb, ApcbIoOptions, BoardInstances, EntryId, FchEntryId, GroupId,
+        PriorityLevel, PriorityLevels,
+    };
+    use std::borrow::Cow;
+
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    apcb.insert_group(GroupId::Fch, *b"FCHG").unwrap();
+    apcb.insert_group(GroupId::Token, *b"TOKN").unwrap();
+    apcb.insert_struct_entry(
+        EntryId::Fch(FchEntryId::EspiInit),
+        0,
+        BoardInstances::all(),
+        PriorityLevels::from_level(PriorityLevel::Normal),
+        &EspiInit::default(),
+        &[],
+    )
+    .unwrap();
+    let mut tokens = apcb
+        .tokens_mut(
+            0,
+            BoardInstances::from_instance(0).unwrap(),
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            None,
+        )
+        .unwrap();
+    tokens.set_mem_urg_ref_limit(4).unwrap();
+    tokens.set_mem_restore_valid_days(15).unwrap();
+    let buf = apcb.save().unwrap().into_owned();
+    let apcb = Apcb::load(Cow::from(buf), &ApcbIoOptions::default()).unwrap();
+
+    let yaml = apcb.to_yaml_string().unwrap();
+    let deserialized = Apcb::from_yaml_str(&yaml).unwrap();
+    assert!(deserialized.content_eq(&apcb));
+    assert_eq!(deserialized.to_yaml_string().unwrap(), yaml);
+    assert!(matches!(
+        Apcb::from_yaml_str("header: ["),
+        Err(amd_apcb::Error::Yaml(_))
+    ));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_header_checksum() {
+    use amd_apcb::{Apcb, ApcbIoOptions, Error, GroupId, V3_HEADER_EXT};
+    use std::borrow::Cow;
+    use zerocopy::AsBytes;
+
+    fn integrity_sign(groups: &[u8]) -> [u8; 32] {
+        let mut result = [0u8; 32];
+        for (i, c) in groups.iter().enumerate() {
+            result[i % 32] ^= *c;
+        }
+        result
+    }
+    // Makes the bytes of the V3_HEADER_EXT sum up to 0.
+    fn header_checksum_fn(header_ext: &V3_HEADER_EXT) -> u8 {
+        let sum = header_ext
+            .as_bytes()
+            .iter()
+            .fold(0u8, |sum, c| sum.wrapping_add(*c));
+        0u8.wrapping_sub(sum)
+    }
+
+    let sum = |apcb: &Apcb| {
+        apcb.v3_header_ext()
+            .unwrap()
+            .unwrap()
+            .bytes()
+            .iter()
+            .fold(0u8, |sum, c| sum.wrapping_add(*c))
+    };
+    let header_checksum =
+        |apcb: &Apcb| apcb.v3_header_ext().unwrap().unwrap().header_checksum;
+    let options = ApcbIoOptions::builder()
+        .with_header_checksum_fn(header_checksum_fn)
+        .with_check_header_checksum(true)
+        .build();
+    let mut apcb =
+        Apcb::create(Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]), 42, &options)
+            .unwrap();
+    assert_eq!(sum(&apcb), 0);
+    apcb.insert_group(GroupId::Psp, *b"PSPG").unwrap();
+    let expected_checksum = header_checksum(&apcb);
+
+    // Stable across saves, even though unique_apcb_instance changes.
+    let buf = apcb.save().unwrap().into_owned();
+    let apcb = Apcb::load(Cow::from(buf), &options).unwrap();
+    assert_eq!(header_checksum(&apcb), expected_checksum);
+    let buf = apcb.save().unwrap().into_owned();
+    let apcb = Apcb::load(Cow::from(buf.clone()), &options).unwrap();
+    assert_eq!(apcb.unique_apcb_instance().unwrap(), 44);
+    assert_eq!(header_checksum(&apcb), expected_checksum);
+    assert_eq!(apcb.v3_header_ext().unwrap().unwrap().integrity_sign, [0; 32]);
+
+    // Without header_checksum_fn, save leaves it alone, and the stale
+    // header checksum is caught on load.
+    let mut apcb =
+        Apcb::load(Cow::from(buf), &ApcbIoOptions::default()).unwrap();
+    apcb.v3_header_ext_mut().unwrap().unwrap().integrity_sign[0] = 1;
+    let buf = apcb.save().unwrap().into_owned();
+    match Apcb::load(Cow::from(buf.clone()), &options) {
+        Err(Error::HeaderChecksum { stored, calculated }) => {
+            assert_eq!(stored, expected_checksum);
+            assert_eq!(calculated, expected_checksum.wrapping_sub(1));
+        }
+        _ => panic!("expected HeaderChecksum"),
+    }
+    let apcb = Apcb::load(Cow::from(buf), &ApcbIoOptions::default()).unwrap();
+    assert_eq!(header_checksum(&apcb), expected_checksum);
+
+    let options = ApcbIoOptions::builder()
+        .with_header_checksum_fn(header_checksum_fn)
+        .with_integrity_sign_fn(integrity_sign)
+        .build();
+    let mut apcb =
+        Apcb::create(Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]), 42, &options)
+            .unwrap();
+    apcb.insert_group(GroupId::Psp, *b"PSPG").unwrap();
+    let expected_sign =
+        integrity_sign(&apcb.beginning_of_groups().unwrap()[..16]);
+    let buf = apcb.save().unwrap().into_owned();
+    let apcb = Apcb::load(Cow::from(buf), &options).unwrap();
+    let header_ext = apcb.v3_header_ext().unwrap().unwrap();
+    assert_eq!(header_ext.integrity_sign, expected_sign);
+    assert_ne!(header_ext.integrity_sign, [0; 32]);
+    assert_eq!(sum(&apcb), 0);
+
+    // compact_into finalizes the copy exactly like save_no_inc does.
+    let mut apcb =
+        Apcb::create(Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]), 42, &options)
+            .unwrap();
+    apcb.insert_group(GroupId::Psp, *b"PSPG").unwrap();
+    let mut out = vec![0u8; Apcb::MAX_SIZE];
+    let size = apcb.compact_into(&mut out).unwrap();
+    let saved = apcb.save_no_inc().unwrap();
+    assert_eq!(&out[..size], &saved[..size]);
+    let apcb = Apcb::load(Cow::from(&out[..]), &options).unwrap();
+    assert_eq!(
+        apcb.v3_header_ext().unwrap().unwrap().integrity_sign,
+        expected_sign
+    );
+}
@@ -0,0 +1,278 @@
+#[cfg(feature = "std")]
+#[test]
+fn test_parameter_display() {
+    use amd_apcb::{
+        Parameter, ParameterAttributes, ParameterTimePoint,
+        ParameterTokenConfig,
+    };
+    let attributes = ParameterAttributes::new()
+        .with_time_point(ParameterTimePoint::Any)
+        .with_token(ParameterTokenConfig::MemLrdimmCapable)
+        .with_size_minus_one(3);
+    let parameter = Parameter::new(&attributes, 0x1234).unwrap();
+    assert_eq!(
+        parameter.to_string(),
+        "time_point=Any token=MemLrdimmCapable size=4 value=0x1234"
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_parameters_by_time_point() {
+    use amd_apcb::{
+        ParameterAttributes, ParameterTimePoint, ParameterTokenConfig,
+        ParametersIter,
+    };
+    let parameters = [
+        (ParameterTimePoint::Any, ParameterTokenConfig::MemLrdimmCapable, 1),
+        (ParameterTimePoint::Never, ParameterTokenConfig::Cbs00, 2),
+        (ParameterTimePoint::Any, ParameterTokenConfig::Cbs01, 3),
+    ];
+    let mut raw = Vec::new();
+    for (time_point, token, _) in parameters {
+        let attributes = ParameterAttributes::new()
+            .with_time_point(time_point)
+            .with_token(token)
+            .with_size_minus_one(0);
+        raw.extend_from_slice(&u32::from(attributes).to_le_bytes());
+    }
+    raw.extend_from_slice(
+        &u32::from(ParameterAttributes::terminator()).to_le_bytes(),
+    );
+    for (_, _, value) in parameters {
+        raw.push(value);
+    }
+    raw.push(0xff);
+
+    let grouped =
+        ParametersIter::new(&raw).unwrap().parameters_by_time_point().unwrap();
+    let summary = grouped
+        .iter()
+        .map(|(time_point, parameters)| {
+            (
+                *time_point,
+                parameters
+                    .iter()
+                    .map(|p| (p.token().unwrap(), p.value().unwrap()))
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        summary,
+        [
+            (ParameterTimePoint::Never, vec![(ParameterTokenConfig::Cbs00, 2)]),
+            (
+                ParameterTimePoint::Any,
+                vec![
+                    (ParameterTokenConfig::MemLrdimmCapable, 1),
+                    (ParameterTokenConfig::Cbs01, 3)
+                ]
+            ),
+        ]
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_parameters_without_terminator() {
+    use amd_apcb::{
+        Apcb, ApcbIoOptions, BoardInstances, ContextType, EntryId,
+        EntryItemBody, Error, GroupId, ParameterAttributes, ParameterTimePoint,
+        ParameterTokenConfig, ParametersIter, PriorityLevel, PriorityLevels,
+        PspEntryId,
+    };
+    use std::borrow::Cow;
+
+    let mut raw = Vec::new();
+    for token in [ParameterTokenConfig::Cbs00, ParameterTokenConfig::Cbs01] {
+        let attributes = ParameterAttributes::new()
+            .with_time_point(ParameterTimePoint::Any)
+            .with_token(token)
+            .with_size_minus_one(0);
+        raw.extend_from_slice(&u32::from(attributes).to_le_bytes());
+    }
+    raw.extend_from_slice(&[1, 2, 0, 0]);
+    assert!(matches!(
+        ParametersIter::new(&raw),
+        Err(Error::ParameterRange { offset: 12 })
+    ));
+
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    apcb.insert_group(GroupId::Psp, *b"PSPG").unwrap();
+    let entry_id = EntryId::Psp(PspEntryId::Parameters);
+    assert!(matches!(
+        apcb.upsert_entry(
+            entry_id,
+            0,
+            BoardInstances::all(),
+            ContextType::Struct,
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            &raw,
+        ),
+        Err(Error::ParameterRange { offset: 12 })
+    ));
+    assert!(apcb.group(GroupId::Psp).unwrap().unwrap().entries().count() == 0);
+
+    // With the terminator, the entry can be stored.  If it gets lost
+    // afterwards, loading fails.
+    let terminator = u32::from(ParameterAttributes::terminator());
+    let mut terminated = raw[..8].to_vec();
+    terminated.extend_from_slice(&terminator.to_le_bytes());
+    terminated.extend_from_slice(&[1, 2, 0xff, 0]);
+    apcb.upsert_entry(
+        entry_id,
+        0,
+        BoardInstances::all(),
+        ContextType::Struct,
+        PriorityLevels::from_level(PriorityLevel::Normal),
+        &terminated,
+    )
+    .unwrap();
+    {
+        let mut group = apcb.group_mut(GroupId::Psp).unwrap().unwrap();
+        let mut entry =
+            group.entry_exact_mut(entry_id, 0, BoardInstances::all()).unwrap();
+        let EntryItemBody::Struct(body) = &mut entry.body else {
+            panic!("expected struct body");
+        };
+        body[8..12].copy_from_slice(&raw[0..4]);
+    }
+    let buf = apcb.save().unwrap().into_owned();
+    assert!(matches!(
+        Apcb::load(Cow::from(buf), &ApcbIoOptions::default()),
+        Err(Error::ParameterRange { .. })
+    ));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_set_parameters() {
+    use amd_apcb::{
+        Apcb, ApcbIoOptions, EntryId, Error, GroupId, Parameter,
+        ParameterAttributes, ParameterTimePoint, ParameterTokenConfig,
+        PspEntryId,
+    };
+    use std::borrow::Cow;
+
+    let parameter = |token, size_minus_one, value| {
+        let attributes = ParameterAttributes::new()
+            .with_time_point(ParameterTimePoint::Any)
+            .with_token(token)
+            .with_size_minus_one(size_minus_one);
+        Parameter::new(&attributes, value).unwrap()
+    };
+    let entry_id = EntryId::Psp(PspEntryId::Parameters);
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    apcb.insert_group(GroupId::Psp, *b"PSPG").unwrap();
+    assert!(matches!(
+        apcb.parameters(entry_id),
+        Err(Error::EntryNotFound { .. })
+    ));
+
+    let parameters = [
+        parameter(ParameterTokenConfig::Cbs00, 0, 0x12),
+        parameter(ParameterTokenConfig::Cbs01, 1, 0x3456),
+        parameter(ParameterTokenConfig::MemLrdimmCapable, 7, 0x1_0000_0001),
+    ];
+    apcb.set_parameters(entry_id, &parameters).unwrap();
+    // Replaces the previous entry
+    apcb.set_parameters(entry_id, &parameters).unwrap();
+    assert!(matches!(
+        apcb.set_parameters(
+            entry_id,
+            &[parameter(ParameterTokenConfig::Cbs00, 2, 0)]
+        ),
+        Err(Error::ParameterRange { offset: 0 })
+    ));
+    assert!(matches!(
+        apcb.set_parameters(
+            entry_id,
+            &[parameter(ParameterTokenConfig::Cbs00, 0, 0x100)]
+        ),
+        Err(Error::ParameterRange { offset: 0 })
+    ));
+
+    let buf = apcb.save().unwrap().into_owned();
+    let apcb = Apcb::load(Cow::from(buf), &ApcbIoOptions::default()).unwrap();
+    let group = apcb.group(GroupId::Psp).unwrap().unwrap();
+    assert_eq!(group.entries().count(), 1);
+    let result = apcb.parameters(entry_id).unwrap();
+    let summary = |parameters: &[Parameter]| {
+        parameters
+            .iter()
+            .map(|p| {
+                (
+                    p.time_point().unwrap(),
+                    p.token().unwrap(),
+                    p.value_size().unwrap(),
+                    p.value().unwrap(),
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(summary(&result), summary(&parameters));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_entry_as_parameters() {
+    use amd_apcb::{
+        Apcb, ApcbIoOptions, BoardInstances, ContextType, EntryId, GroupId,
+        Parameter, ParameterAttributes, ParameterTimePoint,
+        ParameterTokenConfig, PriorityLevel, PriorityLevels, PspEntryId,
+    };
+    use std::borrow::Cow;
+
+    let attributes = ParameterAttributes::new()
+        .with_time_point(ParameterTimePoint::Any)
+        .with_token(ParameterTokenConfig::Cbs01)
+        .with_size_minus_one(1);
+    let parameters = [Parameter::new(&attributes, 0x1234).unwrap()];
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    apcb.insert_group(GroupId::Psp, *b"PSPG").unwrap();
+    apcb.set_parameters(EntryId::Psp(PspEntryId::Parameters), &parameters)
+        .unwrap();
+    apcb.upsert_entry(
+        EntryId::Psp(PspEntryId::BoardIdGettingMethod),
+        0,
+        BoardInstances::all(),
+        ContextType::Struct,
+        PriorityLevels::from_level(PriorityLevel::Normal),
+        &[0u8; 8],
+    )
+    .unwrap();
+
+    let group = apcb.group(GroupId::Psp).unwrap().unwrap();
+    let mut seen_parameters = false;
+    for entry in group.entries() {
+        match entry.id() {
+            EntryId::Psp(PspEntryId::Parameters) => {
+                let result = entry
+                    .as_parameters()
+                    .unwrap()
+                    .map(|p| (p.token().unwrap(), p.value().unwrap()))
+                    .collect::<Vec<_>>();
+                assert_eq!(result, [(ParameterTokenConfig::Cbs01, 0x1234)]);
+                seen_parameters = true;
+            }
+            _ => assert!(entry.as_parameters().is_none()),
+        }
+    }
+    assert!(seen_parameters);
+}
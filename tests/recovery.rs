@@ -0,0 +1,203 @@
+#[cfg(feature = "std")]
+#[test]
+fn test_load_best_effort_truncated() {
+    use amd_apcb::{Apcb, ApcbIoOptions, ApcbWarning, GroupId};
+    use std::borrow::Cow;
+
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    apcb.insert_group(GroupId::Psp, *b"PSPG").unwrap();
+    apcb.insert_group(GroupId::Ccx, *b"CCXG").unwrap();
+    apcb.insert_group(GroupId::Df, *b"DFG ").unwrap();
+    let mut blob = apcb.save().unwrap().into_owned();
+
+    // Cut the blob in the middle of the third group.
+    let groups_offset = 128;
+    let end_of_second_group = groups_offset + 16 + 16;
+    blob.truncate(end_of_second_group + 8);
+    assert!(
+        Apcb::load(Cow::from(blob.clone()), &ApcbIoOptions::default()).is_err()
+    );
+
+    let (apcb, warnings) =
+        Apcb::load_best_effort(Cow::from(blob), &ApcbIoOptions::default());
+    let apcb = apcb.unwrap();
+    assert_eq!(
+        apcb.groups().unwrap().map(|group| group.id()).collect::<Vec<_>>(),
+        [GroupId::Psp, GroupId::Ccx]
+    );
+    assert_eq!(warnings.len(), 2);
+    assert!(matches!(warnings[0], ApcbWarning::Truncated { .. }));
+    assert!(matches!(
+        warnings[1],
+        ApcbWarning::GroupsSkipped { offset, size: 8, .. }
+            if offset == end_of_second_group
+    ));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_find_in_image() {
+    use amd_apcb::{Apcb, ApcbIoOptions, GroupId};
+    use std::borrow::Cow;
+
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    apcb.insert_group(GroupId::Psp, *b"PSPG").unwrap();
+    apcb.insert_group(GroupId::Token, *b"TOKN").unwrap();
+    let buf = apcb.save().unwrap().into_owned();
+    let apcb = Apcb::load(Cow::from(buf), &ApcbIoOptions::default()).unwrap();
+    let blob = apcb.live_bytes().unwrap();
+
+    let mut image = vec![0xFFu8; 0x4000];
+    assert_eq!(Apcb::find_in_image(&image), None);
+    // A copy of the APCB with one byte flipped, so its checksum is wrong.
+    image[0x100..0x100 + blob.len()].copy_from_slice(blob);
+    image[0x100 + 0x40] ^= 0x01;
+    // The signature in unrelated data.
+    image[0x1f00..0x1f04].copy_from_slice(b"APCB");
+    image[0x2000..0x2000 + blob.len()].copy_from_slice(blob);
+    assert_eq!(Apcb::find_in_image(&image), Some((0x2000, blob.len())));
+    assert_eq!(Apcb::find_in_image(&image[..0x2000 + blob.len() - 1]), None);
+
+    let (offset, size) = Apcb::find_in_image(&image).unwrap();
+    let apcb = Apcb::load(
+        Cow::from(image[offset..offset + size].to_vec()),
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(apcb.groups().unwrap().len(), 2);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_load_stale_checksum() {
+    use amd_apcb::{Apcb, ApcbIoOptions, Error, GroupId};
+    use std::borrow::Cow;
+
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    apcb.insert_group(GroupId::Psp, *b"PSPG").unwrap();
+    let mut blob = apcb.save().unwrap().into_owned();
+    let checksum_byte = blob[16];
+    assert_eq!(Apcb::compute_checksum(&blob[..128 + 16]), checksum_byte);
+
+    // Change the group signature without updating the checksum.
+    blob[128 + 3] = b'H';
+    let calculated = Apcb::compute_checksum(&blob[..128 + 16]);
+    assert_ne!(calculated, checksum_byte);
+    assert!(matches!(
+        Apcb::load(Cow::from(blob.clone()), &ApcbIoOptions::default()),
+        Err(Error::Checksum { stored, calculated: c })
+            if stored == checksum_byte && c == calculated
+    ));
+
+    let apcb = Apcb::load(
+        Cow::from(blob),
+        ApcbIoOptions::builder().with_check_checksum(false),
+    )
+    .unwrap();
+    let repaired = apcb.save_no_inc().unwrap().into_owned();
+    assert_eq!(repaired[16], calculated);
+    Apcb::load(Cow::from(repaired), &ApcbIoOptions::default()).unwrap();
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_reader_writer_round_trip() {
+    use amd_apcb::{Apcb, ApcbIoOptions, Error, GroupId};
+    use std::borrow::Cow;
+    use std::io::Cursor;
+
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    apcb.insert_group(GroupId::Psp, *b"PSPG").unwrap();
+    apcb.insert_group(GroupId::Memory, *b"MEMG").unwrap();
+
+    let mut file = Cursor::new(Vec::new());
+    apcb.to_writer(&mut file).unwrap();
+    let bytes = file.into_inner();
+    assert_eq!(bytes.len(), 128 + 16 + 16);
+    assert_eq!(Apcb::compute_checksum(&bytes), bytes[16]);
+
+    let mut apcb =
+        Apcb::from_reader(Cursor::new(&bytes), &ApcbIoOptions::default())
+            .unwrap();
+    assert_eq!(
+        apcb.groups().unwrap().map(|group| group.id()).collect::<Vec<_>>(),
+        [GroupId::Psp, GroupId::Memory]
+    );
+    assert_eq!(apcb.unique_apcb_instance().unwrap(), 42);
+    // There is room for more.
+    apcb.insert_group(GroupId::Token, *b"TOKN").unwrap();
+
+    let mut file = Cursor::new(Vec::new());
+    apcb.to_writer(&mut file).unwrap();
+    let bytes = file.into_inner();
+    let apcb = Apcb::from_reader(Cursor::new(bytes), &ApcbIoOptions::default())
+        .unwrap();
+    assert_eq!(apcb.groups().unwrap().len(), 3);
+
+    assert!(Apcb::from_reader(Cursor::new(b"APCB"), &ApcbIoOptions::default())
+        .is_err());
+
+    struct FailingReader;
+    impl std::io::Read for FailingReader {
+        fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+            Err(std::io::ErrorKind::BrokenPipe.into())
+        }
+    }
+    assert!(matches!(
+        Apcb::from_reader(FailingReader, &ApcbIoOptions::default()),
+        Err(Error::Io(e)) if e.kind() == std::io::ErrorKind::BrokenPipe
+    ));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_pinned_unique_apcb_instance() {
+    use amd_apcb::{Apcb, ApcbIoOptions, GroupId};
+    use std::borrow::Cow;
+
+    let build = |options: &ApcbIoOptions| {
+        let mut apcb =
+            Apcb::create(Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]), 42, options)
+                .unwrap();
+        apcb.insert_group(GroupId::Memory, *b"MEMG").unwrap();
+        apcb.save().unwrap().into_owned()
+    };
+    let options = ApcbIoOptions::builder().with_unique_apcb_instance(7).build();
+    let first = build(&options);
+    let second = build(&options);
+    assert_eq!(first, second);
+    let apcb =
+        Apcb::load(Cow::from(&first[..]), &ApcbIoOptions::default()).unwrap();
+    assert_eq!(apcb.unique_apcb_instance().unwrap(), 7);
+
+    // Saving again keeps the pinned value instead of incrementing it.
+    let apcb = Apcb::load(Cow::from(first.clone()), &options).unwrap();
+    assert_eq!(apcb.save().unwrap().into_owned(), first);
+
+    // Without pinning, save increments it.
+    let apcb = Apcb::load(Cow::from(first.clone()), &ApcbIoOptions::default())
+        .unwrap();
+    let buf = apcb.save().unwrap().into_owned();
+    let apcb = Apcb::load(Cow::from(buf), &ApcbIoOptions::default()).unwrap();
+    assert_eq!(apcb.unique_apcb_instance().unwrap(), 8);
+}
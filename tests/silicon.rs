@@ -0,0 +1,176 @@
+#[cfg(feature = "std")]
+#[test]
+fn test_check_for_silicon_ddr4_on_turin() {
+    use amd_apcb::memory::RdimmDdr4CadBusElement;
+    use amd_apcb::{
+        Apcb, ApcbIoOptions, BoardInstances, EntryId, GroupId, MemoryEntryId,
+        PriorityLevel, PriorityLevels, SiliconGeneration,
+        SiliconIncompatibility,
+    };
+    use std::borrow::Cow;
+
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    apcb.insert_group(GroupId::Memory, *b"MEMG").unwrap();
+    apcb.insert_struct_array_as_entry(
+        EntryId::Memory(MemoryEntryId::PsRdimmDdr4CadBus),
+        0,
+        BoardInstances::all(),
+        PriorityLevels::from_level(PriorityLevel::Normal),
+        &[RdimmDdr4CadBusElement::default()],
+    )
+    .unwrap();
+    assert_eq!(apcb.check_for_silicon(SiliconGeneration::Milan).unwrap(), []);
+    assert_eq!(
+        apcb.check_for_silicon(SiliconGeneration::Turin).unwrap(),
+        [SiliconIncompatibility::UnsupportedMemoryTechnology {
+            entry_id: EntryId::Memory(MemoryEntryId::PsRdimmDdr4CadBus),
+            instance_id: 0,
+            board_instance_mask: BoardInstances::all(),
+        }]
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_report_unknown_bits() {
+    use amd_apcb::memory::{DdrRates, RdimmDdr4CadBusElement};
+    use amd_apcb::{
+        Apcb, ApcbIoOptions, BoardInstances, EntryId, GroupId, MemoryEntryId,
+        PriorityLevel, PriorityLevels, UnknownBitsReport,
+    };
+    use std::borrow::Cow;
+    use zerocopy::AsBytes;
+
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    apcb.insert_group(GroupId::Memory, *b"MEMG").unwrap();
+    let mut element = RdimmDdr4CadBusElement::default();
+    element.set_ddr_rates(DdrRates::new().with_ddr3200(true));
+    let mut raw = element.as_bytes().to_vec();
+    // dimm0_ranks; bit 4 is reserved.
+    raw[12] |= 1 << 4;
+    let vendor_element = RdimmDdr4CadBusElement::try_from(&raw[..]).unwrap();
+    apcb.insert_struct_array_as_entry(
+        EntryId::Memory(MemoryEntryId::PsRdimmDdr4CadBus),
+        0,
+        BoardInstances::all(),
+        PriorityLevels::from_level(PriorityLevel::Normal),
+        &[element, vendor_element],
+    )
+    .unwrap();
+    let reports = apcb.report_unknown_bits().unwrap();
+    assert_eq!(reports.len(), 1);
+    let UnknownBitsReport {
+        entry_id,
+        instance_id,
+        element_index,
+        field,
+        value,
+        ..
+    } = reports[0];
+    assert_eq!(entry_id, EntryId::Memory(MemoryEntryId::PsRdimmDdr4CadBus));
+    assert_eq!(instance_id, 0);
+    assert_eq!(element_index, 1);
+    assert_eq!(field, "RdimmDdr4CadBusElement.dimm0_ranks");
+    assert_eq!(value & (1 << 4), 1 << 4);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_report_unknown_bits_in_sequences_and_tokens() {
+    use amd_apcb::memory::platform_specific_override::LvDimmForce1V5;
+    use amd_apcb::memory::{ErrorOutControl116, ErrorOutControlBeepCode};
+    use amd_apcb::{
+        Apcb, ApcbIoOptions, BoardInstances, EntryId, GroupId,
+        MemPmuBistTestSelect, MemoryEntryId, PriorityLevel, PriorityLevels,
+        TokenEntryId,
+    };
+    use std::borrow::Cow;
+    use zerocopy::{AsBytes, FromBytes};
+
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    apcb.insert_group(GroupId::Memory, *b"MEMG").unwrap();
+    apcb.insert_group(GroupId::Token, *b"TOKN").unwrap();
+    let priority_mask = PriorityLevels::from_level(PriorityLevel::Normal);
+
+    let mut raw = LvDimmForce1V5::default().as_bytes().to_vec();
+    // dimms; only bits 0 to 3 are modeled.
+    raw[4] = 0x30;
+    let override_ = LvDimmForce1V5::read_from(&raw[..]).unwrap();
+    apcb.insert_struct_sequence_as_entry(
+        EntryId::Memory(MemoryEntryId::PlatformSpecificOverride),
+        0,
+        BoardInstances::all(),
+        priority_mask,
+        &[&LvDimmForce1V5::default(), &override_],
+    )
+    .unwrap();
+
+    let mut error_out_control = ErrorOutControl116::default();
+    let mut beep_codes = error_out_control.beep_code_table().unwrap();
+    // peak_attr; only bits 0 to 11 are modeled.
+    beep_codes[2] =
+        ErrorOutControlBeepCode::read_from(&[4, 0, 0, 0, 0, 0x10, 0, 0][..])
+            .unwrap();
+    error_out_control.set_beep_code_table(beep_codes);
+    apcb.insert_struct_entry(
+        EntryId::Memory(MemoryEntryId::ErrorOutControl),
+        0,
+        BoardInstances::all(),
+        priority_mask,
+        &error_out_control,
+        &[],
+    )
+    .unwrap();
+
+    let mut tokens =
+        apcb.tokens_mut(0, BoardInstances::all(), priority_mask, None).unwrap();
+    tokens.set_mem_pmu_bist_test_select(MemPmuBistTestSelect::new()).unwrap();
+    // Only bits 0 to 4 are modeled.
+    apcb.set_token_value(
+        EntryId::Token(TokenEntryId::Byte),
+        0,
+        BoardInstances::all(),
+        0x7034_fbfb,
+        0x27,
+    )
+    .unwrap();
+
+    let mut reports = apcb
+        .report_unknown_bits()
+        .unwrap()
+        .into_iter()
+        .map(|report| (report.entry_id, report.element_index, report.field))
+        .collect::<Vec<_>>();
+    reports.sort_by_key(|(_, _, field)| *field);
+    assert_eq!(
+        reports,
+        [
+            (
+                EntryId::Memory(MemoryEntryId::ErrorOutControl),
+                2,
+                "ErrorOutControlBeepCode.peak_attr"
+            ),
+            (
+                EntryId::Memory(MemoryEntryId::PlatformSpecificOverride),
+                1,
+                "LvDimmForce1V5.dimms"
+            ),
+            (EntryId::Token(TokenEntryId::Byte), 0, "MemPmuBistTestSelect"),
+        ]
+    );
+}
@@ -0,0 +1,802 @@
+#[cfg(feature = "std")]
+#[test]
+fn test_token_counts_by_width() {
+    use amd_apcb::{
+        Apcb, ApcbIoOptions, BaudRate, BoardInstances, GroupId, PriorityLevel,
+        PriorityLevels, PspEnableDebugMode, TokenEntryId,
+    };
+    use std::borrow::Cow;
+
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    apcb.insert_group(GroupId::Token, *b"TOKN").unwrap();
+    let board_instance_mask = BoardInstances::from_instance(0).unwrap();
+    let mut tokens = apcb
+        .tokens_mut(
+            0,
+            board_instance_mask,
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            None,
+        )
+        .unwrap();
+    tokens.set_abl_serial_baud_rate(BaudRate::_115200Baud).unwrap();
+    tokens.set_psp_enable_debug_mode(PspEnableDebugMode::Enabled).unwrap();
+    tokens.set_mem_urg_ref_limit(4).unwrap();
+    tokens.set_mem_restore_valid_days(15).unwrap();
+    tokens.set_ccx_min_sev_asid(2).unwrap();
+
+    let counts = apcb.token_counts_by_width(board_instance_mask).unwrap();
+    assert_eq!(
+        counts.into_iter().collect::<Vec<_>>(),
+        [(TokenEntryId::Byte, 3), (TokenEntryId::Dword, 2)]
+    );
+    assert!(apcb
+        .token_counts_by_width(BoardInstances::all())
+        .unwrap()
+        .is_empty());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_tokens_sorted() {
+    use amd_apcb::{
+        Apcb, ApcbIoOptions, BaudRate, BoardInstances, GroupId, PriorityLevel,
+        PriorityLevels, PspEnableDebugMode, TokenEntryId,
+    };
+    use std::borrow::Cow;
+
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    apcb.insert_group(GroupId::Token, *b"TOKN").unwrap();
+    let mut tokens = apcb
+        .tokens_mut(
+            0,
+            BoardInstances::all(),
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            None,
+        )
+        .unwrap();
+    tokens.set_abl_serial_baud_rate(BaudRate::_115200Baud).unwrap();
+    tokens.set_psp_enable_debug_mode(PspEnableDebugMode::Enabled).unwrap();
+    tokens.set_mem_urg_ref_limit(4).unwrap();
+    tokens.set_mem_restore_valid_days(15).unwrap();
+    tokens.set_ccx_min_sev_asid(2).unwrap();
+
+    // Storage order is by token entry (width) first, so keys are not
+    // globally sorted there.
+    let stored = apcb
+        .group(GroupId::Token)
+        .unwrap()
+        .unwrap()
+        .entries()
+        .flat_map(|entry| match entry.body {
+            amd_apcb::EntryItemBody::Tokens(tokens) => tokens
+                .iter()
+                .unwrap()
+                .map(|token| token.id())
+                .collect::<Vec<_>>(),
+            _ => panic!("unexpected struct entry"),
+        })
+        .collect::<Vec<_>>();
+    let mut sorted_keys = stored.clone();
+    sorted_keys.sort();
+    assert_ne!(stored, sorted_keys);
+
+    let sorted = apcb.tokens_sorted().unwrap().collect::<Vec<_>>();
+    assert_eq!(
+        sorted.iter().map(|&(_, key, _)| key).collect::<Vec<_>>(),
+        sorted_keys
+    );
+    assert!(sorted.contains(&(TokenEntryId::Dword, 0x6bd7_0482, 15)));
+    assert!(sorted.contains(&(TokenEntryId::Byte, 0x1333_32df, 4)));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_delete_token_everywhere() {
+    use amd_apcb::{
+        Apcb, ApcbIoOptions, BaudRate, BoardInstances, GroupId, PriorityLevel,
+        PriorityLevels,
+    };
+    use std::borrow::Cow;
+
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    apcb.insert_group(GroupId::Token, *b"TOKN").unwrap();
+    for board_instance in [0, 1] {
+        let mut tokens = apcb
+            .tokens_mut(
+                0,
+                BoardInstances::from_instance(board_instance).unwrap(),
+                PriorityLevels::from_level(PriorityLevel::Normal),
+                None,
+            )
+            .unwrap();
+        tokens.set_abl_serial_baud_rate(BaudRate::_115200Baud).unwrap();
+        tokens.set_mem_restore_valid_days(15).unwrap();
+    }
+
+    // AblSerialBaudRate
+    assert_eq!(apcb.delete_token_everywhere(0xae46_cea4).unwrap(), 2);
+    assert_eq!(apcb.delete_token_everywhere(0xae46_cea4).unwrap(), 0);
+    for board_instance in [0, 1] {
+        let tokens = apcb
+            .tokens(0, BoardInstances::from_instance(board_instance).unwrap())
+            .unwrap();
+        assert!(tokens.abl_serial_baud_rate().is_err());
+        assert_eq!(tokens.mem_restore_valid_days().unwrap(), 15);
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_assert_token() {
+    use amd_apcb::{
+        Apcb, ApcbIoOptions, BoardInstances, Error, GroupId, PriorityLevel,
+        PriorityLevels,
+    };
+    use std::borrow::Cow;
+
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    apcb.insert_group(GroupId::Token, *b"TOKN").unwrap();
+    let mut tokens = apcb
+        .tokens_mut(
+            0,
+            BoardInstances::from_instance(1).unwrap(),
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            None,
+        )
+        .unwrap();
+    tokens.set_mem_restore_valid_days(15).unwrap();
+
+    // MemRestoreValidDays
+    apcb.assert_token(0x6bd7_0482, 0b10, 15).unwrap();
+    assert!(matches!(
+        apcb.assert_token(0x6bd7_0482, 0b10, 16),
+        Err(Error::TokenValueMismatch {
+            key: 0x6bd7_0482,
+            expected: 16,
+            actual: 15,
+        })
+    ));
+    assert!(matches!(
+        apcb.assert_token(0x6bd7_0482, 0b01, 15),
+        Err(Error::TokenNotFound { .. })
+    ));
+    // MemUrgRefLimit
+    assert!(matches!(
+        apcb.assert_token(0x1333_32df, 0b10, 15),
+        Err(Error::TokenNotFound { .. })
+    ));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_token_value() {
+    use amd_apcb::{
+        Apcb, ApcbIoOptions, BoardInstances, EntryId, Error, GroupId,
+        PriorityLevel, PriorityLevels, TokenEntryId,
+    };
+    use std::borrow::Cow;
+
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    let entry_id = EntryId::Token(TokenEntryId::Dword);
+    let board_instance_mask = BoardInstances::from_instance(1).unwrap();
+    // MemRestoreValidDays
+    assert!(matches!(
+        apcb.token_value(entry_id, 0, board_instance_mask, 0x6bd7_0482),
+        Err(Error::TokenNotFound { .. })
+    ));
+    apcb.insert_group(GroupId::Token, *b"TOKN").unwrap();
+    let mut tokens = apcb
+        .tokens_mut(
+            0,
+            board_instance_mask,
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            None,
+        )
+        .unwrap();
+    tokens.set_mem_restore_valid_days(15).unwrap();
+
+    assert_eq!(
+        apcb.token_value(entry_id, 0, board_instance_mask, 0x6bd7_0482)
+            .unwrap(),
+        15
+    );
+    assert!(matches!(
+        apcb.token_value(entry_id, 0, BoardInstances::all(), 0x6bd7_0482),
+        Err(Error::TokenNotFound { .. })
+    ));
+    assert!(matches!(
+        apcb.token_value(entry_id, 1, board_instance_mask, 0x6bd7_0482),
+        Err(Error::TokenNotFound { .. })
+    ));
+    // MemUrgRefLimit
+    assert!(matches!(
+        apcb.token_value(entry_id, 0, board_instance_mask, 0x1333_32df),
+        Err(Error::TokenNotFound { .. })
+    ));
+
+    apcb.set_token_value(entry_id, 0, board_instance_mask, 0x6bd7_0482, 30)
+        .unwrap();
+    assert_eq!(
+        apcb.token_value(entry_id, 0, board_instance_mask, 0x6bd7_0482)
+            .unwrap(),
+        30
+    );
+    assert!(matches!(
+        apcb.set_token_value(entry_id, 0, board_instance_mask, 0x1333_32df, 4),
+        Err(Error::TokenNotFound { .. })
+    ));
+    assert!(matches!(
+        apcb.token_value(entry_id, 0, board_instance_mask, 0x1333_32df),
+        Err(Error::TokenNotFound { .. })
+    ));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_token_entries_raw() {
+    use amd_apcb::{
+        Apcb, ApcbIoOptions, BaudRate, BoardInstances, EntryItemBody, Error,
+        GroupId, PriorityLevel, PriorityLevels,
+    };
+    use std::borrow::Cow;
+
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    apcb.insert_group(GroupId::Token, *b"TOKN").unwrap();
+    let mut tokens = apcb
+        .tokens_mut(
+            0,
+            BoardInstances::all(),
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            None,
+        )
+        .unwrap();
+    tokens.set_abl_serial_baud_rate(BaudRate::_115200Baud).unwrap();
+    tokens.set_mem_restore_valid_days(15).unwrap();
+    tokens.set_mem_urg_ref_limit(6).unwrap();
+
+    let group = apcb.group(GroupId::Token).unwrap().unwrap();
+    let mut count = 0;
+    for entry in group.entries() {
+        let raw = entry.body.token_entries_raw().unwrap();
+        let decoded = match &entry.body {
+            EntryItemBody::Tokens(tokens) => tokens
+                .iter()
+                .unwrap()
+                .map(|token| (token.id(), token.value()))
+                .collect::<Vec<_>>(),
+            _ => panic!("expected token entry"),
+        };
+        assert_eq!(
+            raw.iter()
+                .map(|token| (token.key.get(), token.value.get()))
+                .collect::<Vec<_>>(),
+            decoded
+        );
+        count += raw.len();
+    }
+    assert_eq!(count, 3);
+    assert!(matches!(
+        EntryItemBody::Struct(&[0u8; 8][..]).token_entries_raw(),
+        Err(Error::EntryTypeMismatch)
+    ));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_edit_log() {
+    use amd_apcb::{
+        Apcb, ApcbEdit, ApcbIoOptions, BoardInstances, EntryId, GroupId,
+        PriorityLevel, PriorityLevels, TokenEntryId,
+    };
+    use std::borrow::Cow;
+
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        ApcbIoOptions::builder().with_record_edits(true),
+    )
+    .unwrap();
+    let board_instance_mask = BoardInstances::from_instance(0).unwrap();
+    apcb.insert_group(GroupId::Token, *b"TOKN").unwrap();
+    let mut tokens = apcb
+        .tokens_mut(
+            0,
+            board_instance_mask,
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            None,
+        )
+        .unwrap();
+    tokens.set_mem_restore_valid_days(15).unwrap();
+    tokens.set_mem_restore_valid_days(16).unwrap();
+    let entry_id = EntryId::Token(TokenEntryId::Dword);
+    // MemRestoreValidDays
+    apcb.delete_token(entry_id, 0, board_instance_mask, 0x6bd7_0482).unwrap();
+    apcb.delete_entry(entry_id, 0, board_instance_mask).unwrap();
+    apcb.delete_group(GroupId::Token).unwrap();
+
+    assert_eq!(
+        apcb.edit_log(),
+        [
+            ApcbEdit::InsertGroup {
+                group_id: GroupId::Token,
+                signature: *b"TOKN"
+            },
+            ApcbEdit::InsertEntry {
+                entry_id,
+                instance_id: 0,
+                board_instance_mask
+            },
+            ApcbEdit::InsertToken {
+                entry_id,
+                instance_id: 0,
+                board_instance_mask,
+                token_id: 0x6bd7_0482,
+                token_value: 15
+            },
+            ApcbEdit::SetToken {
+                entry_id,
+                instance_id: 0,
+                board_instance_mask,
+                token_id: 0x6bd7_0482,
+                token_value: 16
+            },
+            ApcbEdit::DeleteToken {
+                entry_id,
+                instance_id: 0,
+                board_instance_mask,
+                token_id: 0x6bd7_0482
+            },
+            ApcbEdit::DeleteEntry {
+                entry_id,
+                instance_id: 0,
+                board_instance_mask
+            },
+            ApcbEdit::DeleteGroup { group_id: GroupId::Token },
+        ]
+    );
+
+    // Off by default
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    apcb.insert_group(GroupId::Token, *b"TOKN").unwrap();
+    assert!(apcb.edit_log().is_empty());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_edit_log_body_edits() {
+    use amd_apcb::memory::ConsoleOutControl;
+    use amd_apcb::{
+        Apcb, ApcbEdit, ApcbIoOptions, BoardInstances, EntryId, Error, GroupId,
+        MemoryEntryId, PriorityLevel, PriorityLevels,
+    };
+    use std::borrow::Cow;
+
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        ApcbIoOptions::builder().with_record_edits(true),
+    )
+    .unwrap();
+    let board_instance_mask = BoardInstances::all();
+    let entry_id = EntryId::Memory(MemoryEntryId::ConsoleOutControl);
+    apcb.insert_group(GroupId::Memory, *b"MEMG").unwrap();
+    // Failed operations are not recorded.
+    assert!(matches!(
+        apcb.insert_group(GroupId::Memory, *b"MEMG"),
+        Err(Error::GroupUniqueKeyViolation)
+    ));
+    apcb.insert_struct_entry(
+        entry_id,
+        0,
+        board_instance_mask,
+        PriorityLevels::from_level(PriorityLevel::Normal),
+        &ConsoleOutControl::default(),
+        &[],
+    )
+    .unwrap();
+    apcb.modify_struct(
+        entry_id,
+        0,
+        board_instance_mask,
+        |console_out_control: &mut ConsoleOutControl| {
+            console_out_control
+                .abl_console_out_control
+                .set_abl_console_port(0x80);
+        },
+    )
+    .unwrap();
+    apcb.sort_entries(GroupId::Memory, |a, b| {
+        a.instance_id().cmp(&b.instance_id())
+    })
+    .unwrap();
+    let body =
+        apcb.group(GroupId::Memory).unwrap().unwrap().body_as_buf().to_vec();
+    apcb.delete_group(GroupId::Memory).unwrap();
+    apcb.insert_group_raw(*b"MEMG", 0x1704, &body).unwrap();
+
+    let inserted_group = ApcbEdit::InsertGroup {
+        group_id: GroupId::Memory,
+        signature: *b"MEMG",
+    };
+    let inserted_entry =
+        ApcbEdit::InsertEntry { entry_id, instance_id: 0, board_instance_mask };
+    assert_eq!(
+        apcb.edit_log(),
+        [
+            inserted_group,
+            inserted_entry,
+            ApcbEdit::ModifyEntryBody {
+                entry_id,
+                instance_id: 0,
+                board_instance_mask
+            },
+            ApcbEdit::SortEntries { group_id: GroupId::Memory },
+            ApcbEdit::DeleteGroup { group_id: GroupId::Memory },
+            inserted_group,
+            inserted_entry,
+        ]
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_diff_token_changed() {
+    use amd_apcb::{
+        Apcb, ApcbDiffItem, ApcbIoOptions, BoardInstances, EntryId, GroupId,
+        PriorityLevel, PriorityLevels, TokenEntryId,
+    };
+    use std::borrow::Cow;
+
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    let board_instance_mask = BoardInstances::from_instance(0).unwrap();
+    apcb.insert_group(GroupId::Token, *b"TOKN").unwrap();
+    let mut tokens = apcb
+        .tokens_mut(
+            0,
+            board_instance_mask,
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            None,
+        )
+        .unwrap();
+    tokens.set_mem_urg_ref_limit(4).unwrap();
+    tokens.set_mem_restore_valid_days(15).unwrap();
+    let buf = apcb.save().unwrap().into_owned();
+
+    let old =
+        Apcb::load(Cow::from(buf.clone()), &ApcbIoOptions::default()).unwrap();
+    let mut new =
+        Apcb::load(Cow::from(buf), &ApcbIoOptions::default()).unwrap();
+    // Cosmetic re-serialization is not a difference.
+    let new_buf = new.clone().save().unwrap().into_owned();
+    let resaved =
+        Apcb::load(Cow::from(new_buf), &ApcbIoOptions::default()).unwrap();
+    assert_eq!(old.diff(&resaved).unwrap(), []);
+
+    let mut tokens = new
+        .tokens_mut(
+            0,
+            board_instance_mask,
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            None,
+        )
+        .unwrap();
+    tokens.set_mem_restore_valid_days(16).unwrap();
+    assert_eq!(
+        old.diff(&new).unwrap(),
+        [ApcbDiffItem::TokenChanged {
+            entry_id: EntryId::Token(TokenEntryId::Dword),
+            instance_id: 0,
+            board_instance_mask,
+            // MemRestoreValidDays
+            token_id: 0x6bd7_0482,
+            old: Some(15),
+            new: Some(16),
+        }]
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_diff_headers() {
+    use amd_apcb::{Apcb, ApcbDiffItem, ApcbIoOptions, GroupId};
+    use std::borrow::Cow;
+
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    apcb.insert_group(GroupId::Token, *b"TOKN").unwrap();
+    let buf = apcb.save().unwrap().into_owned();
+    let old =
+        Apcb::load(Cow::from(buf.clone()), &ApcbIoOptions::default()).unwrap();
+    let mut new =
+        Apcb::load(Cow::from(buf), &ApcbIoOptions::default()).unwrap();
+    assert!(old.content_eq(&new));
+
+    new.set_group_signature(GroupId::Token, *b"TKN2").unwrap();
+    assert_eq!(
+        old.diff(&new).unwrap(),
+        [ApcbDiffItem::GroupChanged { group_id: GroupId::Token }]
+    );
+
+    let mut header_ext = new.v3_header_ext_mut().unwrap().unwrap();
+    let data_version = header_ext.data_version.get();
+    header_ext.data_version.set(data_version + 1);
+    assert_eq!(
+        old.diff(&new).unwrap(),
+        [
+            ApcbDiffItem::HeaderChanged,
+            ApcbDiffItem::GroupChanged { group_id: GroupId::Token },
+        ]
+    );
+    assert!(!old.content_eq(&new));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_configure_serial_console() {
+    use amd_apcb::memory::ConsoleOutControl;
+    use amd_apcb::{
+        Apcb, ApcbIoOptions, BaudRate, BoardInstances, ContextType, EntryId,
+        Error, GroupId, MemoryEntryId, PriorityLevel, PriorityLevels,
+    };
+    use std::borrow::Cow;
+
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    apcb.configure_serial_console(0b10, BaudRate::_9600Baud, 0x3f8).unwrap();
+    // Reconfiguring updates both in place.
+    apcb.configure_serial_console(0b10, BaudRate::_115200Baud, 0x2f8).unwrap();
+
+    let board_instance_mask = BoardInstances::from(0b10);
+    let tokens = apcb.tokens(0, board_instance_mask).unwrap();
+    assert_eq!(tokens.abl_serial_baud_rate().unwrap(), BaudRate::_115200Baud);
+    let group = apcb.group(GroupId::Memory).unwrap().unwrap();
+    assert_eq!(group.entries().count(), 1);
+    let entry = group
+        .entry_exact(
+            EntryId::Memory(MemoryEntryId::ConsoleOutControl),
+            0,
+            board_instance_mask,
+        )
+        .unwrap();
+    let (console_out_control, _) =
+        entry.body_as_struct::<ConsoleOutControl>().unwrap();
+    let abl_console_out_control = console_out_control.abl_console_out_control;
+    assert!(abl_console_out_control.enable_console_logging().unwrap());
+    assert_eq!(abl_console_out_control.abl_console_port().unwrap(), 0x2f8);
+
+    // If the ConsoleOutControl entry cannot be updated, the token is left
+    // alone.
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    apcb.insert_group(GroupId::Memory, *b"MEMG").unwrap();
+    apcb.upsert_entry(
+        EntryId::Memory(MemoryEntryId::ConsoleOutControl),
+        0,
+        board_instance_mask,
+        ContextType::Struct,
+        PriorityLevels::from_level(PriorityLevel::Normal),
+        &[0u8; 4],
+    )
+    .unwrap();
+    assert!(matches!(
+        apcb.configure_serial_console(0b10, BaudRate::_9600Baud, 0x3f8),
+        Err(Error::EntryTypeMismatch)
+    ));
+    assert!(apcb.group(GroupId::Token).unwrap().is_none());
+
+    // If there is no space for the token, the existing ConsoleOutControl
+    // entry is left alone.
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    apcb.insert_group(GroupId::Memory, *b"MEMG").unwrap();
+    let mut console_out_control = ConsoleOutControl::default();
+    console_out_control
+        .abl_console_out_control
+        .set_enable_console_logging(false);
+    console_out_control.abl_console_out_control.set_abl_console_port(0x3f8);
+    apcb.insert_struct_entry(
+        EntryId::Memory(MemoryEntryId::ConsoleOutControl),
+        0,
+        board_instance_mask,
+        PriorityLevels::from_level(PriorityLevel::Normal),
+        &console_out_control,
+        &[],
+    )
+    .unwrap();
+    let mut buf = vec![0xFFu8; apcb.used_size().unwrap()];
+    apcb.compact_into(&mut buf).unwrap();
+    let mut apcb =
+        Apcb::load(Cow::from(buf), &ApcbIoOptions::default()).unwrap();
+    let original = apcb.clone();
+    assert!(matches!(
+        apcb.configure_serial_console(0b10, BaudRate::_9600Baud, 0x2f8),
+        Err(Error::OutOfSpace)
+    ));
+    assert!(apcb.group(GroupId::Token).unwrap().is_none());
+    assert!(apcb.content_eq(&original));
+
+    // If there is only space for the ConsoleOutControl entry, it is removed
+    // again (and so is its group).
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    apcb.insert_group(GroupId::Memory, *b"MEMG").unwrap();
+    apcb.insert_struct_entry(
+        EntryId::Memory(MemoryEntryId::ConsoleOutControl),
+        0,
+        board_instance_mask,
+        PriorityLevels::from_level(PriorityLevel::Normal),
+        &console_out_control,
+        &[],
+    )
+    .unwrap();
+    let size = apcb.used_size().unwrap();
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; size]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    let original = apcb.clone();
+    assert!(matches!(
+        apcb.configure_serial_console(0b10, BaudRate::_9600Baud, 0x2f8),
+        Err(Error::OutOfSpace)
+    ));
+    assert!(apcb.group(GroupId::Memory).unwrap().is_none());
+    assert!(apcb.group(GroupId::Token).unwrap().is_none());
+    assert!(apcb.content_eq(&original));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_abl_serial_baud_rate() {
+    use amd_apcb::{
+        Apcb, ApcbIoOptions, BaudRate, BoardInstances, GroupId, PriorityLevel,
+        PriorityLevels,
+    };
+    use std::borrow::Cow;
+
+    let mut apcb = Apcb::create(
+        Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+        42,
+        &ApcbIoOptions::default(),
+    )
+    .unwrap();
+    apcb.insert_group(GroupId::Token, *b"TOKN").unwrap();
+    let mut tokens = apcb
+        .tokens_mut(
+            0,
+            BoardInstances::all(),
+            PriorityLevels::from_level(PriorityLevel::Normal),
+            None,
+        )
+        .unwrap();
+    tokens.set_abl_serial_baud_rate(BaudRate::_3000000Baud).unwrap();
+    assert_eq!(tokens.abl_serial_baud_rate().unwrap(), BaudRate::_3000000Baud);
+    tokens.set_abl_serial_baud_rate(BaudRate::Unknown(42)).unwrap();
+
+    let buf = apcb.save().unwrap().into_owned();
+    let apcb = Apcb::load(Cow::from(buf), &ApcbIoOptions::default()).unwrap();
+    let tokens = apcb.tokens(0, BoardInstances::all()).unwrap();
+    assert_eq!(tokens.abl_serial_baud_rate().unwrap(), BaudRate::Unknown(42));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_content_eq() {
+    use amd_apcb::{
+        Apcb, ApcbIoOptions, BoardInstances, GroupId, PriorityLevel,
+        PriorityLevels,
+    };
+    use std::borrow::Cow;
+
+    let build = |unique_apcb_instance, token_group_first: bool, urg_first| {
+        let mut apcb = Apcb::create(
+            Cow::from(vec![0xFFu8; Apcb::MAX_SIZE]),
+            unique_apcb_instance,
+            &ApcbIoOptions::default(),
+        )
+        .unwrap();
+        if token_group_first {
+            apcb.insert_group(GroupId::Token, *b"TOKN").unwrap();
+            apcb.insert_group(GroupId::Memory, *b"MEMG").unwrap();
+        } else {
+            apcb.insert_group(GroupId::Memory, *b"MEMG").unwrap();
+            apcb.insert_group(GroupId::Token, *b"TOKN").unwrap();
+        }
+        let mut tokens = apcb
+            .tokens_mut(
+                0,
+                BoardInstances::from_instance(0).unwrap(),
+                PriorityLevels::from_level(PriorityLevel::Normal),
+                None,
+            )
+            .unwrap();
+        if urg_first {
+            tokens.set_mem_urg_ref_limit(4).unwrap();
+            tokens.set_mem_restore_valid_days(15).unwrap();
+        } else {
+            tokens.set_mem_restore_valid_days(15).unwrap();
+            tokens.set_mem_urg_ref_limit(4).unwrap();
+        }
+        let buf = apcb.save().unwrap().into_owned();
+        Apcb::load(Cow::from(buf), &ApcbIoOptions::default()).unwrap()
+    };
+    let a = build(42, true, true);
+    let b = build(7, false, false);
+    assert!(a.content_eq(&b));
+    assert!(b.content_eq(&a));
+
+    let mut c = b.clone();
+    c.tokens_mut(
+        0,
+        BoardInstances::from_instance(0).unwrap(),
+        PriorityLevels::from_level(PriorityLevel::Normal),
+        None,
+    )
+    .unwrap()
+    .set_mem_restore_valid_days(16)
+    .unwrap();
+    assert!(!a.content_eq(&c));
+    c.delete_group(GroupId::Memory).unwrap();
+    assert!(!c.content_eq(&a));
+}
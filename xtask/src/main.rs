@@ -166,10 +166,13 @@ fn tests(args: BuildArgs) {
         "build {locked} {build_type} {verbose} --features serde,schemars,serde-hex"
     );
     cmd(cargo(), args.split_whitespace()).run().expect("test successful");
+    let args =
+        format!("build {locked} {build_type} {verbose} --features serde-yaml");
+    cmd(cargo(), args.split_whitespace()).run().expect("test successful");
     let args = format!("build {locked} {build_type} {verbose} --features serde,schemars --example fromyaml");
     cmd(cargo(), args.split_whitespace()).run().expect("test successful");
     let args = format!(
-        "test {locked} {build_type} {verbose} --test * --features serde,schemars"
+        "test {locked} {build_type} {verbose} --test * --features serde,schemars,serde-yaml"
     );
     cmd(cargo(), args.split_whitespace()).run().expect("test successful");
 }